@@ -0,0 +1,604 @@
+//! Offline checker for `extended-framework` projects.
+//!
+//! Loads `index.html`, every `*.component.rs` definition and the referenced
+//! component templates, runs the framework compile step without an `App` and
+//! reports template problems as [`TemplateDiagnostic`]s.
+
+use bevy_extended_ui::component::{
+    ComponentDefinition, load_component_definitions, load_component_template_html,
+    validate_component_assets,
+};
+use bevy_extended_ui::framework::{
+    ExtendedFrameworkConfiguration, FrameworkCompileCache,
+    try_compile_framework_template_with_router_cached,
+};
+use bevy_extended_ui::html::converter::{
+    HTML_EVENT_ATTRIBUTES, TEMPLATE_USE_RE, preprocess_template_directives_with_diagnostics,
+    resolve_relative_asset_path,
+};
+use bevy_extended_ui::html::{TemplateDiagnostic, parse_html_inline_action};
+use bevy_extended_ui::lang::{UiLangVariables, UiSharedValues};
+use kuchiki::iter::NodeIterator;
+use kuchiki::traits::TendrilSink;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+static HTML_FN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"#\s*\[\s*(?:[\w:]+::)?html_fn\s*\(\s*=?\s*"([^"]+)"\s*\)\s*\]"#).unwrap()
+});
+static HTML_FN_REGISTRATION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"HtmlFnRegistration::\w+\s*\{\s*name\s*:\s*"([^"]+)""#).unwrap());
+static TYPE_DECL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?(?:struct|enum)\s+([A-Za-z_][A-Za-z0-9_]*)\b"#)
+        .unwrap()
+});
+static PROVIDER_TAG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"fn\s+tag\s*\(\s*&self\s*\)\s*->\s*&'static\s+str\s*\{\s*"([^"]+)""#).unwrap()
+});
+static FOR_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"@for\s*\(\s*([A-Za-z_][A-Za-z0-9_]*)(?:\s*,\s*([A-Za-z_][A-Za-z0-9_]*))?\s+in\s")
+        .unwrap()
+});
+static MOUSTACHE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)\{\{\s*([^{}]+?)\s*\}\}").unwrap());
+static ROOT_IDENT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^!?\s*([A-Za-z_][A-Za-z0-9_]*)").unwrap());
+static FTL_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^([A-Za-z][A-Za-z0-9_.-]*)\s*=").unwrap());
+static PROPERTIES_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*([A-Za-z][A-Za-z0-9_.-]*)\s*[=:]").unwrap());
+
+/// Hyphenated tags understood by the converter and built-in providers.
+const BUILTIN_CUSTOM_TAGS: &[&str] = &[
+    "date-picker",
+    "tool-tip",
+    "dialog-header",
+    "dialog-body",
+    "dialog-footer",
+    "router-outlet",
+    "theme-provider",
+];
+
+/// Input paths for a check run.
+///
+/// - `asset_root`: folder that contains `index.html` (the Bevy `assets/` folder).
+/// - `components_path`: component folder inside `asset_root`.
+/// - `rust_component_root`: folder with `*.component.rs`; derived from the
+///   component folder when `None`, like the runtime converter does.
+/// - `source_roots`: Rust sources scanned for `#[html_fn]`, `HtmlFnRegistration`
+///   entries, shared types and providers; the component root is always scanned too.
+/// - `language_path`: translation files whose keys may appear as `{{ key }}`.
+/// - `allowed_handlers`: handler names registered at runtime, e.g. directly in
+///   `HtmlFunctionRegistry`, that the source scan cannot see.
+#[derive(Debug, Clone)]
+pub struct CheckConfig {
+    pub asset_root: PathBuf,
+    pub components_path: String,
+    pub rust_component_root: Option<PathBuf>,
+    pub source_roots: Vec<PathBuf>,
+    pub language_path: PathBuf,
+    pub allowed_tags: Vec<String>,
+    pub allowed_handlers: Vec<String>,
+}
+
+impl Default for CheckConfig {
+    /// Uses the same defaults as `ExtendedUiConfiguration`.
+    fn default() -> Self {
+        Self {
+            asset_root: PathBuf::from("assets"),
+            components_path: String::from("components"),
+            rust_component_root: None,
+            source_roots: vec![PathBuf::from("src")],
+            language_path: PathBuf::from("assets/lang"),
+            allowed_tags: Vec::new(),
+            allowed_handlers: Vec::new(),
+        }
+    }
+}
+
+impl CheckConfig {
+    /// Builds the framework configuration used by the compile step.
+    pub fn framework_configuration(&self) -> ExtendedFrameworkConfiguration {
+        let components_path = self.components_path.trim_matches(['/', '\\']).to_string();
+        let rust_component_root = self
+            .rust_component_root
+            .clone()
+            .unwrap_or_else(|| self.asset_root.join(&components_path));
+
+        ExtendedFrameworkConfiguration {
+            assets_component_root: components_path,
+            rust_component_root: rust_component_root.to_string_lossy().to_string(),
+            asset_root_fs_path: self.asset_root.to_string_lossy().to_string(),
+            index_html_file: "index.html".to_string(),
+        }
+    }
+}
+
+/// Collected result of a check run.
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    pub diagnostics: Vec<TemplateDiagnostic>,
+    pub checked_files: usize,
+}
+
+impl CheckReport {
+    /// Returns the number of error diagnostics.
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_error())
+            .count()
+    }
+
+    /// Returns the number of warning diagnostics.
+    pub fn warning_count(&self) -> usize {
+        self.diagnostics.len() - self.error_count()
+    }
+
+    /// Returns `true` when at least one error was reported.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.is_error())
+    }
+
+    fn push(&mut self, diagnostic: TemplateDiagnostic) {
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+/// Names collected from Rust sources and translation files.
+#[derive(Debug, Default)]
+struct ProjectIndex {
+    handlers: HashSet<String>,
+    types: HashSet<String>,
+    roots: HashSet<String>,
+    custom_tags: HashSet<String>,
+    translation_keys: HashSet<String>,
+}
+
+impl ProjectIndex {
+    fn scan(config: &CheckConfig, rust_component_root: &Path) -> Self {
+        let mut index = Self::default();
+
+        let mut rust_files = Vec::new();
+        for root in &config.source_roots {
+            collect_files(root, &["rs"], &mut rust_files);
+        }
+        if !config
+            .source_roots
+            .iter()
+            .any(|root| rust_component_root.starts_with(root))
+        {
+            collect_files(rust_component_root, &["rs"], &mut rust_files);
+        }
+        for file in rust_files {
+            let Ok(text) = fs::read_to_string(&file) else {
+                continue;
+            };
+            for captures in HTML_FN_RE
+                .captures_iter(&text)
+                .chain(HTML_FN_REGISTRATION_RE.captures_iter(&text))
+            {
+                index.handlers.insert(captures[1].to_string());
+            }
+            for captures in TYPE_DECL_RE.captures_iter(&text) {
+                let name = captures[1].to_string();
+                index.roots.insert(to_template_alias(&name));
+                index.roots.insert(name.clone());
+                index.types.insert(name);
+            }
+            for captures in PROVIDER_TAG_RE.captures_iter(&text) {
+                index.custom_tags.insert(captures[1].to_ascii_lowercase());
+            }
+        }
+
+        let mut lang_files = Vec::new();
+        collect_files(
+            &config.language_path,
+            &["ftl", "properties"],
+            &mut lang_files,
+        );
+        for file in lang_files {
+            let Ok(text) = fs::read_to_string(&file) else {
+                continue;
+            };
+            let key_re = if file.extension().is_some_and(|ext| ext == "ftl") {
+                &FTL_KEY_RE
+            } else {
+                &PROPERTIES_KEY_RE
+            };
+            for captures in key_re.captures_iter(&text) {
+                index.translation_keys.insert(captures[1].to_string());
+            }
+        }
+
+        index.custom_tags.extend(
+            config
+                .allowed_tags
+                .iter()
+                .map(|tag| tag.to_ascii_lowercase()),
+        );
+        index
+            .handlers
+            .extend(config.allowed_handlers.iter().cloned());
+        index
+    }
+}
+
+/// Runs every check for the project described by `config`.
+pub fn check_project(config: &CheckConfig) -> CheckReport {
+    let mut report = CheckReport::default();
+    let framework = config.framework_configuration();
+
+    let index_path = config.asset_root.join(&framework.index_html_file);
+    let index_html = match fs::read_to_string(&index_path) {
+        Ok(html) => html,
+        Err(err) => {
+            report.push(TemplateDiagnostic::error(
+                index_path.to_string_lossy(),
+                "",
+                0,
+                format!("failed to read framework entrypoint: {err}"),
+            ));
+            return report;
+        }
+    };
+
+    let project = ProjectIndex::scan(config, Path::new(&framework.rust_component_root));
+    let definitions = match load_component_definitions(&framework) {
+        Ok(definitions) => definitions,
+        Err(err) => {
+            report.push(TemplateDiagnostic::error(
+                &framework.rust_component_root,
+                "",
+                0,
+                err,
+            ));
+            return report;
+        }
+    };
+
+    let assets_valid = match validate_component_assets(&definitions, &framework) {
+        Ok(()) => true,
+        Err(err) => {
+            report.push(TemplateDiagnostic::error(
+                &framework.rust_component_root,
+                "",
+                0,
+                err,
+            ));
+            false
+        }
+    };
+
+    if assets_valid {
+        let mut cache = FrameworkCompileCache::default();
+        if let Err(diagnostic) = try_compile_framework_template_with_router_cached(
+            &index_html,
+            &framework.index_html_file,
+            &framework,
+            None,
+            &mut cache,
+        ) {
+            report.push(diagnostic);
+        }
+    }
+
+    let component_tags = definitions
+        .iter()
+        .map(|definition| definition.template_name.to_ascii_lowercase())
+        .collect::<HashSet<_>>();
+
+    let mut templates = vec![(framework.index_html_file.clone(), index_html)];
+    for definition in &definitions {
+        if let Ok(html) = load_component_template_html(definition, &framework) {
+            templates.push((component_template_path(&framework, definition), html));
+        }
+    }
+
+    for (path, source) in &templates {
+        check_template(path, source, config, &project, &component_tags, &mut report);
+    }
+    report.checked_files = templates.len();
+
+    report
+}
+
+/// Checks one template file for directive, tag, handler, binding and stylesheet problems.
+fn check_template(
+    path: &str,
+    source: &str,
+    config: &CheckConfig,
+    project: &ProjectIndex,
+    component_tags: &HashSet<String>,
+    report: &mut CheckReport,
+) {
+    // Unknown @use targets are runtime warnings there; they are checked against
+    // the scanned sources below instead.
+    let (_, directive_diagnostics) = preprocess_template_directives_with_diagnostics(
+        source,
+        &UiLangVariables::default(),
+        &UiSharedValues::default(),
+        &Vec::<String>::new(),
+        path,
+    );
+    for diagnostic in directive_diagnostics {
+        if diagnostic.is_error() {
+            report.push(diagnostic);
+        }
+    }
+
+    let mut roots = project.roots.clone();
+    let mut imports_fields = false;
+    for captures in TEMPLATE_USE_RE.captures_iter(source) {
+        let Some(target) = captures.get(1) else {
+            continue;
+        };
+        let alias = captures.get(2).map(|alias| alias.as_str());
+        imports_fields |= alias == Some("*");
+
+        for (type_path, item_alias) in expand_use_target(target.as_str()) {
+            if type_path.ends_with('*') {
+                continue;
+            }
+
+            let type_name = type_path.rsplit("::").next().unwrap_or(&type_path).trim();
+            if !project.types.contains(type_name) {
+                report.push(TemplateDiagnostic::error(
+                    path,
+                    source,
+                    target.start(),
+                    format!("unknown @use target `{type_path}`"),
+                ));
+            }
+            match item_alias.as_deref().or(alias) {
+                Some("*") | None => {
+                    roots.insert(to_template_alias(type_name));
+                }
+                Some(alias) => {
+                    roots.insert(alias.to_string());
+                }
+            }
+        }
+    }
+    for captures in FOR_HEADER_RE.captures_iter(source) {
+        roots.insert(captures[1].to_string());
+        if let Some(index_name) = captures.get(2) {
+            roots.insert(index_name.as_str().to_string());
+        }
+    }
+
+    let document = kuchiki::parse_html().one(source);
+    // Elements come in source order, so each start tag is searched after the previous one.
+    let mut element_cursor = 0;
+    for element in document.descendants().elements() {
+        let tag = element.name.local.to_string();
+        let element_offset = find_offset(source, &format!("<{tag}"), element_cursor);
+        if let Some(offset) = element_offset {
+            element_cursor = offset + 1;
+        }
+        // Implied elements (`<html>`, `<body>`) have no tag of their own in the source.
+        let element_offset = element_offset.unwrap_or(element_cursor);
+
+        if tag.contains('-')
+            && !BUILTIN_CUSTOM_TAGS.contains(&tag.as_str())
+            && !component_tags.contains(&tag)
+            && !project.custom_tags.contains(&tag)
+        {
+            report.push(TemplateDiagnostic::error(
+                path,
+                source,
+                element_offset,
+                format!("unknown component tag `<{tag}>`"),
+            ));
+        }
+
+        let attributes = element.attributes.borrow();
+        for (name, attribute) in attributes.map.iter() {
+            let name = name.local.to_string();
+            let event = name.split('.').next().unwrap_or(&name);
+            let shortcut_action = tag == "shortcut" && name == "action";
+            if HTML_EVENT_ATTRIBUTES.contains(&event) || shortcut_action {
+                let offset = find_offset(source, &name, element_offset).unwrap_or(element_offset);
+                check_event_attribute(
+                    path,
+                    source,
+                    offset,
+                    &name,
+                    &attribute.value,
+                    project,
+                    report,
+                );
+            }
+        }
+
+        let is_stylesheet = tag == "link"
+            && attributes
+                .get("rel")
+                .is_some_and(|rel| rel.eq_ignore_ascii_case("stylesheet"));
+        if let Some(href) = attributes
+            .get("href")
+            .filter(|href| is_stylesheet && !href.contains("://"))
+        {
+            let resolved = resolve_relative_asset_path(path, href);
+            if !Path::new(&resolved).is_absolute() && !config.asset_root.join(&resolved).exists() {
+                report.push(TemplateDiagnostic::error(
+                    path,
+                    source,
+                    find_offset(source, href, element_offset).unwrap_or(element_offset),
+                    format!("stylesheet `{href}` not found (resolved to `{resolved}`)"),
+                ));
+            }
+        }
+    }
+
+    // `@use "Type" as *` imports fields we cannot see offline.
+    if imports_fields {
+        return;
+    }
+
+    for captures in MOUSTACHE_RE.captures_iter(source) {
+        let Some(expression) = captures.get(1) else {
+            continue;
+        };
+        let expression_text = expression.as_str().trim();
+        if project.translation_keys.contains(expression_text) {
+            continue;
+        }
+        let Some(root) = ROOT_IDENT_RE
+            .captures(expression_text)
+            .and_then(|root| root.get(1))
+        else {
+            continue;
+        };
+        let root = root.as_str();
+        if matches!(root, "true" | "false" | "null") || roots.contains(root) {
+            continue;
+        }
+
+        // Values may still come from `UiLangVariables` at runtime, so this is a warning.
+        report.push(TemplateDiagnostic::warning(
+            path,
+            source,
+            expression.start(),
+            format!("unresolved binding `{{{{ {expression_text} }}}}`: no value named `{root}`"),
+        ));
+    }
+}
+
+/// Validates one `on*` attribute against registered handlers or the inline syntax.
+fn check_event_attribute(
+    path: &str,
+    source: &str,
+    offset: usize,
+    attribute: &str,
+    value: &str,
+    project: &ProjectIndex,
+    report: &mut CheckReport,
+) {
+    let value = value.trim();
+    if value.is_empty() || value.contains("{{") {
+        return;
+    }
+
+    if value.starts_with('$') {
+        if let Err(err) = parse_html_inline_action(value) {
+            report.push(TemplateDiagnostic::error(
+                path,
+                source,
+                offset,
+                format!("invalid inline action in `{attribute}`: {err}"),
+            ));
+        }
+        return;
+    }
+
+    // Handlers may also be registered at runtime, which the source scan cannot
+    // see, so an unknown name is only a warning.
+    let handler = value.split('(').next().unwrap_or(value).trim();
+    if !project.handlers.contains(handler) {
+        report.push(TemplateDiagnostic::warning(
+            path,
+            source,
+            offset,
+            format!(
+                "{attribute} handler `{handler}` is not registered via #[html_fn(...)] or --allow-handler"
+            ),
+        ));
+    }
+}
+
+/// Splits grouped `@use "path::{A, B as b}"` targets into `(type path, alias)` pairs.
+fn expand_use_target(target: &str) -> Vec<(String, Option<String>)> {
+    let target = target.trim();
+    let Some((prefix, group)) = target
+        .strip_suffix('}')
+        .and_then(|rest| rest.split_once("::{"))
+    else {
+        return vec![(target.to_string(), None)];
+    };
+
+    group
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (name, alias) = match item.split_once(" as ") {
+                Some((name, alias)) => (name.trim(), Some(alias.trim().to_string())),
+                None => (item, None),
+            };
+            let path = if name == "self" {
+                prefix.to_string()
+            } else {
+                format!("{prefix}::{name}")
+            };
+            (path, alias)
+        })
+        .collect()
+}
+
+/// Returns the asset-relative path of a component template for diagnostics.
+fn component_template_path(
+    config: &ExtendedFrameworkConfiguration,
+    definition: &ComponentDefinition,
+) -> String {
+    [
+        config.assets_component_root.as_str(),
+        definition.source_dir_rel.as_str(),
+        definition.template_file.as_str(),
+    ]
+    .iter()
+    .map(|part| part.trim_matches('/'))
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Returns the byte offset of the first case-insensitive `needle` match at or after `from`.
+fn find_offset(source: &str, needle: &str, from: usize) -> Option<usize> {
+    let haystack = source.get(from..)?.to_ascii_lowercase();
+    haystack
+        .find(&needle.to_ascii_lowercase())
+        .map(|offset| from + offset)
+}
+
+/// Recursively collects files with one of the given extensions.
+fn collect_files(root: &Path, extensions: &[&str], out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, extensions, out);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext))
+        {
+            out.push(path);
+        }
+    }
+}
+
+fn to_template_alias(type_name: &str) -> String {
+    let mut out = String::new();
+    for (index, ch) in type_name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if index != 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
@@ -0,0 +1,80 @@
+use beu_check::{CheckConfig, check_project};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: beu-check [OPTIONS]
+
+Checks an extended-framework project without starting the game.
+
+Options:
+  --assets <DIR>        Asset root containing index.html [default: assets]
+  --components <PATH>   Component folder inside the asset root [default: components]
+  --rust-root <DIR>     Folder with *.component.rs files [default: <assets>/<components>]
+  --src <DIR>           Rust sources scanned for #[html_fn] and shared types; repeatable [default: src]
+  --lang <DIR>          Translation folder for {{ key }} placeholders [default: assets/lang]
+  --allow-tag <TAG>     Accept an additional custom tag; repeatable
+  --allow-handler <FN>  Accept a handler registered at runtime; repeatable
+  --deny-warnings       Exit non-zero on warnings too
+  -h, --help            Print this help";
+
+fn main() -> ExitCode {
+    let mut config = CheckConfig::default();
+    let mut custom_sources = Vec::new();
+    let mut deny_warnings = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{name}`"))
+        };
+
+        let result = match arg.as_str() {
+            "--assets" => value(&arg).map(|dir| config.asset_root = PathBuf::from(dir)),
+            "--components" => value(&arg).map(|path| config.components_path = path),
+            "--rust-root" => {
+                value(&arg).map(|dir| config.rust_component_root = Some(PathBuf::from(dir)))
+            }
+            "--src" => value(&arg).map(|dir| custom_sources.push(PathBuf::from(dir))),
+            "--lang" => value(&arg).map(|dir| config.language_path = PathBuf::from(dir)),
+            "--allow-tag" => value(&arg).map(|tag| config.allowed_tags.push(tag)),
+            "--allow-handler" => value(&arg).map(|name| config.allowed_handlers.push(name)),
+            "--deny-warnings" => {
+                deny_warnings = true;
+                Ok(())
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            other => Err(format!("unknown argument `{other}`")),
+        };
+
+        if let Err(err) = result {
+            eprintln!("beu-check: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    if !custom_sources.is_empty() {
+        config.source_roots = custom_sources;
+    }
+
+    let report = check_project(&config);
+    for diagnostic in &report.diagnostics {
+        eprintln!("{diagnostic}");
+    }
+    eprintln!(
+        "beu-check: {} file(s) checked, {} error(s), {} warning(s)",
+        report.checked_files,
+        report.error_count(),
+        report.warning_count()
+    );
+
+    if report.has_errors() || (deny_warnings && report.warning_count() > 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -1,10 +1,11 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
-    Data, DeriveInput, FnArg, GenericArgument, Item, ItemEnum, ItemFn, ItemStruct, ItemUse, LitStr,
-    PathArguments, Result, Type, TypePath, UseTree,
+    Data, DeriveInput, FnArg, GenericArgument, ImplItem, Item, ItemEnum, ItemFn, ItemImpl,
+    ItemStruct, ItemUse, LitStr, PathArguments, Result, Token, Type, TypePath, UseTree, bracketed,
     parse::{Parse, ParseStream},
     parse_macro_input,
+    punctuated::Punctuated,
     token::Eq,
 };
 
@@ -30,10 +31,17 @@ impl Parse for HtmlFnAttr {
 }
 
 /// Registers a function as an HTML event handler.
+///
+/// A parameter marked `#[args]` receives the arguments of
+/// `onclick="handler(a, b)"`, deserialized into its type, e.g.
+/// `#[args] (id, slot): (u32, String)`.
 #[proc_macro_attribute]
 pub fn html_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
     let HtmlFnAttr { name } = parse_macro_input!(attr as HtmlFnAttr);
-    let input_fn = parse_macro_input!(item as ItemFn);
+    let mut input_fn = parse_macro_input!(item as ItemFn);
+    if let Err(err) = expand_html_fn_args(&mut input_fn, &name) {
+        return err.to_compile_error().into();
+    }
 
     let fn_ident = input_fn.sig.ident.clone();
     let builder_ident = format_ident!("__html_fn_build_{}", fn_ident);
@@ -67,6 +75,55 @@ pub fn html_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+/// Replaces the `#[args]` parameter with `Res<HtmlEventArgs>` and
+/// deserializes it at the start of the body.
+fn expand_html_fn_args(input_fn: &mut ItemFn, name: &LitStr) -> Result<()> {
+    let mut args_param = None;
+    let mut inputs = Punctuated::<FnArg, Token![,]>::new();
+    for arg in std::mem::take(&mut input_fn.sig.inputs) {
+        let FnArg::Typed(mut pat_type) = arg else {
+            inputs.push(arg);
+            continue;
+        };
+        let before = pat_type.attrs.len();
+        pat_type.attrs.retain(|attr| !attr.path().is_ident("args"));
+        if pat_type.attrs.len() == before {
+            inputs.push(FnArg::Typed(pat_type));
+            continue;
+        }
+        if args_param.is_some() {
+            return Err(syn::Error::new_spanned(
+                pat_type,
+                "only one #[args] parameter is allowed",
+            ));
+        }
+        args_param = Some(pat_type);
+    }
+    input_fn.sig.inputs = inputs;
+
+    let Some(args_param) = args_param else {
+        return Ok(());
+    };
+    let pat = &args_param.pat;
+    let ty = &args_param.ty;
+    input_fn.sig.inputs.push(syn::parse_quote! {
+        __html_event_args: bevy::prelude::Res<bevy_extended_ui::html::HtmlEventArgs>
+    });
+    input_fn.block.stmts.insert(
+        0,
+        syn::parse_quote! {
+            let #pat: #ty = match __html_event_args.parse::<#ty>() {
+                Ok(args) => args,
+                Err(err) => {
+                    bevy::log::warn!("HTML handler '{}' received invalid arguments: {}", #name, err);
+                    return;
+                }
+            };
+        },
+    );
+    Ok(())
+}
+
 /// Registers a component constructor that runs once during Bevy `Startup`.
 #[proc_macro_attribute]
 pub fn component_init(_attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -257,6 +314,12 @@ fn extract_event_type(input_fn: &ItemFn) -> Result<Option<(syn::Ident, Type)>> {
         "HtmlTouchStart" => format_ident!("HtmlTouchStart"),
         "HtmlTouchMove" => format_ident!("HtmlTouchMove"),
         "HtmlTouchEnd" => format_ident!("HtmlTouchEnd"),
+        "HtmlDoubleClick" => format_ident!("HtmlDoubleClick"),
+        "HtmlContextMenu" => format_ident!("HtmlContextMenu"),
+        "HtmlLongPress" => format_ident!("HtmlLongPress"),
+        "HtmlDragEnter" => format_ident!("HtmlDragEnter"),
+        "HtmlDragLeave" => format_ident!("HtmlDragLeave"),
+        "HtmlDrop" => format_ident!("HtmlDrop"),
         _ => {
             return Err(syn::Error::new_spanned(
                 &event_ident,
@@ -306,7 +369,10 @@ pub fn beu_routes(_attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 /// Derives a reactive UI binding store registration for a struct or enum.
-#[proc_macro_derive(BeuStore)]
+///
+/// `#[beu(persist = "settings.json")]` saves the store to disk on change and
+/// loads it at startup; the type must then also implement `Deserialize`.
+#[proc_macro_derive(BeuStore, attributes(beu))]
 pub fn derive_beu_store(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
 
@@ -334,8 +400,49 @@ fn expand_beu_store(input: DeriveInput) -> Result<proc_macro2::TokenStream> {
         ));
     }
 
+    let mut persist = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("beu") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("persist") {
+                persist = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported beu attribute; expected `persist = \"file.json\"`"))
+            }
+        })?;
+    }
+
     let ident = input.ident;
     let register_ident = format_ident!("__beu_store_register_{}", ident);
+    let load_ident = format_ident!("__beu_store_load_{}", ident);
+
+    let (persist_tokens, load_fn) = match persist {
+        Some(path) => (
+            quote! {
+                Some(bevy_extended_ui::framework::UiBindingStorePersist {
+                    path: #path,
+                    load: #load_ident,
+                })
+            },
+            quote! {
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                fn #load_ident(
+                    store: &mut bevy_extended_ui::framework::UiBindingStore,
+                    json: bevy_extended_ui::lang::serde_json::Value,
+                ) -> Result<(), String> {
+                    let value = bevy_extended_ui::lang::serde_json::from_value::<#ident>(json)
+                        .map_err(|err| err.to_string())?;
+                    store.set_store(value);
+                    Ok(())
+                }
+            },
+        ),
+        None => (quote! { None }, quote! {}),
+    };
 
     Ok(quote! {
         impl bevy_extended_ui::framework::BeuStore for #ident {
@@ -357,11 +464,140 @@ fn expand_beu_store(input: DeriveInput) -> Result<proc_macro2::TokenStream> {
                 key: stringify!(#ident),
                 path: concat!(module_path!(), "::", stringify!(#ident)),
                 register: #register_ident,
+                persist: #persist_tokens,
             }
         }
+
+        #load_fn
     })
 }
 
+/// Parsed arguments of a `#[computed(deps = [...])]` method attribute.
+struct ComputedAttr {
+    deps: Vec<LitStr>,
+}
+
+impl Parse for ComputedAttr {
+    /// Parses `deps = ["a", "b.c"]`; an empty attribute means no dependencies.
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(Self { deps: Vec::new() });
+        }
+
+        let key: syn::Ident = input.parse()?;
+        if key != "deps" {
+            return Err(syn::Error::new_spanned(key, "expected `deps = [...]`"));
+        }
+        let _eq: Eq = input.parse()?;
+        let content;
+        bracketed!(content in input);
+        let deps = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Self {
+            deps: deps.into_iter().collect(),
+        })
+    }
+}
+
+/// Registers `#[computed(deps = [...])]` methods of a `BeuStore` as computed fields.
+///
+/// The store type must also implement `Deserialize`, because computed values
+/// are derived from the same JSON that templates read.
+#[proc_macro_attribute]
+pub fn beu_computed(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as ItemImpl);
+
+    match expand_beu_computed(&mut input) {
+        Ok(registrations) => quote! { #input #registrations }.into(),
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote! { #err #input }.into()
+        }
+    }
+}
+
+fn expand_beu_computed(input: &mut ItemImpl) -> Result<proc_macro2::TokenStream> {
+    let self_ty = input.self_ty.clone();
+    let Some(type_name) = simple_type_name_from_type(&self_ty) else {
+        return Err(syn::Error::new_spanned(
+            &self_ty,
+            "#[beu_computed] requires a named store type",
+        ));
+    };
+
+    let mut registrations = Vec::new();
+    for impl_item in &mut input.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+
+        let mut computed = None;
+        let mut parse_error = None;
+        method.attrs.retain(|attr| {
+            if !attr.path().is_ident("computed") {
+                return true;
+            }
+            let parsed = if matches!(attr.meta, syn::Meta::Path(_)) {
+                Ok(ComputedAttr { deps: Vec::new() })
+            } else {
+                attr.parse_args::<ComputedAttr>()
+            };
+            match parsed {
+                Ok(parsed) => computed = Some(parsed),
+                Err(err) => parse_error = Some(err),
+            }
+            false
+        });
+        if let Some(err) = parse_error {
+            return Err(err);
+        }
+
+        let Some(ComputedAttr { deps }) = computed else {
+            continue;
+        };
+
+        let takes_ref_self = method.sig.inputs.len() == 1
+            && matches!(
+                method.sig.inputs.first(),
+                Some(FnArg::Receiver(receiver))
+                    if receiver.reference.is_some() && receiver.mutability.is_none()
+            );
+        if !takes_ref_self {
+            return Err(syn::Error::new_spanned(
+                &method.sig,
+                "computed fields must take `&self` and no other arguments",
+            ));
+        }
+
+        let method_ident = method.sig.ident.clone();
+        let name_lit = LitStr::new(&method_ident.to_string(), method_ident.span());
+        let compute_ident = format_ident!("__beu_computed_{}_{}", type_name, method_ident);
+
+        registrations.push(quote! {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            fn #compute_ident(
+                json: &bevy_extended_ui::lang::serde_json::Value,
+            ) -> Option<bevy_extended_ui::lang::serde_json::Value> {
+                let value: #self_ty =
+                    bevy_extended_ui::lang::serde_json::from_value(json.clone()).ok()?;
+                bevy_extended_ui::lang::serde_json::to_value(value.#method_ident()).ok()
+            }
+
+            bevy_extended_ui::framework::inventory::submit! {
+                bevy_extended_ui::framework::UiBindingComputedRegistration {
+                    key: <#self_ty as bevy_extended_ui::framework::BeuStore>::STORE_KEY,
+                    name: #name_lit,
+                    deps: &[#(#deps),*],
+                    compute: #compute_ident,
+                }
+            }
+        });
+    }
+
+    Ok(quote! { #(#registrations)* })
+}
+
 /// Registers a typed item as shared template state (`@use "Type" as alias;`).
 #[proc_macro_attribute]
 pub fn html_shared(_attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -66,6 +66,7 @@ fn load_framework_index(
         handle,
         source_id: "framework-index".to_string(),
         controller: None,
+        virtual_path: None,
     });
 }
 
@@ -1157,6 +1157,31 @@ fn on_dialog_trigger_press(
     }
 }
 
+/// Opens every dialog whose CSS id matches `id` (a leading `#` is ignored).
+///
+/// Returns `false` when no dialog matched.
+pub(crate) fn open_dialog_by_id(world: &mut World, id: &str) -> bool {
+    let id = normalize_trigger_id(id);
+    let mut dialogs = world.query::<(&CssID, &mut DialogWidget)>();
+    let mut opened = false;
+
+    for (css_id, mut dialog) in dialogs.iter_mut(world) {
+        if normalize_trigger_id(&css_id.0) != id {
+            continue;
+        }
+        opened = true;
+        match dialog.renderer {
+            // `sync_dialog_widget_visibility` shows the changed widget.
+            DialogProvider::BevyApp => dialog.open = true,
+            DialogProvider::System => {
+                let _ = show_system_message(dialog.dialog_type, &dialog.content_text);
+            }
+        }
+    }
+
+    opened
+}
+
 /// Handles `on_dialog_widget_overlay_click` in the extended UI workflow.
 fn on_dialog_widget_overlay_click(
     mut trigger: On<Pointer<Click>>,
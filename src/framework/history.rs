@@ -0,0 +1,195 @@
+use serde_json::Value as JsonValue;
+
+use super::UiBindingStore;
+
+/// State of a store key before or after a recorded edit.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum UiBindingSnapshot {
+    /// The key was not in the store.
+    Missing,
+    /// The key was registered but held no JSON value.
+    Empty,
+    Value(JsonValue),
+}
+
+impl UiBindingSnapshot {
+    /// Captures the current state of `key`.
+    pub(super) fn of(store: &UiBindingStore, key: &str) -> Self {
+        match store.data.get(key) {
+            None => Self::Missing,
+            Some(entry) => entry
+                .json()
+                .cloned()
+                .map_or(Self::Empty, Self::Value),
+        }
+    }
+}
+
+/// One recorded change of a store key, before and after the edit.
+#[derive(Debug, Clone)]
+struct UiBindingChange {
+    key: String,
+    before: UiBindingSnapshot,
+    after: UiBindingSnapshot,
+}
+
+/// Opt-in undo/redo log of [`UiBindingStore::set_path_json`] edits.
+#[derive(Debug, Default)]
+pub(crate) struct UiBindingHistory {
+    limit: usize,
+    undo: Vec<Vec<UiBindingChange>>,
+    redo: Vec<Vec<UiBindingChange>>,
+    group: Vec<UiBindingChange>,
+    group_depth: usize,
+}
+
+impl UiBindingHistory {
+    /// Pushes a finished step and drops the oldest steps above the limit.
+    fn push_step(&mut self, step: Vec<UiBindingChange>) {
+        if step.is_empty() {
+            return;
+        }
+        self.undo.push(step);
+        self.redo.clear();
+        if self.undo.len() > self.limit {
+            let overflow = self.undo.len() - self.limit;
+            self.undo.drain(..overflow);
+        }
+    }
+}
+
+impl UiBindingStore {
+    /// Starts recording `set_path_json` edits so they can be undone.
+    ///
+    /// `limit` is the maximum number of undo steps kept. Calling this again
+    /// only changes the limit.
+    pub fn enable_history(&mut self, limit: usize) {
+        let history = self.history.get_or_insert_with(UiBindingHistory::default);
+        history.limit = limit.max(1);
+    }
+
+    /// Stops recording edits and drops the undo/redo log.
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Returns `true` when [`UiBindingStore::undo`] has a step to revert.
+    pub fn can_undo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| !history.undo.is_empty())
+    }
+
+    /// Returns `true` when [`UiBindingStore::redo`] has a step to reapply.
+    pub fn can_redo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| !history.redo.is_empty())
+    }
+
+    /// Groups all edits until the matching [`UiBindingStore::end_group`]
+    /// into a single undo step. Groups may be nested.
+    pub fn begin_group(&mut self) {
+        if let Some(history) = self.history.as_mut() {
+            history.group_depth += 1;
+        }
+    }
+
+    /// Closes the group opened by [`UiBindingStore::begin_group`].
+    pub fn end_group(&mut self) {
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+        if history.group_depth == 0 {
+            return;
+        }
+
+        history.group_depth -= 1;
+        if history.group_depth == 0 {
+            let step = std::mem::take(&mut history.group);
+            history.push_step(step);
+        }
+    }
+
+    /// Reverts the last undo step. Returns `false` when there is none.
+    ///
+    /// Steps are not undone while a group is still open.
+    pub fn undo(&mut self) -> bool {
+        let Some(step) = self
+            .history
+            .as_mut()
+            .filter(|history| history.group_depth == 0)
+            .and_then(|history| history.undo.pop())
+        else {
+            return false;
+        };
+
+        self.replay(step.iter().rev(), |change| &change.before);
+        if let Some(history) = self.history.as_mut() {
+            history.redo.push(step);
+        }
+        true
+    }
+
+    /// Reapplies the last undone step. Returns `false` when there is none.
+    pub fn redo(&mut self) -> bool {
+        let Some(step) = self
+            .history
+            .as_mut()
+            .filter(|history| history.group_depth == 0)
+            .and_then(|history| history.redo.pop())
+        else {
+            return false;
+        };
+
+        self.replay(step.iter(), |change| &change.after);
+        if let Some(history) = self.history.as_mut() {
+            history.undo.push(step);
+        }
+        true
+    }
+
+    /// Records the change of `key` made by the last `set_path_json` call.
+    pub(super) fn record_history(&mut self, key: &str, before: UiBindingSnapshot) {
+        let after = UiBindingSnapshot::of(self, key);
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+
+        let change = UiBindingChange {
+            key: key.to_string(),
+            before,
+            after,
+        };
+        if history.group_depth > 0 {
+            history.group.push(change);
+        } else {
+            history.push_step(vec![change]);
+        }
+    }
+
+    /// Writes recorded values back without recording them again.
+    ///
+    /// Keys that did not exist are removed again instead of being set to `null`.
+    fn replay<'a>(
+        &mut self,
+        changes: impl Iterator<Item = &'a UiBindingChange>,
+        snapshot: impl Fn(&UiBindingChange) -> &UiBindingSnapshot,
+    ) {
+        let history = self.history.take();
+        for change in changes {
+            match snapshot(change) {
+                UiBindingSnapshot::Missing => {
+                    self.clear_key(&change.key, false);
+                }
+                UiBindingSnapshot::Empty => {
+                    self.clear_key(&change.key, true);
+                }
+                UiBindingSnapshot::Value(value) => {
+                    self.set_path_json(&change.key, value.clone());
+                }
+            }
+        }
+        self.history = history;
+    }
+}
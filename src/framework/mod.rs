@@ -13,11 +13,19 @@ use crate::component::{
     ComponentDefinition, load_component_definitions, load_component_template_html,
     validate_component_assets,
 };
+#[cfg(feature = "extended-framework")]
+use crate::html::TemplateDiagnostic;
 use crate::lang::UiSharedValues;
 #[cfg(feature = "extended-framework")]
 use crate::routing::Router;
 
+mod history;
+pub mod persist;
+
 pub use inventory;
+pub use persist::{
+    UiBindingPersistence, UiBindingStoreLoadFn, UiBindingStorePersist, save_persisted_stores,
+};
 
 /// Configuration for the experimental extended framework mode.
 ///
@@ -55,6 +63,19 @@ pub struct FrameworkCompileResult {
     pub html: String,
     pub inferred_controller: Option<String>,
     pub component_controllers: Vec<String>,
+    /// Unexpanded templates of every component inlined into `html`.
+    pub component_sources: Vec<FrameworkComponentSource>,
+}
+
+/// Component template as written on disk, before it was inlined into `index.html`.
+///
+/// Used to report template diagnostics against the component file instead of
+/// the expanded page.
+#[cfg(feature = "extended-framework")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameworkComponentSource {
+    pub source_path: String,
+    pub html: String,
 }
 
 #[cfg(feature = "extended-framework")]
@@ -78,7 +99,9 @@ impl Plugin for ExtendedFrameworkPlugin {
         app.init_resource::<ExtendedFrameworkConfiguration>();
         app.init_resource::<UiBindingStore>();
         app.init_resource::<FrameworkCompileCache>();
+        app.init_resource::<UiBindingPersistence>();
         app.add_systems(Startup, register_beu_stores);
+        app.add_systems(Last, save_persisted_stores);
     }
 }
 
@@ -99,10 +122,31 @@ pub struct UiBindingStoreRegistration {
     pub key: &'static str,
     pub path: &'static str,
     pub register: fn(&mut UiBindingStore),
+    pub persist: Option<UiBindingStorePersist>,
 }
 
 inventory::collect!(UiBindingStoreRegistration);
 
+/// Computes a derived field from a store's JSON projection.
+pub type UiBindingComputeFn = fn(&JsonValue) -> Option<JsonValue>;
+
+/// Runtime registration emitted by `#[beu_computed]` for each computed field.
+pub struct UiBindingComputedRegistration {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub deps: &'static [&'static str],
+    pub compute: UiBindingComputeFn,
+}
+
+inventory::collect!(UiBindingComputedRegistration);
+
+/// Computed field attached to a store key.
+struct UiBindingComputedField {
+    name: String,
+    deps: Vec<String>,
+    compute: UiBindingComputeFn,
+}
+
 /// Type-erased value storage for [`UiBindingStore`].
 pub trait UiBindingStoredValue: Any + Send + Sync {
     /// Returns the value as [`Any`] for typed downcasting.
@@ -207,6 +251,10 @@ impl UiBindingEntry {
 /// values, strings, booleans, structs, enums, and other serializable Rust values
 /// can share the same resource. The store maintains its own revision counter;
 /// the revision only changes when a setter stores a different value.
+///
+/// Besides the global revision, every changed JSON path (`Player.name`,
+/// `info.value`) gets its own revision so consumers can react only to the
+/// paths they display, see [`UiBindingStore::path_revision`].
 #[derive(Resource, Default)]
 pub struct UiBindingStore {
     /// Keyed store data. Use the provided setter methods so revisions stay
@@ -214,6 +262,13 @@ pub struct UiBindingStore {
     pub data: HashMap<String, UiBindingEntry>,
     known_types: HashSet<String>,
     revision: u64,
+    path_revisions: HashMap<String, u64>,
+    replaced_revisions: HashMap<String, u64>,
+    synced_revision: u64,
+    computed: HashMap<String, Vec<UiBindingComputedField>>,
+    history: Option<history::UiBindingHistory>,
+    /// Template names of cleared keys, removed from `UiSharedValues` on the next sync.
+    removed_keys: Vec<String>,
 }
 
 impl UiBindingStore {
@@ -252,6 +307,38 @@ impl UiBindingStore {
             },
         );
         self.bump_revision();
+        self.path_revisions.insert(key.to_string(), self.revision);
+        self.replaced_revisions
+            .insert(key.to_string(), self.revision);
+        true
+    }
+
+    /// Registers a computed field that is written into the JSON projection of `key`.
+    ///
+    /// `compute` runs whenever one of the dot-separated `deps` changed; with no
+    /// `deps` it runs on every change. Templates read the result like a normal
+    /// field. Returns `false` when the field was already registered.
+    pub fn register_computed(
+        &mut self,
+        key: &str,
+        name: &str,
+        deps: &[&str],
+        compute: UiBindingComputeFn,
+    ) -> bool {
+        let fields = self.computed.entry(key.to_string()).or_default();
+        if fields.iter().any(|field| field.name == name) {
+            return false;
+        }
+
+        fields.push(UiBindingComputedField {
+            name: name.to_string(),
+            deps: deps.iter().map(|dep| dep.to_string()).collect(),
+            compute,
+        });
+
+        if let Some(json) = self.data.get(key).and_then(UiBindingEntry::json).cloned() {
+            self.set_json_projection(key, json);
+        }
         true
     }
 
@@ -316,13 +403,26 @@ impl UiBindingStore {
     /// Direct primitive keys keep their registered Rust type (`bool`, strings,
     /// all common integer widths, `usize`/`isize`, `f32`, `f64`). Nested paths
     /// update the stored JSON projection so templates can react immediately.
+    ///
+    /// With [`UiBindingStore::enable_history`] every change becomes an undo step.
     pub fn set_path_json(&mut self, path: &str, value: JsonValue) -> bool {
         let Some((root, tail)) = split_binding_path(path) else {
             return false;
         };
         let root = self.resolve_binding_root(root);
-        let root = root.as_str();
+        let before = self
+            .history
+            .is_some()
+            .then(|| history::UiBindingSnapshot::of(self, &root));
+
+        let changed = self.set_resolved_path_json(&root, &tail, value);
+        if changed && let Some(before) = before {
+            self.record_history(&root, before);
+        }
+        changed
+    }
 
+    fn set_resolved_path_json(&mut self, root: &str, tail: &[String], value: JsonValue) -> bool {
         if tail.is_empty() {
             return self.set_direct_json(root, value);
         }
@@ -334,7 +434,7 @@ impl UiBindingStore {
             .cloned()
             .unwrap_or_else(|| JsonValue::Object(Default::default()));
 
-        if !set_json_tail(&mut root_json, &tail, value) {
+        if !set_json_tail(&mut root_json, tail, value) {
             return false;
         }
 
@@ -351,6 +451,59 @@ impl UiBindingStore {
         self.revision
     }
 
+    /// Returns the revision of the last change that affected a binding path.
+    ///
+    /// A path is affected when it, one of its parents, or one of its children
+    /// changed. Template aliases (`player.name`) resolve like in
+    /// [`UiBindingStore::json_path`]. Unknown paths return `0`.
+    pub fn path_revision(&self, path: &str) -> u64 {
+        let Some((root, tail)) = split_binding_path(path) else {
+            return 0;
+        };
+        let mut current = self.resolve_binding_root(root);
+        let mut revision = self.replaced_revisions.get(&current).copied().unwrap_or(0);
+        for segment in &tail {
+            current = format!("{current}.{segment}");
+            revision = revision.max(self.replaced_revisions.get(&current).copied().unwrap_or(0));
+        }
+
+        // Parents are recorded for every child change, so the exact path also
+        // covers changes below it.
+        revision.max(self.path_revisions.get(&current).copied().unwrap_or(0))
+    }
+
+    /// Returns all store paths that changed after `revision`, sorted.
+    ///
+    /// Paths use store keys as roots (`Player.name`); parents of a changed
+    /// path are included.
+    pub fn changed_paths_since(&self, revision: u64) -> Vec<&str> {
+        let mut paths = self
+            .path_revisions
+            .iter()
+            .filter(|(_path, path_revision)| **path_revision > revision)
+            .map(|(path, _path_revision)| path.as_str())
+            .collect::<Vec<_>>();
+        paths.sort_unstable();
+        paths
+    }
+
+    /// Returns the revision last mirrored into `UiSharedValues`.
+    pub(crate) fn synced_revision(&self) -> u64 {
+        self.synced_revision
+    }
+
+    /// Returns paths changed after `revision` that are already mirrored into
+    /// `UiSharedValues`, so templates can render their new values.
+    pub(crate) fn synced_paths_since(&self, revision: u64) -> Vec<&str> {
+        self.path_revisions
+            .iter()
+            .filter(|(_path, path_revision)| {
+                **path_revision > revision && **path_revision <= self.synced_revision
+            })
+            .map(|(path, _path_revision)| path.as_str())
+            .collect()
+    }
+
     /// Returns all known type names and paths registered with the store.
     pub fn known_types(&self) -> impl Iterator<Item = &str> {
         self.known_types.iter().map(String::as_str)
@@ -403,6 +556,9 @@ impl UiBindingStore {
         }
 
         let next_revision = self.revision + 1;
+        let previous_json = self.data.get(key).and_then(UiBindingEntry::json).cloned();
+        let json = json.map(|json| self.apply_computed_fields(key, previous_json.as_ref(), json));
+        self.record_path_changes(key, previous_json.as_ref(), json.as_ref(), next_revision);
         self.data.insert(
             key.to_string(),
             UiBindingEntry {
@@ -509,6 +665,9 @@ impl UiBindingStore {
     }
 
     fn set_json_projection(&mut self, key: &str, value: JsonValue) -> bool {
+        let previous_json = self.data.get(key).and_then(UiBindingEntry::json).cloned();
+        let value = self.apply_computed_fields(key, previous_json.as_ref(), value);
+
         if let Some(entry) = self.data.get_mut(key) {
             let Some(stored) = entry.value.as_mut() else {
                 return self.set::<JsonValue>(key, value);
@@ -518,20 +677,176 @@ impl UiBindingStore {
                 return false;
             }
 
-            stored.json = Some(value);
+            let previous_json = stored.json.replace(value);
             let next_revision = self.revision + 1;
             entry.revision = next_revision;
             self.revision = next_revision;
             self.known_types.insert(key.to_string());
+            let current_json = self.data.get(key).and_then(UiBindingEntry::json).cloned();
+            self.record_path_changes(
+                key,
+                previous_json.as_ref(),
+                current_json.as_ref(),
+                next_revision,
+            );
             return true;
         }
 
         self.set::<JsonValue>(key, value)
     }
 
+    /// Drops the value of `key`, and its entry unless `keep_entry` is set.
+    ///
+    /// Used by undo/redo to restore keys that had no value before an edit.
+    fn clear_key(&mut self, key: &str, keep_entry: bool) -> bool {
+        let Some(entry) = self.data.get(key) else {
+            return false;
+        };
+        if keep_entry && !entry.has_value() {
+            return false;
+        }
+
+        let previous_json = entry.json().cloned();
+        self.removed_keys.extend([
+            key.to_string(),
+            entry.type_path().to_string(),
+            entry.type_name().to_string(),
+        ]);
+        let next_revision = self.revision + 1;
+        if keep_entry {
+            if let Some(entry) = self.data.get_mut(key) {
+                entry.value = None;
+                entry.revision = next_revision;
+            }
+        } else {
+            self.data.remove(key);
+        }
+        self.record_path_changes(key, previous_json.as_ref(), None, next_revision);
+        self.revision = next_revision;
+        true
+    }
+
     fn bump_revision(&mut self) {
         self.revision += 1;
     }
+
+    /// Writes the computed fields of `key` into `json`.
+    ///
+    /// Fields whose dependencies did not change keep their previous value, so
+    /// unrelated updates do not run the compute functions.
+    fn apply_computed_fields(
+        &self,
+        key: &str,
+        previous: Option<&JsonValue>,
+        mut json: JsonValue,
+    ) -> JsonValue {
+        let Some(fields) = self.computed.get(key) else {
+            return json;
+        };
+        if !json.is_object() {
+            return json;
+        }
+
+        for field in fields {
+            let previous_value = previous.and_then(|previous| previous.get(&field.name));
+            let deps_changed = previous_value.is_none()
+                || field.deps.is_empty()
+                || field
+                    .deps
+                    .iter()
+                    .any(|dep| json_dep_value(previous, dep) != json_dep_value(Some(&json), dep));
+
+            let value = if deps_changed {
+                (field.compute)(&json)
+            } else {
+                previous_value.cloned()
+            };
+
+            if let JsonValue::Object(map) = &mut json {
+                match value {
+                    Some(value) => map.insert(field.name.clone(), value),
+                    None => map.remove(&field.name),
+                };
+            }
+        }
+
+        json
+    }
+
+    /// Stores `revision` for every path that differs between two JSON values
+    /// of `key`, including all parents of those paths.
+    ///
+    /// The differing paths themselves are also kept in `replaced_revisions`,
+    /// which lets [`UiBindingStore::path_revision`] see a replaced parent
+    /// without treating every sibling change as a change of the parent.
+    fn record_path_changes(
+        &mut self,
+        key: &str,
+        previous: Option<&JsonValue>,
+        current: Option<&JsonValue>,
+        revision: u64,
+    ) {
+        let mut changed = Vec::new();
+        collect_changed_json_paths(key, previous, current, &mut changed);
+        if changed.is_empty() {
+            changed.push(key.to_string());
+        }
+
+        for path in changed {
+            self.replaced_revisions.insert(path.clone(), revision);
+            let mut end = path.len();
+            loop {
+                self.path_revisions
+                    .insert(path[..end].to_string(), revision);
+                match path[..end].rfind('.') {
+                    Some(dot) if dot >= key.len() => end = dot,
+                    _ => break,
+                }
+            }
+        }
+    }
+}
+
+/// Collects the deepest object paths whose values differ.
+///
+/// Arrays and primitives are compared as a whole, so `items` changes when
+/// any element changes.
+fn collect_changed_json_paths(
+    path: &str,
+    previous: Option<&JsonValue>,
+    current: Option<&JsonValue>,
+    out: &mut Vec<String>,
+) {
+    if previous == current {
+        return;
+    }
+
+    match (previous, current) {
+        (Some(JsonValue::Object(previous)), Some(JsonValue::Object(current))) => {
+            let before = out.len();
+            for (field, value) in current {
+                collect_changed_json_paths(
+                    &format!("{path}.{field}"),
+                    previous.get(field),
+                    Some(value),
+                    out,
+                );
+            }
+            for (field, value) in previous {
+                if !current.contains_key(field) {
+                    collect_changed_json_paths(&format!("{path}.{field}"), Some(value), None, out);
+                }
+            }
+            if out.len() == before {
+                out.push(path.to_string());
+            }
+        }
+        _ => out.push(path.to_string()),
+    }
+}
+
+fn json_dep_value<'a>(value: Option<&'a JsonValue>, dep: &str) -> Option<&'a JsonValue> {
+    value?.pointer(&format!("/{}", dep.replace('.', "/")))
 }
 
 fn split_binding_path(path: &str) -> Option<(&str, Vec<String>)> {
@@ -652,13 +967,27 @@ fn json_to_f64(value: &JsonValue) -> Option<f64> {
     }
 }
 
-/// Registers all `#[derive(BeuStore)]` types collected by `inventory`.
+/// Registers all `#[derive(BeuStore)]` types and `#[beu_computed]` fields
+/// collected by `inventory`, and loads persisted stores from disk.
 pub fn register_beu_stores(world: &mut World) {
+    let mut persistence = world
+        .remove_resource::<UiBindingPersistence>()
+        .unwrap_or_default();
     let mut store = world.resource_mut::<UiBindingStore>();
 
     for registration in inventory::iter::<UiBindingStoreRegistration> {
         (registration.register)(&mut store);
+        if let Some(persist) = &registration.persist {
+            persistence.load(&mut store, registration.key, persist);
+        }
+    }
+
+    for computed in inventory::iter::<UiBindingComputedRegistration> {
+        store.register_computed(computed.key, computed.name, computed.deps, computed.compute);
     }
+
+    persistence.mark_saved(&store);
+    world.insert_resource(persistence);
 }
 
 /// Mirrors serializable store values into the template shared-value resource.
@@ -675,6 +1004,8 @@ pub fn sync_ui_binding_store_values(world: &mut World) {
         .map(str::to_string)
         .collect::<Vec<String>>();
     let values = store.template_values();
+    let removed_keys = store.removed_keys.clone();
+    let revision = store.revision();
 
     let mut shared = world.resource_mut::<UiSharedValues>();
     let mut changed = false;
@@ -683,6 +1014,12 @@ pub fn sync_ui_binding_store_values(world: &mut World) {
         changed |= shared.known_types.insert(known);
     }
 
+    for key in &removed_keys {
+        if !values.iter().any(|(value_key, _value)| value_key == key) {
+            changed |= shared.values.remove(key).is_some();
+        }
+    }
+
     for (key, value) in values {
         if shared.values.get(&key) != Some(&value) {
             shared.values.insert(key, value);
@@ -693,6 +1030,14 @@ pub fn sync_ui_binding_store_values(world: &mut World) {
     if changed {
         debug!("UiBindingStore values synced to template shared values");
     }
+
+    let mut store = world.resource_mut::<UiBindingStore>();
+    if store.synced_revision != revision {
+        store.bypass_change_detection().synced_revision = revision;
+    }
+    if !store.removed_keys.is_empty() {
+        store.bypass_change_detection().removed_keys.clear();
+    }
 }
 
 /// Run condition that is true only when [`UiBindingStore::revision`] changed.
@@ -715,6 +1060,30 @@ pub fn ui_binding_store_changed(
     true
 }
 
+/// Builds a run condition that is true only when the given binding path changed.
+///
+/// ```ignore
+/// app.add_systems(Update, refresh_hp_bar.run_if(ui_binding_path_changed("player.hp")));
+/// ```
+pub fn ui_binding_path_changed(
+    path: impl Into<String>,
+) -> impl FnMut(Option<Res<UiBindingStore>>, Local<u64>) -> bool + Clone {
+    let path = path.into();
+    move |store: Option<Res<UiBindingStore>>, mut last_revision: Local<u64>| {
+        let Some(store) = store else {
+            return false;
+        };
+
+        let revision = store.path_revision(&path);
+        if revision == *last_revision {
+            return false;
+        }
+
+        *last_revision = revision;
+        true
+    }
+}
+
 /// Compiles an HTML template in extended framework mode.
 ///
 /// Current base behavior:
@@ -749,6 +1118,9 @@ pub fn compile_framework_template_with_router(
 }
 
 /// Compiles an HTML template with a reusable component metadata/template cache.
+///
+/// Panics when `index.html` cannot be compiled; use
+/// [`try_compile_framework_template_with_router_cached`] to get the diagnostic instead.
 #[cfg(feature = "extended-framework")]
 pub fn compile_framework_template_with_router_cached(
     template_html: &str,
@@ -757,26 +1129,48 @@ pub fn compile_framework_template_with_router_cached(
     router: Option<&Router>,
     cache: &mut FrameworkCompileCache,
 ) -> FrameworkCompileResult {
+    try_compile_framework_template_with_router_cached(
+        template_html,
+        source_path,
+        config,
+        router,
+        cache,
+    )
+    .unwrap_or_else(|err| panic!("extended-framework compile failed for index.html: {err}"))
+}
+
+/// Compiles an HTML template with a reusable cache and reports failures as a
+/// located [`TemplateDiagnostic`].
+#[cfg(feature = "extended-framework")]
+pub fn try_compile_framework_template_with_router_cached(
+    template_html: &str,
+    source_path: &str,
+    config: &ExtendedFrameworkConfiguration,
+    router: Option<&Router>,
+    cache: &mut FrameworkCompileCache,
+) -> Result<FrameworkCompileResult, TemplateDiagnostic> {
     let source = normalize_source_path(source_path);
     let mut html = template_html.to_string();
     let mut component_controllers = Vec::new();
+    let mut component_sources = Vec::new();
     if source == normalize_source_path(&config.index_html_file) {
         let route_components = router.map(registered_route_components).unwrap_or_default();
-        component_controllers = compile_index_template(
+        (component_controllers, component_sources) = compile_index_template(
             &mut html,
+            source_path,
             config,
             router,
             route_components.as_slice(),
             cache,
-        )
-        .unwrap_or_else(|err| panic!("extended-framework compile failed for index.html: {err}"));
+        )?;
     }
 
-    FrameworkCompileResult {
+    Ok(FrameworkCompileResult {
         html,
         inferred_controller: infer_component_controller_path(source_path, config),
         component_controllers,
-    }
+        component_sources,
+    })
 }
 
 /// Infers a Rust component source path from an HTML component source path.
@@ -844,20 +1238,31 @@ fn normalize_root(path: &str) -> String {
 }
 
 /// Handles `compile_index_template` in the extended UI workflow.
+///
+/// Returns the used component controllers and the unexpanded templates of the
+/// inlined components.
 #[cfg(feature = "extended-framework")]
 fn compile_index_template(
     index_html: &mut String,
+    source_path: &str,
     config: &ExtendedFrameworkConfiguration,
     router: Option<&Router>,
     route_components: &[String],
     cache: &mut FrameworkCompileCache,
-) -> Result<Vec<String>, String> {
-    refresh_framework_compile_cache(cache, config)?;
+) -> Result<(Vec<String>, Vec<FrameworkComponentSource>), TemplateDiagnostic> {
+    let original = index_html.clone();
+    let diagnostic = |offset: usize, message: String| {
+        TemplateDiagnostic::error(source_path, &original, offset, message)
+    };
+
+    refresh_framework_compile_cache(cache, config).map_err(|err| diagnostic(0, err))?;
     let defs = cache.definitions.clone();
-    let has_router_outlet = has_router_outlet(index_html)?;
+    let has_router_outlet = has_router_outlet(index_html).map_err(|err| diagnostic(0, err))?;
 
     if let Some(router) = router {
-        replace_router_outlets(index_html, router, &defs)?;
+        let outlet_offset = find_tag_offset(&original, "router-outlet").unwrap_or(0);
+        replace_router_outlets(index_html, router, &defs)
+            .map_err(|err| diagnostic(outlet_offset, err))?;
     }
 
     let mut used_style_hrefs = if has_router_outlet {
@@ -866,13 +1271,25 @@ fn compile_index_template(
         BTreeSet::new()
     };
     let mut used_component_controllers: BTreeSet<String> = BTreeSet::new();
+    let mut component_sources: Vec<FrameworkComponentSource> = Vec::new();
 
     for _ in 0..16 {
         let mut replaced = false;
 
         for def in &defs {
-            let component_html = cached_component_template(cache, def, config)?;
-            let (full_tag_re, self_closing_re) = cached_component_regexes(cache, def)?;
+            let located_error = |err: String| {
+                locate_component_tag_error(
+                    source_path,
+                    &original,
+                    &component_sources,
+                    &def.template_name,
+                    err,
+                )
+            };
+            let component_html =
+                cached_component_template(cache, def, config).map_err(located_error)?;
+            let (full_tag_re, self_closing_re) =
+                cached_component_regexes(cache, def).map_err(located_error)?;
 
             if full_tag_re.is_match(index_html) || self_closing_re.is_match(index_html) {
                 *index_html = full_tag_re
@@ -893,6 +1310,20 @@ fn compile_index_template(
                     &def.source_dir_rel,
                     &def.template_file,
                 ));
+                let component_path = build_component_style_href(
+                    &config.assets_component_root,
+                    &def.source_dir_rel,
+                    &def.template_file,
+                );
+                if !component_sources
+                    .iter()
+                    .any(|source| source.source_path == component_path)
+                {
+                    component_sources.push(FrameworkComponentSource {
+                        source_path: component_path,
+                        html: component_html,
+                    });
+                }
                 replaced = true;
             }
         }
@@ -903,7 +1334,40 @@ fn compile_index_template(
     }
 
     inject_component_styles(index_html, used_style_hrefs);
-    Ok(used_component_controllers.into_iter().collect())
+    Ok((
+        used_component_controllers.into_iter().collect(),
+        component_sources,
+    ))
+}
+
+/// Returns the byte offset of the first `<tag` occurrence, used to locate diagnostics.
+#[cfg(feature = "extended-framework")]
+fn find_tag_offset(html: &str, tag: &str) -> Option<usize> {
+    let re = Regex::new(&format!(r"(?i)<\s*{}\b", regex::escape(tag))).ok()?;
+    re.find(html).map(|found| found.start())
+}
+
+/// Builds a diagnostic at the first use of a component tag.
+///
+/// Tags nested inside another component are reported against that component's
+/// file; unknown locations fall back to the top of `index.html`.
+#[cfg(feature = "extended-framework")]
+fn locate_component_tag_error(
+    source_path: &str,
+    index_html: &str,
+    component_sources: &[FrameworkComponentSource],
+    tag: &str,
+    message: String,
+) -> TemplateDiagnostic {
+    if let Some(offset) = find_tag_offset(index_html, tag) {
+        return TemplateDiagnostic::error(source_path, index_html, offset, message);
+    }
+    for source in component_sources {
+        if let Some(offset) = find_tag_offset(&source.html, tag) {
+            return TemplateDiagnostic::error(&source.source_path, &source.html, offset, message);
+        }
+    }
+    TemplateDiagnostic::error(source_path, index_html, 0, message)
 }
 
 #[cfg(feature = "extended-framework")]
@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::UiBindingStore;
+
+/// Loads persisted JSON into a typed store value.
+///
+/// Emitted by `#[derive(BeuStore)]` for `#[beu(persist = "...")]` types.
+pub type UiBindingStoreLoadFn = fn(&mut UiBindingStore, JsonValue) -> Result<(), String>;
+
+/// Persistence settings of a single `#[beu(persist = "...")]` store.
+pub struct UiBindingStorePersist {
+    /// File path, relative to [`UiBindingPersistence::root`].
+    pub path: &'static str,
+    pub load: UiBindingStoreLoadFn,
+}
+
+/// Settings and save state for persisted stores.
+///
+/// Persisted stores are loaded during `Startup`, before the first template
+/// render, and written back once no change happened for `debounce`.
+/// Pending writes are flushed when the app exits.
+#[derive(Resource, Debug, Clone)]
+pub struct UiBindingPersistence {
+    /// Folder that persist paths are resolved against.
+    pub root: PathBuf,
+    /// Quiet time after the last change before a store is written.
+    pub debounce: Duration,
+    stores: HashMap<String, PersistedStore>,
+}
+
+#[derive(Debug, Clone)]
+struct PersistedStore {
+    file: PathBuf,
+    seen_revision: u64,
+    saved_revision: u64,
+    changed_at: Duration,
+}
+
+impl Default for UiBindingPersistence {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("."),
+            debounce: Duration::from_millis(500),
+            stores: HashMap::new(),
+        }
+    }
+}
+
+impl UiBindingPersistence {
+    /// Returns the file a store key is persisted to.
+    pub fn file(&self, key: &str) -> Option<&Path> {
+        self.stores
+            .get(key)
+            .map(|persisted| persisted.file.as_path())
+    }
+
+    /// Loads the persisted file of `key` into the store.
+    ///
+    /// A missing file keeps the current value; unreadable files are logged
+    /// and ignored so a corrupt save never blocks startup.
+    pub(crate) fn load(
+        &mut self,
+        store: &mut UiBindingStore,
+        key: &str,
+        persist: &UiBindingStorePersist,
+    ) {
+        let file = self.root.join(persist.path);
+        if file.exists() {
+            let loaded = std::fs::read_to_string(&file)
+                .map_err(|err| err.to_string())
+                .and_then(|content| {
+                    serde_json::from_str::<JsonValue>(&content).map_err(|err| err.to_string())
+                })
+                .and_then(|json| (persist.load)(store, json));
+            if let Err(err) = loaded {
+                warn!("Failed to load persisted store {key} from {file:?}: {err}");
+            }
+        }
+
+        self.stores.insert(
+            key.to_string(),
+            PersistedStore {
+                file,
+                seen_revision: 0,
+                saved_revision: 0,
+                changed_at: Duration::ZERO,
+            },
+        );
+    }
+
+    /// Marks the current store values as saved, e.g. right after loading.
+    pub(crate) fn mark_saved(&mut self, store: &UiBindingStore) {
+        for (key, persisted) in &mut self.stores {
+            let revision = store.data.get(key).map_or(0, |entry| entry.revision());
+            persisted.seen_revision = revision;
+            persisted.saved_revision = revision;
+        }
+    }
+}
+
+/// Writes changed persisted stores to disk after the debounce delay.
+pub fn save_persisted_stores(
+    time: Res<Time>,
+    store: Option<Res<UiBindingStore>>,
+    persistence: Option<ResMut<UiBindingPersistence>>,
+    mut exit: MessageReader<AppExit>,
+) {
+    let (Some(store), Some(mut persistence)) = (store, persistence) else {
+        return;
+    };
+    let flush = exit.read().count() > 0;
+    let now = time.elapsed();
+    let debounce = persistence.debounce;
+
+    for (key, persisted) in &mut persistence.bypass_change_detection().stores {
+        let Some(entry) = store.data.get(key) else {
+            continue;
+        };
+
+        if entry.revision() != persisted.seen_revision {
+            persisted.seen_revision = entry.revision();
+            persisted.changed_at = now;
+        }
+        if persisted.seen_revision == persisted.saved_revision {
+            continue;
+        }
+        if !flush && now.saturating_sub(persisted.changed_at) < debounce {
+            continue;
+        }
+
+        let Some(json) = entry.json() else {
+            continue;
+        };
+        match write_persisted_store(&persisted.file, json) {
+            Ok(()) => persisted.saved_revision = persisted.seen_revision,
+            Err(err) => {
+                warn!(
+                    "Failed to persist store {key} to {:?}: {err}",
+                    persisted.file
+                );
+                // Retry after another debounce period instead of every frame.
+                persisted.changed_at = now;
+            }
+        }
+    }
+}
+
+fn write_persisted_store(file: &Path, json: &JsonValue) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(json).map_err(|err| err.to_string())?;
+    std::fs::write(file, content).map_err(|err| err.to_string())
+}
@@ -1,5 +1,14 @@
 use crate::CurrentWidgetState;
-use crate::html::inline_functions::queue_html_inline_action;
+use crate::html::event_modifiers::{
+    HtmlEventModifierState, clear_html_event_modifier_state, run_due_html_debounces,
+    run_html_handler_modified,
+};
+use crate::html::inline_functions::{execute_html_inline_action, resolve_html_handler_args};
+use crate::html::propagation::{
+    HtmlClickDefault, HtmlEventPath, HtmlEventPropagation, on_html_bubbling_event,
+};
+use crate::html::shortcuts::emit_html_shortcuts;
+use crate::html::ui::unregister_ui_handlers;
 use crate::html::*;
 use crate::widgets::controls::color_picker::ColorPickerUserChanged;
 use crate::widgets::controls::input::InputUserChanged;
@@ -12,7 +21,9 @@ use crate::widgets::{
     Scrollbar, SwitchButton, ToggleButton, UIGenID, UIWidgetState, ValidationRules,
     evaluate_validation_state,
 };
+use bevy::ecs::system::SystemId;
 use bevy::log::warn;
+use bevy::picking::pointer::PointerId;
 use bevy::prelude::*;
 use bevy::ui::{ComputedNode, RelativeCursorPosition, ScrollPosition};
 use std::collections::{HashMap, HashSet};
@@ -37,6 +48,33 @@ pub(crate) struct SliderLiveChangeThrottle {
 
 const SLIDER_LIVE_CHANGE_INTERVAL_SECS: f32 = 1.0 / 30.0;
 
+/// Resource remembering the last primary click per widget for `ondblclick`.
+#[derive(Resource, Default)]
+pub(crate) struct HtmlDoubleClickTracker {
+    last_click: HashMap<Entity, f32>,
+}
+
+const DOUBLE_CLICK_INTERVAL_SECS: f32 = 0.4;
+
+/// Resource tracking held pointers for `onlongpress`.
+#[derive(Resource, Default)]
+pub(crate) struct HtmlLongPressTracker {
+    pending: HashMap<PointerId, HtmlPendingLongPress>,
+    /// Widget whose long press fired during the current press; its click is
+    /// swallowed.
+    fired: HashMap<PointerId, Entity>,
+}
+
+struct HtmlPendingLongPress {
+    entity: Entity,
+    started: f32,
+    position: Vec2,
+    inner_position: Vec2,
+}
+
+/// Pointer travel in pixels that cancels a pending long press.
+const LONG_PRESS_MOVE_TOLERANCE: f32 = 10.0;
+
 /// Plugin that wires HTML event bindings into Bevy observers and systems.
 pub struct HtmlEventBindingsPlugin;
 
@@ -45,24 +83,51 @@ impl Plugin for HtmlEventBindingsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<HtmlScrollTracker>();
         app.init_resource::<SliderLiveChangeThrottle>();
+        app.init_resource::<HtmlDoubleClickTracker>();
+        app.init_resource::<HtmlLongPressTracker>();
+        app.init_resource::<HtmlLongPressDelay>();
+        app.init_resource::<HtmlEventArgs>();
+        app.init_resource::<HtmlEventPropagation>();
+        app.init_resource::<HtmlEventModifierState>();
+        app.add_message::<HtmlEmit>();
+        app.add_systems(
+            Update,
+            run_due_html_debounces.in_set(HtmlSystemSet::Bindings),
+        );
+        app.add_observer(clear_html_event_modifier_state);
+        app.add_observer(unregister_ui_handlers);
 
         // observer (click)
         app.add_observer(emit_html_click_events);
-        app.add_observer(on_html_click);
+        app.add_observer(on_html_bubbling_event::<HtmlClick>);
         app.add_observer(emit_html_mouse_down_events);
-        app.add_observer(on_html_mouse_down);
+        app.add_observer(on_html_bubbling_event::<HtmlMouseDown>);
         app.add_observer(emit_html_mouse_up_events);
-        app.add_observer(on_html_mouse_up);
+        app.add_observer(on_html_bubbling_event::<HtmlMouseUp>);
         app.add_observer(emit_html_submit_events);
         app.add_observer(on_html_submit);
+        app.add_observer(emit_html_double_click_events);
+        app.add_observer(on_html_bubbling_event::<HtmlDoubleClick>);
+        app.add_observer(emit_html_context_menu_events);
+        app.add_observer(on_html_bubbling_event::<HtmlContextMenu>);
+
+        // observer (long press)
+        app.add_observer(track_html_long_press_start);
+        app.add_observer(cancel_html_long_press_on_release);
+        app.add_observer(cancel_html_long_press_on_drag);
+        app.add_systems(
+            Update,
+            emit_html_long_press_events.in_set(HtmlSystemSet::Bindings),
+        );
+        app.add_observer(on_html_bubbling_event::<HtmlLongPress>);
 
         // observer (over)
         app.add_observer(emit_html_mouse_over_events);
-        app.add_observer(on_html_mouse_over);
+        app.add_observer(on_html_bubbling_event::<HtmlMouseOver>);
 
         // observer (out)
         app.add_observer(emit_html_mouse_out_events);
-        app.add_observer(on_html_mouse_out);
+        app.add_observer(on_html_bubbling_event::<HtmlMouseOut>);
 
         // observer (init)
         app.add_systems(
@@ -93,7 +158,7 @@ impl Plugin for HtmlEventBindingsPlugin {
             Update,
             emit_color_picker_change.in_set(HtmlSystemSet::Bindings),
         );
-        app.add_observer(on_html_change);
+        app.add_observer(on_html_bubbling_event::<HtmlChange>);
 
         // observer (focus)
         app.add_systems(
@@ -113,7 +178,7 @@ impl Plugin for HtmlEventBindingsPlugin {
         );
         app.add_observer(on_html_scroll);
         app.add_observer(emit_html_wheel_events);
-        app.add_observer(on_html_wheel);
+        app.add_observer(on_html_bubbling_event::<HtmlWheel>);
 
         // observer (keyboard)
         app.add_systems(
@@ -124,24 +189,30 @@ impl Plugin for HtmlEventBindingsPlugin {
             Update,
             emit_html_key_up_events.in_set(HtmlSystemSet::Bindings),
         );
-        app.add_observer(on_html_key_down);
-        app.add_observer(on_html_key_up);
+        app.add_observer(on_html_bubbling_event::<HtmlKeyDown>);
+        app.add_observer(on_html_bubbling_event::<HtmlKeyUp>);
+        app.add_systems(Update, emit_html_shortcuts.in_set(HtmlSystemSet::Bindings));
 
         // observer (touch)
         app.add_observer(emit_html_touch_start_events);
-        app.add_observer(on_html_touch_start);
+        app.add_observer(on_html_bubbling_event::<HtmlTouchStart>);
         app.add_observer(emit_html_touch_move_events);
-        app.add_observer(on_html_touch_move);
+        app.add_observer(on_html_bubbling_event::<HtmlTouchMove>);
         app.add_observer(emit_html_touch_end_events);
-        app.add_observer(on_html_touch_end);
+        app.add_observer(on_html_bubbling_event::<HtmlTouchEnd>);
 
         // observer (drag)
         app.add_observer(emit_html_drag_start_events);
-        app.add_observer(on_html_drag_start);
+        app.add_observer(on_html_bubbling_event::<HtmlDragStart>);
         app.add_observer(emit_html_drag_events);
-        app.add_observer(on_html_drag);
+        app.add_observer(on_html_bubbling_event::<HtmlDrag>);
         app.add_observer(emit_html_drag_stop_events);
-        app.add_observer(on_html_drag_stop);
+        app.add_observer(on_html_bubbling_event::<HtmlDragStop>);
+
+        // observer (drop targets)
+        app.add_observer(on_html_bubbling_event::<HtmlDragEnter>);
+        app.add_observer(on_html_bubbling_event::<HtmlDragLeave>);
+        app.add_observer(on_html_bubbling_event::<HtmlDrop>);
     }
 }
 
@@ -167,20 +238,31 @@ fn pointer_inner_position(
 //                        Click
 // =================================================
 
-/// Emits click events for widgets with `onclick` bindings.
+/// Emits click events for primary-button clicks on widgets that handle
+/// `onclick` themselves or through an ancestor.
+///
+/// Without any `onclick` handler on the path the click default action runs
+/// right away.
 pub(crate) fn emit_html_click_events(
     ev: On<Pointer<Click>>,
     mut commands: Commands,
+    path: HtmlEventPath,
+    long_press: Res<HtmlLongPressTracker>,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
     )>,
 ) {
     let entity = ev.event().entity;
+    if ev.button != PointerButton::Primary
+        || !path.is_html_target(ev.original_event_target(), entity)
+        || long_press.fired.get(&ev.pointer_id) == Some(&entity)
+    {
+        return;
+    }
 
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if let Some(state) = state_opt {
@@ -188,115 +270,149 @@ pub(crate) fn emit_html_click_events(
             return;
         }
     }
-    if bindings.onclick.is_some() {
+    if path.listens(entity, "onclick") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
         commands.trigger(HtmlClick {
             entity,
             position,
             inner_position,
+            propagation: default(),
         });
+    } else {
+        commands.trigger(HtmlClickDefault { entity });
     }
 }
 
-/// Dispatches registered click handlers for HTML widgets.
-pub(crate) fn on_html_click(
-    click: On<HtmlClick>,
+/// Emits double-click events when a widget is clicked twice within
+/// [`DOUBLE_CLICK_INTERVAL_SECS`].
+pub(crate) fn emit_html_double_click_events(
+    ev: On<Pointer<Click>>,
     mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
+    time: Res<Time>,
+    path: HtmlEventPath,
+    mut tracker: ResMut<HtmlDoubleClickTracker>,
+    q_bindings: Query<(
+        Option<&UIWidgetState>,
+        Option<&RelativeCursorPosition>,
+        Option<&ComputedNode>,
+    )>,
 ) {
-    let entity = click.entity;
+    let entity = ev.event().entity;
+    if ev.button != PointerButton::Primary
+        || !path.is_html_target(ev.original_event_target(), entity)
+    {
+        return;
+    }
 
-    let Ok(bindings) = q_bindings.get(entity) else {
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
-    if let Some(action) = bindings.inline.onclick.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+    if state_opt.is_some_and(|state| state.disabled) || !path.listens(entity, "ondblclick") {
         return;
     }
-    let Some(name) = bindings.onclick.as_deref() else {
-        return;
-    };
 
-    if let Some(&sys_id) = reg.click_typed.get(name) {
-        commands.run_system_with(sys_id, *click);
-    } else if let Some(&sys_id) = reg.click.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onclick binding '{name}' not registered via #[html_fn(...)]");
-    }
+    let now = time.elapsed_secs();
+    // Drops clicks that can no longer pair up, including those of despawned widgets.
+    tracker
+        .last_click
+        .retain(|_, last| now - *last <= DOUBLE_CLICK_INTERVAL_SECS);
+    let is_double = tracker
+        .last_click
+        .get(&entity)
+        .is_some_and(|last| now - *last <= DOUBLE_CLICK_INTERVAL_SECS);
+    if !is_double {
+        tracker.last_click.insert(entity, now);
+        return;
+    }
+
+    // A third click starts a new pair instead of firing again.
+    tracker.last_click.remove(&entity);
+    let position = ev.pointer_location.position;
+    let inner_position = pointer_inner_position(rel_pos, node, position);
+    commands.trigger(HtmlDoubleClick {
+        entity,
+        position,
+        inner_position,
+        propagation: default(),
+    });
 }
 
-/// Emits mousedown events for widgets with `onmousedown` bindings.
-pub(crate) fn emit_html_mouse_down_events(
-    ev: On<Pointer<Press>>,
+/// Emits context-menu events for secondary-button clicks.
+pub(crate) fn emit_html_context_menu_events(
+    ev: On<Pointer<Click>>,
     mut commands: Commands,
+    path: HtmlEventPath,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
     )>,
 ) {
-    if !ev.pointer_id.is_mouse() {
+    let entity = ev.event().entity;
+    if ev.button != PointerButton::Secondary
+        || !path.is_html_target(ev.original_event_target(), entity)
+    {
         return;
     }
 
-    let entity = ev.event().entity;
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if state_opt.is_some_and(|state| state.disabled) {
         return;
     }
-    if bindings.onmousedown.is_some() {
+    if path.listens(entity, "oncontextmenu") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
-        commands.trigger(HtmlMouseDown {
+        commands.trigger(HtmlContextMenu {
             entity,
-            button: ev.button,
             position,
             inner_position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered mousedown handlers for HTML widgets.
-pub(crate) fn on_html_mouse_down(
-    down: On<HtmlMouseDown>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
+/// Runs `system` with `event`, passing the evaluated `handler(args...)`
+/// arguments of `field` as [`HtmlEventArgs`].
+fn run_html_handler<E: Send + Sync + 'static>(
+    commands: &mut Commands,
+    bindings: &HtmlEventBindings,
+    field: &'static str,
+    entity: Entity,
+    system: SystemId<In<E>>,
+    event: E,
 ) {
-    let entity = down.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.onmousedown.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.onmousedown.as_deref() else {
-        return;
-    };
+    let args = bindings.args.get(field).cloned().unwrap_or_default();
+    commands.queue(move |world: &mut World| {
+        run_html_handler_modified(world, entity, field, move |world: &mut World| {
+            let values = resolve_html_handler_args(world, entity, &args);
+            run_html_handler_with_args(world, system, event, values);
+        });
+    });
+}
 
-    if let Some(&sys_id) = reg.mousedown_typed.get(name) {
-        commands.run_system_with(sys_id, *down);
-    } else if let Some(&sys_id) = reg.mousedown.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onmousedown binding '{name}' not registered via #[html_fn(...)]");
-    }
+/// Queues the inline action bound to `field`, honoring its modifiers.
+fn queue_html_inline_handler(
+    commands: &mut Commands,
+    field: &'static str,
+    entity: Entity,
+    action: HtmlInlineAction,
+) {
+    commands.queue(move |world: &mut World| {
+        run_html_handler_modified(world, entity, field, move |world: &mut World| {
+            execute_html_inline_action(world, entity, &action);
+        });
+    });
 }
 
-/// Emits mouseup events for widgets with `onmouseup` bindings.
-pub(crate) fn emit_html_mouse_up_events(
-    ev: On<Pointer<Release>>,
+/// Emits mousedown events for widgets with `onmousedown` bindings.
+pub(crate) fn emit_html_mouse_down_events(
+    ev: On<Pointer<Press>>,
     mut commands: Commands,
+    path: HtmlEventPath,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
@@ -307,56 +423,72 @@ pub(crate) fn emit_html_mouse_up_events(
     }
 
     let entity = ev.event().entity;
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if state_opt.is_some_and(|state| state.disabled) {
         return;
     }
-    if bindings.onmouseup.is_some() {
+    if path.listens(entity, "onmousedown") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
-        commands.trigger(HtmlMouseUp {
+        commands.trigger(HtmlMouseDown {
             entity,
             button: ev.button,
             position,
             inner_position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered mouseup handlers for HTML widgets.
-pub(crate) fn on_html_mouse_up(
-    up: On<HtmlMouseUp>,
+/// Emits mouseup events for widgets with `onmouseup` bindings.
+pub(crate) fn emit_html_mouse_up_events(
+    ev: On<Pointer<Release>>,
     mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
+    path: HtmlEventPath,
+    q_bindings: Query<(
+        Option<&UIWidgetState>,
+        Option<&RelativeCursorPosition>,
+        Option<&ComputedNode>,
+    )>,
 ) {
-    let entity = up.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
+    if !ev.pointer_id.is_mouse() {
         return;
-    };
-    if let Some(action) = bindings.inline.onmouseup.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+    }
+
+    let entity = ev.event().entity;
+    if !path.is_html_target(ev.original_event_target(), entity) {
         return;
     }
-    let Some(name) = bindings.onmouseup.as_deref() else {
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
-
-    if let Some(&sys_id) = reg.mouseup_typed.get(name) {
-        commands.run_system_with(sys_id, *up);
-    } else if let Some(&sys_id) = reg.mouseup.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onmouseup binding '{name}' not registered via #[html_fn(...)]");
+    if state_opt.is_some_and(|state| state.disabled) {
+        return;
+    }
+    if path.listens(entity, "onmouseup") {
+        let position = ev.pointer_location.position;
+        let inner_position = pointer_inner_position(rel_pos, node, position);
+        commands.trigger(HtmlMouseUp {
+            entity,
+            button: ev.button,
+            position,
+            inner_position,
+            propagation: default(),
+        });
     }
 }
 
-/// Emits submit events for form actions when submit buttons are clicked.
+/// Emits submit events for form actions when submit buttons are clicked,
+/// unless an `onclick` handler prevented the default action.
 pub(crate) fn emit_html_submit_events(
-    ev: On<Pointer<Click>>,
+    ev: On<HtmlClickDefault>,
     mut commands: Commands,
     mut params: ParamSet<(
         Query<(&Button, Option<&UIWidgetState>)>,
@@ -585,44 +717,18 @@ fn collect_descendants(root: Entity, children_q: &Query<&Children>, out: &mut Ve
 pub(crate) fn emit_html_mouse_over_events(
     ev: On<Pointer<Over>>,
     mut commands: Commands,
-    q_bindings: Query<&HtmlEventBindings>,
+    path: HtmlEventPath,
 ) {
     let entity = ev.event().entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if bindings.onmouseover.is_some() {
-        commands.trigger(HtmlMouseOver { entity });
-    }
-}
-
-/// Dispatches registered mouse-over handlers for HTML widgets.
-pub(crate) fn on_html_mouse_over(
-    over: On<HtmlMouseOver>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = over.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.onmouseover.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+    if !path.is_html_target(ev.original_event_target(), entity) {
         return;
     }
-    let Some(name) = bindings.onmouseover.as_deref() else {
-        return;
-    };
 
-    if let Some(&sys_id) = reg.over_typed.get(name) {
-        commands.run_system_with(sys_id, *over);
-    } else if let Some(&sys_id) = reg.over.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onmouseover binding '{name}' not registered via #[html_fn(...)]");
+    if path.listens(entity, "onmouseover") {
+        commands.trigger(HtmlMouseOver {
+            entity,
+            propagation: default(),
+        });
     }
 }
 
@@ -634,44 +740,18 @@ pub(crate) fn on_html_mouse_over(
 pub(crate) fn emit_html_mouse_out_events(
     ev: On<Pointer<Out>>,
     mut commands: Commands,
-    q_bindings: Query<&HtmlEventBindings>,
+    path: HtmlEventPath,
 ) {
     let entity = ev.event().entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
+    if !path.is_html_target(ev.original_event_target(), entity) {
         return;
-    };
-    if bindings.onmouseout.is_some() {
-        commands.trigger(HtmlMouseOut { entity });
     }
-}
 
-/// Dispatches registered mouse-out handlers for HTML widgets.
-pub(crate) fn on_html_mouse_out(
-    out: On<HtmlMouseOut>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = out.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.onmouseout.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.onmouseout.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.out_typed.get(name) {
-        commands.run_system_with(sys_id, *out);
-    } else if let Some(&sys_id) = reg.out.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onmouseout binding '{name}' not registered via #[html_fn(...)]");
+    if path.listens(entity, "onmouseout") {
+        commands.trigger(HtmlMouseOut {
+            entity,
+            propagation: default(),
+        });
     }
 }
 
@@ -729,7 +809,7 @@ pub(crate) fn on_html_init(
     };
 
     if let Some(action) = bindings.inline.oninit.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+        queue_html_inline_handler(&mut commands, "oninit", entity, action);
         return;
     }
     let Some(name) = bindings.oninit.as_deref() else {
@@ -737,9 +817,16 @@ pub(crate) fn on_html_init(
     };
 
     if let Some(&sys_id) = reg.init_typed.get(name) {
-        commands.run_system_with(sys_id, *init);
+        run_html_handler(&mut commands, bindings, "oninit", entity, sys_id, *init);
     } else if let Some(&sys_id) = reg.init.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
+        run_html_handler(
+            &mut commands,
+            bindings,
+            "oninit",
+            entity,
+            sys_id,
+            HtmlEvent { entity },
+        );
     } else {
         warn!("oninit binding '{name}' not registered via #[html_fn(...)]");
     }
@@ -753,10 +840,11 @@ pub(crate) fn on_html_init(
 /// Emits change events for checkbox widgets.
 pub(crate) fn emit_checkbox_change(
     mut commands: Commands,
-    query: Query<(Entity, &HtmlEventBindings), Changed<CheckBox>>,
+    path: HtmlEventPath,
+    query: Query<Entity, (With<HtmlEventBindings>, Changed<CheckBox>)>,
 ) {
-    for (entity, binding) in &query {
-        emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+    for entity in &query {
+        emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
     }
 }
 
@@ -764,10 +852,11 @@ pub(crate) fn emit_checkbox_change(
 /// Emits change events for choice box widgets.
 pub(crate) fn emit_choice_box_change(
     mut commands: Commands,
-    query: Query<(Entity, &HtmlEventBindings), Changed<ChoiceBox>>,
+    path: HtmlEventPath,
+    query: Query<Entity, (With<HtmlEventBindings>, Changed<ChoiceBox>)>,
 ) {
-    for (entity, binding) in &query {
-        emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+    for entity in &query {
+        emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
     }
 }
 
@@ -775,10 +864,11 @@ pub(crate) fn emit_choice_box_change(
 /// Emits change events for list box widgets.
 pub(crate) fn emit_list_box_change(
     mut commands: Commands,
-    query: Query<(Entity, &HtmlEventBindings), Changed<ListBox>>,
+    path: HtmlEventPath,
+    query: Query<Entity, (With<HtmlEventBindings>, Changed<ListBox>)>,
 ) {
-    for (entity, binding) in &query {
-        emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+    for entity in &query {
+        emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
     }
 }
 
@@ -786,13 +876,17 @@ pub(crate) fn emit_list_box_change(
 /// Emits change events for field set widgets.
 pub(crate) fn emit_field_set_change(
     mut commands: Commands,
+    path: HtmlEventPath,
     query: Query<
-        (Entity, &HtmlEventBindings),
-        Or<(Changed<FieldSelectionSingle>, Changed<FieldSelectionMulti>)>,
+        Entity,
+        (
+            With<HtmlEventBindings>,
+            Or<(Changed<FieldSelectionSingle>, Changed<FieldSelectionMulti>)>,
+        ),
     >,
 ) {
-    for (entity, binding) in &query {
-        emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+    for entity in &query {
+        emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
     }
 }
 
@@ -802,19 +896,19 @@ pub(crate) fn emit_slider_change(
     mut commands: Commands,
     time: Res<Time>,
     mut throttle: ResMut<SliderLiveChangeThrottle>,
+    path: HtmlEventPath,
     query: Query<
         (
             Entity,
-            &HtmlEventBindings,
             Option<&SliderLiveChanged>,
             Option<&SliderChangeCommitted>,
         ),
-        With<SliderUserChanged>,
+        (With<HtmlEventBindings>, With<SliderUserChanged>),
     >,
 ) {
     let now = time.elapsed_secs();
 
-    for (entity, binding, live, committed) in &query {
+    for (entity, live, committed) in &query {
         let is_live = live.is_some();
         let is_committed = committed.is_some();
         let force_emit = is_committed || !is_live;
@@ -825,7 +919,7 @@ pub(crate) fn emit_slider_change(
                 .is_none_or(|last_emit| now - *last_emit >= SLIDER_LIVE_CHANGE_INTERVAL_SECS);
 
         if should_emit {
-            emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+            emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
             throttle.last_emit.insert(entity, now);
         }
 
@@ -844,10 +938,11 @@ pub(crate) fn emit_slider_change(
 /// Emits change events for color picker widgets.
 pub(crate) fn emit_color_picker_change(
     mut commands: Commands,
-    query: Query<(Entity, &HtmlEventBindings), With<ColorPickerUserChanged>>,
+    path: HtmlEventPath,
+    query: Query<Entity, (With<HtmlEventBindings>, With<ColorPickerUserChanged>)>,
 ) {
-    for (entity, binding) in &query {
-        emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+    for entity in &query {
+        emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
         commands.entity(entity).remove::<ColorPickerUserChanged>();
     }
 }
@@ -855,20 +950,19 @@ pub(crate) fn emit_color_picker_change(
 /// Emits change events for input widgets.
 pub(crate) fn emit_input_change(
     mut commands: Commands,
+    path: HtmlEventPath,
     query: Query<
+        (Entity, Option<&UIWidgetState>, Option<&InputUserChanged>),
         (
-            Entity,
-            &HtmlEventBindings,
-            Option<&UIWidgetState>,
-            Option<&InputUserChanged>,
+            With<HtmlEventBindings>,
+            Or<(Changed<InputValue>, With<InputUserChanged>)>,
         ),
-        Or<(Changed<InputValue>, With<InputUserChanged>)>,
     >,
 ) {
-    for (entity, binding, state, user_changed) in &query {
+    for (entity, state, user_changed) in &query {
         let is_focused_user_edit = state.is_some_and(|state| state.focused);
         if user_changed.is_some() || is_focused_user_edit {
-            emit_change_if_bound(&mut commands, binding, entity, HtmlChangeAction::State);
+            emit_change_if_bound(&mut commands, &path, entity, HtmlChangeAction::State);
         }
         if user_changed.is_some() {
             commands.entity(entity).remove::<InputUserChanged>();
@@ -876,45 +970,19 @@ pub(crate) fn emit_input_change(
     }
 }
 
-/// Emits a change event when a binding is present.
+/// Emits a change event when the widget or an ancestor handles `onchange`.
 fn emit_change_if_bound(
     commands: &mut Commands,
-    bindings: &HtmlEventBindings,
+    path: &HtmlEventPath,
     entity: Entity,
     action: HtmlChangeAction,
 ) {
-    if bindings.onchange.is_some() {
-        commands.trigger(HtmlChange { entity, action });
-    }
-}
-
-/// Dispatches registered change handlers for HTML widgets.
-pub(crate) fn on_html_change(
-    init: On<HtmlChange>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = init.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-
-    if let Some(action) = bindings.inline.onchange.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.onchange.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.change_typed.get(name) {
-        commands.run_system_with(sys_id, *init);
-    } else if let Some(&sys_id) = reg.change.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onchange binding '{name}' not registered via #[html_fn(...)]");
+    if path.listens(entity, "onchange") {
+        commands.trigger(HtmlChange {
+            entity,
+            action,
+            propagation: default(),
+        });
     }
 }
 
@@ -981,7 +1049,7 @@ pub(crate) fn on_html_focus(
         return;
     };
     if let Some(action) = bindings.inline.onfoucs.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+        queue_html_inline_handler(&mut commands, "onfoucs", entity, action);
         return;
     }
     let Some(name) = bindings.onfoucs.as_deref() else {
@@ -989,9 +1057,16 @@ pub(crate) fn on_html_focus(
     };
 
     if let Some(&sys_id) = reg.focus_typed.get(name) {
-        commands.run_system_with(sys_id, *focus);
+        run_html_handler(&mut commands, bindings, "onfoucs", entity, sys_id, *focus);
     } else if let Some(&sys_id) = reg.focus.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
+        run_html_handler(
+            &mut commands,
+            bindings,
+            "onfoucs",
+            entity,
+            sys_id,
+            HtmlEvent { entity },
+        );
     } else {
         warn!("onfoucs binding '{name}' not registered via #[html_fn(...)]");
     }
@@ -1098,7 +1173,7 @@ pub(crate) fn on_html_scroll(
         return;
     };
     if let Some(action) = bindings.inline.onscroll.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+        queue_html_inline_handler(&mut commands, "onscroll", entity, action);
         return;
     }
     let Some(name) = bindings.onscroll.as_deref() else {
@@ -1106,9 +1181,16 @@ pub(crate) fn on_html_scroll(
     };
 
     if let Some(&sys_id) = reg.scroll_typed.get(name) {
-        commands.run_system_with(sys_id, *scroll);
+        run_html_handler(&mut commands, bindings, "onscroll", entity, sys_id, *scroll);
     } else if let Some(&sys_id) = reg.scroll.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
+        run_html_handler(
+            &mut commands,
+            bindings,
+            "onscroll",
+            entity,
+            sys_id,
+            HtmlEvent { entity },
+        );
     } else {
         warn!("onscroll binding '{name}' not registered via #[html_fn(...)]");
     }
@@ -1118,21 +1200,25 @@ pub(crate) fn on_html_scroll(
 pub(crate) fn emit_html_wheel_events(
     ev: On<Pointer<Scroll>>,
     mut commands: Commands,
+    path: HtmlEventPath,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
     )>,
 ) {
     let entity = ev.event().entity;
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if state_opt.is_some_and(|state| state.disabled) {
         return;
     }
-    if bindings.onwheel.is_some() {
+    if path.listens(entity, "onwheel") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
         commands.trigger(HtmlWheel {
@@ -1141,39 +1227,11 @@ pub(crate) fn emit_html_wheel_events(
             delta: Vec2::new(ev.x, ev.y),
             position,
             inner_position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered wheel handlers for HTML widgets.
-pub(crate) fn on_html_wheel(
-    wheel: On<HtmlWheel>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = wheel.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.onwheel.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.onwheel.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.wheel_typed.get(name) {
-        commands.run_system_with(sys_id, *wheel);
-    } else if let Some(&sys_id) = reg.wheel.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onwheel binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
-
 // =================================================
 //                       Keyboard
 // =================================================
@@ -1205,6 +1263,7 @@ pub(crate) fn emit_html_key_down_events(
     mut commands: Commands,
     keyboard: Res<ButtonInput<KeyCode>>,
     current_widget_state: Res<CurrentWidgetState>,
+    path: HtmlEventPath,
     q_bindings: Query<(Entity, &UIGenID, &HtmlEventBindings, &UIWidgetState)>,
 ) {
     let keys: Vec<KeyCode> = keyboard.get_just_pressed().copied().collect();
@@ -1215,15 +1274,19 @@ pub(crate) fn emit_html_key_down_events(
     let Some(entity) = find_keyboard_target_entity(&current_widget_state, &q_bindings) else {
         return;
     };
-    let Ok((_, _, bindings, state)) = q_bindings.get(entity) else {
+    let Ok((_, _, _, state)) = q_bindings.get(entity) else {
         return;
     };
-    if state.disabled || bindings.onkeydown.is_none() {
+    if state.disabled || !path.listens(entity, "onkeydown") {
         return;
     }
 
     for key in keys {
-        commands.trigger(HtmlKeyDown { entity, key });
+        commands.trigger(HtmlKeyDown {
+            entity,
+            key,
+            propagation: default(),
+        });
     }
 }
 
@@ -1232,6 +1295,7 @@ pub(crate) fn emit_html_key_up_events(
     mut commands: Commands,
     keyboard: Res<ButtonInput<KeyCode>>,
     current_widget_state: Res<CurrentWidgetState>,
+    path: HtmlEventPath,
     q_bindings: Query<(Entity, &UIGenID, &HtmlEventBindings, &UIWidgetState)>,
 ) {
     let keys: Vec<KeyCode> = keyboard.get_just_released().copied().collect();
@@ -1242,73 +1306,112 @@ pub(crate) fn emit_html_key_up_events(
     let Some(entity) = find_keyboard_target_entity(&current_widget_state, &q_bindings) else {
         return;
     };
-    let Ok((_, _, bindings, state)) = q_bindings.get(entity) else {
+    let Ok((_, _, _, state)) = q_bindings.get(entity) else {
         return;
     };
-    if state.disabled || bindings.onkeyup.is_none() {
+    if state.disabled || !path.listens(entity, "onkeyup") {
         return;
     }
 
     for key in keys {
-        commands.trigger(HtmlKeyUp { entity, key });
+        commands.trigger(HtmlKeyUp {
+            entity,
+            key,
+            propagation: default(),
+        });
     }
 }
 
-/// Dispatches registered key-down handlers for HTML widgets.
-pub(crate) fn on_html_key_down(
-    keydown: On<HtmlKeyDown>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = keydown.entity;
+// =================================================
+//                      Long press
+// =================================================
 
-    let Ok(bindings) = q_bindings.get(entity) else {
+/// Starts tracking a touch or primary mouse press for `onlongpress`.
+pub(crate) fn track_html_long_press_start(
+    ev: On<Pointer<Press>>,
+    time: Res<Time>,
+    path: HtmlEventPath,
+    mut tracker: ResMut<HtmlLongPressTracker>,
+    q_bindings: Query<(
+        Option<&UIWidgetState>,
+        Option<&RelativeCursorPosition>,
+        Option<&ComputedNode>,
+    )>,
+) {
+    tracker.fired.remove(&ev.pointer_id);
+    if ev.button != PointerButton::Primary {
         return;
-    };
-    if let Some(action) = bindings.inline.onkeydown.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
+    }
+
+    let entity = ev.event().entity;
+    if !path.is_html_target(ev.original_event_target(), entity) {
         return;
     }
-    let Some(name) = bindings.onkeydown.as_deref() else {
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
-
-    if let Some(&sys_id) = reg.keydown_typed.get(name) {
-        commands.run_system_with(sys_id, *keydown);
-    } else if let Some(&sys_id) = reg.keydown.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onkeydown binding '{name}' not registered via #[html_fn(...)]");
+    if state_opt.is_some_and(|state| state.disabled) || !path.listens(entity, "onlongpress") {
+        return;
     }
+
+    let position = ev.pointer_location.position;
+    tracker.pending.insert(
+        ev.pointer_id,
+        HtmlPendingLongPress {
+            entity,
+            started: time.elapsed_secs(),
+            position,
+            inner_position: pointer_inner_position(rel_pos, node, position),
+        },
+    );
 }
 
-/// Dispatches registered key-up handlers for HTML widgets.
-pub(crate) fn on_html_key_up(
-    keyup: On<HtmlKeyUp>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
+/// Drops a pending long press when the pointer is released early.
+pub(crate) fn cancel_html_long_press_on_release(
+    ev: On<Pointer<Release>>,
+    mut tracker: ResMut<HtmlLongPressTracker>,
 ) {
-    let entity = keyup.entity;
+    tracker.pending.remove(&ev.pointer_id);
+}
 
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.onkeyup.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
+/// Drops a pending long press once the pointer moved too far.
+pub(crate) fn cancel_html_long_press_on_drag(
+    ev: On<Pointer<Drag>>,
+    mut tracker: ResMut<HtmlLongPressTracker>,
+) {
+    if ev.distance.length() > LONG_PRESS_MOVE_TOLERANCE {
+        tracker.pending.remove(&ev.pointer_id);
     }
-    let Some(name) = bindings.onkeyup.as_deref() else {
-        return;
-    };
+}
 
-    if let Some(&sys_id) = reg.keyup_typed.get(name) {
-        commands.run_system_with(sys_id, *keyup);
-    } else if let Some(&sys_id) = reg.keyup.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("onkeyup binding '{name}' not registered via #[html_fn(...)]");
+/// Emits long-press events for presses held for [`HtmlLongPressDelay`].
+pub(crate) fn emit_html_long_press_events(
+    mut commands: Commands,
+    time: Res<Time>,
+    delay: Res<HtmlLongPressDelay>,
+    mut tracker: ResMut<HtmlLongPressTracker>,
+) {
+    let now = time.elapsed_secs();
+    let delay = delay.0.as_secs_f32();
+    let due: Vec<PointerId> = tracker
+        .pending
+        .iter()
+        .filter(|(_, press)| now - press.started >= delay)
+        .map(|(pointer, _)| *pointer)
+        .collect();
+
+    for pointer in due {
+        let Some(press) = tracker.pending.remove(&pointer) else {
+            continue;
+        };
+        tracker.fired.insert(pointer, press.entity);
+        commands.trigger(HtmlLongPress {
+            entity: press.entity,
+            position: press.position,
+            inner_position: press.inner_position,
+            propagation: default(),
+        });
     }
 }
 
@@ -1320,8 +1423,8 @@ pub(crate) fn on_html_key_up(
 pub(crate) fn emit_html_touch_start_events(
     ev: On<Pointer<Press>>,
     mut commands: Commands,
+    path: HtmlEventPath,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
@@ -1332,13 +1435,17 @@ pub(crate) fn emit_html_touch_start_events(
     };
 
     let entity = ev.event().entity;
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if state_opt.is_some_and(|state| state.disabled) {
         return;
     }
-    if bindings.ontouchstart.is_some() {
+    if path.listens(entity, "ontouchstart") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
         commands.trigger(HtmlTouchStart {
@@ -1346,45 +1453,17 @@ pub(crate) fn emit_html_touch_start_events(
             touch_id,
             position,
             inner_position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered touch-start handlers for HTML widgets.
-pub(crate) fn on_html_touch_start(
-    start: On<HtmlTouchStart>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = start.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.ontouchstart.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.ontouchstart.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.touchstart_typed.get(name) {
-        commands.run_system_with(sys_id, *start);
-    } else if let Some(&sys_id) = reg.touchstart.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("ontouchstart binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
-
 /// Emits touch-move events for widgets with `ontouchmove` bindings.
 pub(crate) fn emit_html_touch_move_events(
     ev: On<Pointer<Move>>,
     mut commands: Commands,
+    path: HtmlEventPath,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
@@ -1395,13 +1474,17 @@ pub(crate) fn emit_html_touch_move_events(
     };
 
     let entity = ev.event().entity;
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if state_opt.is_some_and(|state| state.disabled) {
         return;
     }
-    if bindings.ontouchmove.is_some() {
+    if path.listens(entity, "ontouchmove") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
         commands.trigger(HtmlTouchMove {
@@ -1410,45 +1493,17 @@ pub(crate) fn emit_html_touch_move_events(
             position,
             inner_position,
             delta: ev.delta,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered touch-move handlers for HTML widgets.
-pub(crate) fn on_html_touch_move(
-    move_ev: On<HtmlTouchMove>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = move_ev.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.ontouchmove.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.ontouchmove.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.touchmove_typed.get(name) {
-        commands.run_system_with(sys_id, *move_ev);
-    } else if let Some(&sys_id) = reg.touchmove.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("ontouchmove binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
-
 /// Emits touch-end events for widgets with `ontouchend` bindings.
 pub(crate) fn emit_html_touch_end_events(
     ev: On<Pointer<Release>>,
     mut commands: Commands,
+    path: HtmlEventPath,
     q_bindings: Query<(
-        &HtmlEventBindings,
         Option<&UIWidgetState>,
         Option<&RelativeCursorPosition>,
         Option<&ComputedNode>,
@@ -1459,13 +1514,17 @@ pub(crate) fn emit_html_touch_end_events(
     };
 
     let entity = ev.event().entity;
-    let Ok((bindings, state_opt, rel_pos, node)) = q_bindings.get(entity) else {
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
+
+    let Ok((state_opt, rel_pos, node)) = q_bindings.get(entity) else {
         return;
     };
     if state_opt.is_some_and(|state| state.disabled) {
         return;
     }
-    if bindings.ontouchend.is_some() {
+    if path.listens(entity, "ontouchend") {
         let position = ev.pointer_location.position;
         let inner_position = pointer_inner_position(rel_pos, node, position);
         commands.trigger(HtmlTouchEnd {
@@ -1473,39 +1532,11 @@ pub(crate) fn emit_html_touch_end_events(
             touch_id,
             position,
             inner_position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered touch-end handlers for HTML widgets.
-pub(crate) fn on_html_touch_end(
-    end: On<HtmlTouchEnd>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = end.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.ontouchend.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.ontouchend.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.touchend_typed.get(name) {
-        commands.run_system_with(sys_id, *end);
-    } else if let Some(&sys_id) = reg.touchend.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("ontouchend binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
-
 // =================================================
 //                         Drag
 // =================================================
@@ -1514,11 +1545,15 @@ pub(crate) fn on_html_touch_end(
 pub(crate) fn emit_html_drag_start_events(
     ev: On<Pointer<DragStart>>,
     mut commands: Commands,
-    q_bindings: Query<(&HtmlEventBindings, Option<&UIWidgetState>)>,
+    path: HtmlEventPath,
+    q_bindings: Query<Option<&UIWidgetState>>,
 ) {
     let entity = ev.event().entity;
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
 
-    let Ok((bindings, state_opt)) = q_bindings.get(entity) else {
+    let Ok(state_opt) = q_bindings.get(entity) else {
         return;
     };
     if let Some(state) = state_opt {
@@ -1526,52 +1561,28 @@ pub(crate) fn emit_html_drag_start_events(
             return;
         }
     }
-    if bindings.ondragstart.is_some() {
+    if path.listens(entity, "ondragstart") {
         commands.trigger(HtmlDragStart {
             entity,
             position: ev.pointer_location.position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered drag-start handlers for HTML widgets.
-pub(crate) fn on_html_drag_start(
-    drag: On<HtmlDragStart>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = drag.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.ondragstart.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.ondragstart.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.dragstart_typed.get(name) {
-        commands.run_system_with(sys_id, *drag);
-    } else if let Some(&sys_id) = reg.dragstart.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("ondragstart binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
-
 /// Emits drag events for widgets with drag bindings.
 pub(crate) fn emit_html_drag_events(
     ev: On<Pointer<Drag>>,
     mut commands: Commands,
-    q_bindings: Query<(&HtmlEventBindings, Option<&UIWidgetState>)>,
+    path: HtmlEventPath,
+    q_bindings: Query<Option<&UIWidgetState>>,
 ) {
     let entity = ev.event().entity;
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
 
-    let Ok((bindings, state_opt)) = q_bindings.get(entity) else {
+    let Ok(state_opt) = q_bindings.get(entity) else {
         return;
     };
     if let Some(state) = state_opt {
@@ -1579,52 +1590,28 @@ pub(crate) fn emit_html_drag_events(
             return;
         }
     }
-    if bindings.ondrag.is_some() {
+    if path.listens(entity, "ondrag") {
         commands.trigger(HtmlDrag {
             entity,
             position: ev.pointer_location.position,
+            propagation: default(),
         });
     }
 }
 
-/// Dispatches registered drag handlers for HTML widgets.
-pub(crate) fn on_html_drag(
-    drag: On<HtmlDrag>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = drag.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.ondrag.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.ondrag.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.drag_typed.get(name) {
-        commands.run_system_with(sys_id, *drag);
-    } else if let Some(&sys_id) = reg.drag.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("ondrag binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
-
 /// Emits drag-stop events for widgets with drag bindings.
 pub(crate) fn emit_html_drag_stop_events(
     ev: On<Pointer<DragEnd>>,
     mut commands: Commands,
-    q_bindings: Query<(&HtmlEventBindings, Option<&UIWidgetState>)>,
+    path: HtmlEventPath,
+    q_bindings: Query<Option<&UIWidgetState>>,
 ) {
     let entity = ev.event().entity;
+    if !path.is_html_target(ev.original_event_target(), entity) {
+        return;
+    }
 
-    let Ok((bindings, state_opt)) = q_bindings.get(entity) else {
+    let Ok(state_opt) = q_bindings.get(entity) else {
         return;
     };
     if let Some(state) = state_opt {
@@ -1632,39 +1619,11 @@ pub(crate) fn emit_html_drag_stop_events(
             return;
         }
     }
-    if bindings.ondragstop.is_some() {
+    if path.listens(entity, "ondragstop") {
         commands.trigger(HtmlDragStop {
             entity,
             position: ev.pointer_location.position,
+            propagation: default(),
         });
     }
 }
-
-/// Dispatches registered drag-stop handlers for HTML widgets.
-pub(crate) fn on_html_drag_stop(
-    drag: On<HtmlDragStop>,
-    mut commands: Commands,
-    reg: Res<HtmlFunctionRegistry>,
-    q_bindings: Query<&HtmlEventBindings>,
-) {
-    let entity = drag.entity;
-
-    let Ok(bindings) = q_bindings.get(entity) else {
-        return;
-    };
-    if let Some(action) = bindings.inline.ondragstop.clone() {
-        queue_html_inline_action(&mut commands, entity, action);
-        return;
-    }
-    let Some(name) = bindings.ondragstop.as_deref() else {
-        return;
-    };
-
-    if let Some(&sys_id) = reg.dragstop_typed.get(name) {
-        commands.run_system_with(sys_id, *drag);
-    } else if let Some(&sys_id) = reg.dragstop.get(name) {
-        commands.run_system_with(sys_id, HtmlEvent { entity });
-    } else {
-        warn!("ondragstop binding '{name}' not registered via #[html_fn(...)]");
-    }
-}
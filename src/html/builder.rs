@@ -51,6 +51,39 @@ enum HtmlNodeKindDiscriminant {
     ListBox,
 }
 
+impl HtmlNodeKind {
+    /// Returns the tag the widget is declared with; headlines report `h1`.
+    pub fn tag_name(&self) -> &'static str {
+        match self.discriminant {
+            HtmlNodeKindDiscriminant::Body => "body",
+            HtmlNodeKindDiscriminant::Div => "div",
+            HtmlNodeKindDiscriminant::Form => "form",
+            HtmlNodeKindDiscriminant::Dialog => "dialog",
+            HtmlNodeKindDiscriminant::Divider => "divider",
+            HtmlNodeKindDiscriminant::Button => "button",
+            HtmlNodeKindDiscriminant::CheckBox => "checkbox",
+            HtmlNodeKindDiscriminant::ColorPicker => "colorpicker",
+            HtmlNodeKindDiscriminant::ChoiceBox => "select",
+            HtmlNodeKindDiscriminant::DatePicker => "date-picker",
+            HtmlNodeKindDiscriminant::FieldSet => "fieldset",
+            HtmlNodeKindDiscriminant::Headline => "h1",
+            HtmlNodeKindDiscriminant::HyperLink => "a",
+            HtmlNodeKindDiscriminant::Img => "img",
+            HtmlNodeKindDiscriminant::Input => "input",
+            HtmlNodeKindDiscriminant::Paragraph => "p",
+            HtmlNodeKindDiscriminant::ToolTip => "tool-tip",
+            HtmlNodeKindDiscriminant::Badge => "badge",
+            HtmlNodeKindDiscriminant::ProgressBar => "progressbar",
+            HtmlNodeKindDiscriminant::RadioButton => "radio",
+            HtmlNodeKindDiscriminant::Scrollbar => "scroll",
+            HtmlNodeKindDiscriminant::Slider => "slider",
+            HtmlNodeKindDiscriminant::SwitchButton => "switch",
+            HtmlNodeKindDiscriminant::ToggleButton => "toggle",
+            HtmlNodeKindDiscriminant::ListBox => "listbox",
+        }
+    }
+}
+
 impl Plugin for HtmlBuilderSystem {
     /// Registers systems to build HTML structures into UI entities.
     fn build(&self, app: &mut App) {
@@ -1022,6 +1055,24 @@ fn update_meta_components(
             .remove::<crate::widgets::ValidationRules>();
     }
 
+    if let Some(draggable) = &meta.draggable {
+        sync_meta_component(commands, entity, draggable.clone());
+    } else {
+        commands
+            .entity(entity)
+            .remove::<crate::html::HtmlDraggable>();
+    }
+
+    if let Some(shortcuts) = &meta.shortcuts {
+        sync_meta_component(commands, entity, shortcuts.clone());
+    } else {
+        commands
+            .entity(entity)
+            .remove::<crate::html::HtmlShortcuts>();
+    }
+
+    sync_meta_component(commands, entity, meta.attributes.clone());
+
     if states.hidden {
         commands.entity(entity).insert(NeedHidden);
     } else {
@@ -1398,7 +1449,7 @@ fn collect_html_ids(nodes: &Vec<HtmlWidgetNode>, ids: &mut Vec<HtmlID>) {
 }
 
 /// Recursively spawns entities for a HtmlWidgetNode and its children.
-fn spawn_widget_node(
+pub(crate) fn spawn_widget_node(
     commands: &mut Commands,
     node: &HtmlWidgetNode,
     asset_server: &AssetServer,
@@ -1780,6 +1831,7 @@ fn spawn_with_meta<T: Component>(
             CssSource(meta.css.clone()),
             CssClass(meta.class.clone().unwrap_or_default()),
             CssID(meta.id.clone().unwrap_or_default()),
+            meta.attributes.clone(),
             ui_state,
             if start_hidden || states.hidden {
                 Visibility::Hidden
@@ -1801,6 +1853,14 @@ fn spawn_with_meta<T: Component>(
         commands.entity(entity).insert(validation.clone());
     }
 
+    if let Some(draggable) = &meta.draggable {
+        commands.entity(entity).insert(draggable.clone());
+    }
+
+    if let Some(shortcuts) = &meta.shortcuts {
+        commands.entity(entity).insert(shortcuts.clone());
+    }
+
     if states.hidden {
         commands.entity(entity).insert(NeedHidden);
     }
@@ -11,6 +11,7 @@ use serde_json::Value as JsonValue;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::Ordering;
 #[cfg(feature = "extended-framework")]
 use std::{
     fs,
@@ -22,13 +23,17 @@ use crate::ExtendedUiConfiguration;
 use crate::dialog::{DialogProvider, DialogWidget, DialogWidgetType};
 #[cfg(feature = "extended-framework")]
 use crate::framework::{
-    ExtendedFrameworkConfiguration, FrameworkCompileCache,
-    compile_framework_template_with_router_cached,
+    ExtendedFrameworkConfiguration, FrameworkCompileCache, UiBindingStore,
+    try_compile_framework_template_with_router_cached,
 };
+use crate::html::inline_functions::split_top_level;
 use crate::html::{
-    HtmlDirty, HtmlEventBindings, HtmlID, HtmlInlineEventBindings, HtmlInnerContent, HtmlMeta,
-    HtmlPendingReveal, HtmlSource, HtmlStates, HtmlStructureMap, HtmlStyle, HtmlSystemSet,
-    HtmlTextBinding, HtmlWidgetNode, parse_html_inline_action,
+    HTML_ID_COUNTER, HtmlAttributes, HtmlDirty, HtmlDraggable, HtmlEventBindings, HtmlEventHandler,
+    HtmlEventModifiers, HtmlID, HtmlInlineEventBindings, HtmlInnerContent, HtmlKeyChord, HtmlMeta,
+    HtmlPendingReveal, HtmlShortcut, HtmlShortcuts, HtmlSource, HtmlStates, HtmlStructureMap,
+    HtmlStyle, HtmlSystemSet, HtmlTextBinding, HtmlWidgetNode, REFLECT_RESOURCE_PREFIX,
+    TemplateDiagnostic, TemplateDiagnosticsConfig, expand_reflect_forms, parse_html_handler_call,
+    parse_html_inline_action, parse_html_inline_expr, reflect_form_diagnostics,
 };
 use crate::io::{CssAsset, DefaultCssHandle, HtmlAsset};
 use crate::lang::{
@@ -55,6 +60,14 @@ static FOR_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^\s*([A-Za-z_][A-Za-z0-9_]*)(?:\s*,\s*([A-Za-z_][A-Za-z0-9_]*))?\s+in\s+(.+?)\s*$")
         .unwrap()
 });
+static HANDLER_CALL_ATTRIBUTE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^(on[a-z]+(?:\.[a-z0-9]+)*\s*=\s*")\s*([A-Za-z_][A-Za-z0-9_]*)\s*\(([^"]*)\)\s*""#,
+    )
+    .unwrap()
+});
+static DRAG_PAYLOAD_ATTRIBUTE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^(data-drag\s*=\s*")([^"]*)""#).unwrap());
 static SLIDER_RANGE_VALUE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^\s*([+-]?(?:\d+(?:\.\d+)?|\.\d+))\s*-\s*([+-]?(?:\d+(?:\.\d+)?|\.\d+))\s*$")
         .unwrap()
@@ -66,8 +79,10 @@ pub struct HtmlConverterSystem;
 impl Plugin for HtmlConverterSystem {
     /// Registers the HTML conversion system.
     fn build(&self, app: &mut App) {
+        app.add_message::<TemplateDiagnostic>();
         #[cfg(feature = "extended-framework")]
         app.init_resource::<FrameworkCompileCache>();
+        app.init_resource::<TemplateDiagnosticsConfig>();
         app.init_resource::<HtmlBindingValueTracker>();
         app.init_resource::<HtmlTemplateBindingProfiles>();
         app.init_resource::<HtmlTemplateBindingAnalysisCache>();
@@ -120,12 +135,37 @@ struct HtmlRuntimeState<'w> {
     lang_state: ResMut<'w, UiLangState>,
 }
 
+/// Bundled template diagnostic outputs for `update_html_ui`.
+#[derive(SystemParam)]
+struct TemplateDiagnosticOutputs<'w> {
+    writer: MessageWriter<'w, TemplateDiagnostic>,
+    config: Res<'w, TemplateDiagnosticsConfig>,
+}
+
+impl TemplateDiagnosticOutputs<'_> {
+    /// Logs and emits a diagnostic; errors abort in strict mode.
+    fn report(&mut self, diagnostic: TemplateDiagnostic) {
+        diagnostic.log();
+        if self.config.strict && diagnostic.is_error() {
+            panic!("template compile failed in strict mode: {diagnostic}");
+        }
+        self.writer.write(diagnostic);
+    }
+}
+
+/// Tracks which binding paths changed since the previous frame.
+///
+/// `changed_paths` uses template names (`player.name`); `changed_roots` holds
+/// their leading identifiers for the coarse reparse decision.
 #[derive(Resource, Default)]
 struct HtmlBindingValueTracker {
     vars: HashMap<String, String>,
     shared_values: HashMap<String, JsonValue>,
     changed_roots: HashSet<String>,
+    changed_paths: HashSet<String>,
     values_changed: bool,
+    #[cfg(feature = "extended-framework")]
+    store_revision: u64,
 }
 
 #[derive(Clone, Default)]
@@ -161,35 +201,124 @@ fn track_html_binding_value_changes(
     mut tracker: ResMut<HtmlBindingValueTracker>,
     vars: Res<UiLangVariables>,
     shared_values: Res<UiSharedValues>,
+    #[cfg(feature = "extended-framework")] store: Option<Res<UiBindingStore>>,
 ) {
-    let mut changed_roots = HashSet::new();
+    let mut changed_paths = HashSet::new();
 
     for (key, value) in &vars.vars {
         if tracker.vars.get(key) != Some(value) {
-            changed_roots.insert(key.clone());
+            changed_paths.insert(key.clone());
         }
     }
     for key in tracker.vars.keys() {
         if !vars.vars.contains_key(key) {
-            changed_roots.insert(key.clone());
+            changed_paths.insert(key.clone());
         }
     }
 
+    // Store-backed values carry per-path revisions, so they skip the JSON diff below.
+    #[cfg(feature = "extended-framework")]
+    let store_keys = match store.as_deref() {
+        Some(store) => {
+            if store.synced_revision() != tracker.store_revision {
+                for path in store.synced_paths_since(tracker.store_revision) {
+                    insert_store_binding_paths(&mut changed_paths, &shared_values, store, path);
+                }
+                tracker.store_revision = store.synced_revision();
+            }
+            store_shared_value_keys(store)
+        }
+        None => HashSet::new(),
+    };
+    #[cfg(not(feature = "extended-framework"))]
+    let store_keys = HashSet::<String>::new();
+
     for (key, value) in &shared_values.values {
-        if tracker.shared_values.get(key) != Some(value) {
-            changed_roots.insert(binding_root(key));
+        if !store_keys.contains(key) && tracker.shared_values.get(key) != Some(value) {
+            insert_shared_binding_paths(&mut changed_paths, &shared_values, &[key.as_str()], "");
         }
     }
     for key in tracker.shared_values.keys() {
-        if !shared_values.values.contains_key(key) {
-            changed_roots.insert(binding_root(key));
+        if !store_keys.contains(key) && !shared_values.values.contains_key(key) {
+            insert_shared_binding_paths(&mut changed_paths, &shared_values, &[key.as_str()], "");
         }
     }
 
-    tracker.values_changed = !changed_roots.is_empty();
-    tracker.changed_roots = changed_roots;
+    tracker.values_changed = !changed_paths.is_empty();
+    tracker.changed_roots = changed_paths
+        .iter()
+        .map(|path| binding_root(path))
+        .collect();
+    tracker.changed_paths = changed_paths;
     tracker.vars = vars.vars.clone();
-    tracker.shared_values = shared_values.values.clone();
+    tracker.shared_values = shared_values
+        .values
+        .iter()
+        .filter(|(key, _value)| !store_keys.contains(*key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+}
+
+/// Inserts `tail` below every template name that resolves to one of `keys`:
+/// the key itself, its snake_case alias and `#[html_use]` aliases.
+fn insert_shared_binding_paths(
+    out: &mut HashSet<String>,
+    shared_values: &UiSharedValues,
+    keys: &[&str],
+    tail: &str,
+) {
+    let mut names = HashSet::new();
+    for key in keys {
+        if key.contains("::") {
+            continue;
+        }
+        names.insert(key.to_string());
+        names.insert(to_template_alias(key));
+    }
+    for (alias, target) in &shared_values.auto_use_aliases {
+        if keys.contains(&target.as_str()) {
+            names.insert(alias.clone());
+        }
+    }
+
+    for name in names {
+        out.insert(format!("{name}{tail}"));
+    }
+}
+
+/// Maps a store path (`Player.name`) to the template names it is visible under.
+#[cfg(feature = "extended-framework")]
+fn insert_store_binding_paths(
+    out: &mut HashSet<String>,
+    shared_values: &UiSharedValues,
+    store: &UiBindingStore,
+    path: &str,
+) {
+    let (key, tail) = match path.find('.') {
+        Some(dot) => (&path[..dot], &path[dot..]),
+        None => (path, ""),
+    };
+    let Some(entry) = store.data.get(key) else {
+        return;
+    };
+
+    let keys = [key, entry.type_name(), entry.type_path()];
+    insert_shared_binding_paths(out, shared_values, &keys, tail);
+}
+
+/// Returns the `UiSharedValues` keys that mirror `UiBindingStore` entries.
+#[cfg(feature = "extended-framework")]
+fn store_shared_value_keys(store: &UiBindingStore) -> HashSet<String> {
+    let mut keys = HashSet::new();
+    for (key, entry) in &store.data {
+        if entry.json().is_none() {
+            continue;
+        }
+        keys.insert(key.clone());
+        keys.insert(entry.type_name().to_string());
+        keys.insert(entry.type_path().to_string());
+    }
+    keys
 }
 
 fn patch_html_text_bindings(
@@ -209,11 +338,10 @@ fn patch_html_text_bindings(
     }
 
     for (binding, paragraph, headline, button, text) in query.iter_mut() {
-        if binding
+        if !binding
             .bindings
             .iter()
-            .map(|binding| binding_root(binding))
-            .all(|root| !tracker.changed_roots.contains(&root))
+            .any(|binding| binding_affected_by(binding, &tracker.changed_paths))
         {
             continue;
         }
@@ -275,6 +403,7 @@ fn update_html_ui(
 
     binding_tracker: Res<HtmlBindingValueTracker>,
     queries: HtmlSourceQueries,
+    mut diagnostics: TemplateDiagnosticOutputs,
 ) {
     let type_registry = type_registry.read();
     #[cfg(feature = "extended-framework")]
@@ -405,17 +534,24 @@ fn update_html_ui(
         let source_path = html.get_source_path();
         let raw_content = html_asset.html.clone();
         #[cfg(feature = "extended-framework")]
-        let framework_compiled = compile_framework_template_with_router_cached(
+        let framework_compiled = match try_compile_framework_template_with_router_cached(
             &raw_content,
             &source_path,
             &framework_config,
             framework_inputs.router.as_deref(),
             &mut framework_inputs.cache,
-        );
+        ) {
+            Ok(compiled) => compiled,
+            Err(diagnostic) => {
+                diagnostics.report(diagnostic);
+                continue;
+            }
+        };
         #[cfg(feature = "extended-framework")]
         let content = framework_compiled.html.clone();
         #[cfg(not(feature = "extended-framework"))]
-        let content = raw_content;
+        let content = raw_content.clone();
+        let content = expand_reflect_forms(&content, &type_registry);
 
         let raw_document = kuchiki::parse_html().one(content.clone());
         let html_lang = raw_document
@@ -449,12 +585,50 @@ fn update_html_ui(
         #[cfg(not(feature = "extended-framework"))]
         let component_local_type_names = Vec::<String>::new();
 
-        let template_resolved = preprocess_template_directives_with_shared_and_local_types(
-            &localized,
-            &template_inputs.lang_vars,
-            &template_inputs.shared_values,
-            &component_local_type_names,
+        let (template_resolved, rendered_diagnostics) =
+            preprocess_template_directives_with_diagnostics(
+                &localized,
+                &template_inputs.lang_vars,
+                &template_inputs.shared_values,
+                &component_local_type_names,
+                &source_path,
+            );
+
+        // Diagnostics point at the files as written. The render above covers
+        // the page only if it was rendered unchanged; localized pages and
+        // inlined components are checked in their own coordinates.
+        let page_covered = localized == raw_content;
+        if page_covered {
+            for diagnostic in rendered_diagnostics {
+                diagnostics.report(diagnostic);
+            }
+        }
+        let diagnostic_sources = std::iter::once((source_path.as_str(), raw_content.as_str()));
+        #[cfg(feature = "extended-framework")]
+        let diagnostic_sources = diagnostic_sources.chain(
+            framework_compiled
+                .component_sources
+                .iter()
+                .map(|source| (source.source_path.as_str(), source.html.as_str())),
         );
+        let diagnostic_sources: Vec<_> = diagnostic_sources.collect();
+        for (path, source) in &diagnostic_sources {
+            for diagnostic in reflect_form_diagnostics(source, &type_registry, path) {
+                diagnostics.report(diagnostic);
+            }
+        }
+        let unchecked = usize::from(page_covered);
+        if unchecked < diagnostic_sources.len() {
+            for diagnostic in collect_source_template_diagnostics(
+                &diagnostic_sources,
+                unchecked,
+                &template_inputs.lang_vars,
+                &template_inputs.shared_values,
+                &component_local_type_names,
+            ) {
+                diagnostics.report(diagnostic);
+            }
+        }
         let document = if template_resolved == content {
             raw_document
         } else {
@@ -615,6 +789,70 @@ fn trim_path_separators(path: &str) -> String {
     path.trim_matches('/').trim_matches('\\').to_string()
 }
 
+/// Parses an HTML fragment like `<div class="toast">...</div>` into widget
+/// nodes, localized, rendered and bound like a full document.
+pub(crate) fn parse_html_fragment(
+    world: &World,
+    fragment: &str,
+    css_sources: &Vec<Handle<CssAsset>>,
+    key: &str,
+    html: &HtmlSource,
+) -> Vec<HtmlWidgetNode> {
+    let default_vars = UiLangVariables::default();
+    let default_shared = UiSharedValues::default();
+    let vars = world
+        .get_resource::<UiLangVariables>()
+        .unwrap_or(&default_vars);
+    let shared_values = world
+        .get_resource::<UiSharedValues>()
+        .unwrap_or(&default_shared);
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .cloned()
+        .unwrap_or_default();
+    let type_registry = type_registry.read();
+
+    let content = expand_reflect_forms(&format!("<body>{fragment}</body>"), &type_registry);
+    let localized = localize_html(
+        &content,
+        world.get_resource::<UILang>().and_then(UILang::resolved),
+        world
+            .get_resource::<ExtendedUiConfiguration>()
+            .map_or("", |config| config.language_path.as_str()),
+        vars,
+    );
+    let raw_text_bindings = select_primary_body_node(&kuchiki::parse_html().one(localized.clone()))
+        .as_ref()
+        .map(collect_text_bindings_by_path)
+        .unwrap_or_default();
+    let rendered = preprocess_template_directives_with_shared(&localized, vars, shared_values);
+
+    let document = kuchiki::parse_html().one(rendered);
+    let Some(body_node) = select_primary_body_node(&document) else {
+        return Vec::new();
+    };
+    let label_map = collect_labels_by_for(&body_node);
+    // Fragments get their own id space so they never collide with template nodes.
+    let key = format!("{key}#{}", HTML_ID_COUNTER.fetch_add(1, Ordering::Relaxed));
+
+    body_node
+        .children()
+        .enumerate()
+        .filter_map(|(index, child)| {
+            parse_html_node(
+                &child,
+                css_sources,
+                &label_map,
+                &key,
+                html,
+                &raw_text_bindings,
+                &type_registry,
+                &format!("0.{index}"),
+            )
+        })
+        .collect()
+}
+
 /// Builds a deterministic HtmlID from logical position in the parsed HTML tree.
 fn stable_html_id(key: &str, path: &str, tag: &str) -> HtmlID {
     const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
@@ -659,6 +897,9 @@ fn parse_html_node(
         validation: parse_validation_attributes(&attributes),
         inner_content: parse_inner_content(node),
         text_binding: raw_text_bindings.get(path).cloned(),
+        draggable: parse_draggable_attributes(&attributes),
+        shortcuts: parse_shortcuts(node, &attributes),
+        attributes: parse_html_attributes(&attributes),
     };
 
     let states = HtmlStates {
@@ -1099,6 +1340,9 @@ fn parse_html_node(
                     validation: parse_validation_attributes(&attrs),
                     inner_content: parse_inner_content(&radio_node),
                     text_binding: None,
+                    draggable: parse_draggable_attributes(&attrs),
+                    shortcuts: parse_shortcuts(&radio_node, &attrs),
+                    attributes: parse_html_attributes(&attrs),
                 };
 
                 let child_states = HtmlStates {
@@ -1518,6 +1762,8 @@ fn parse_html_node(
         "select" => {
             let mut options = Vec::new();
             let mut selected_value = None;
+            let mut matched_value = None;
+            let select_value = attributes.get("value").map(str::trim);
 
             for child in node.children() {
                 if let Some(option_el) = child.as_element() {
@@ -1538,6 +1784,7 @@ fn parse_html_node(
                             Some(icon)
                         };
 
+                        let matches_select_value = select_value == Some(value.trim());
                         let value = parse_option_internal_value(&value, &value_type, type_registry);
 
                         let option = ChoiceOption {
@@ -1549,14 +1796,18 @@ fn parse_html_node(
                         if attrs.contains("selected") {
                             selected_value = Some(option.clone());
                         }
+                        if matches_select_value && matched_value.is_none() {
+                            matched_value = Some(option.clone());
+                        }
 
                         options.push(option);
                     }
                 }
             }
 
-            let value =
-                selected_value.unwrap_or_else(|| options.first().cloned().unwrap_or_default());
+            let value = selected_value
+                .or(matched_value)
+                .unwrap_or_else(|| options.first().cloned().unwrap_or_default());
 
             Some(HtmlWidgetNode::ChoiceBox(
                 ChoiceBox {
@@ -1755,6 +2006,39 @@ fn parse_html_node(
     }
 }
 
+/// Event attributes read by [`bind_html_func`], including their aliases.
+pub const HTML_EVENT_ATTRIBUTES: &[&str] = &[
+    "onclick",
+    "onmousedown",
+    "onmouseup",
+    "onmouseover",
+    "onmouseenter",
+    "onmouseout",
+    "onmouseleave",
+    "onchange",
+    "oninit",
+    "onfoucs",
+    "onfocus",
+    "onscroll",
+    "onwheel",
+    "onmousewheel",
+    "onkeydown",
+    "onkeyup",
+    "ondragstart",
+    "ondrag",
+    "ondragstop",
+    "ondragend",
+    "ontouchstart",
+    "ontouchmove",
+    "ontouchend",
+    "ondblclick",
+    "oncontextmenu",
+    "onlongpress",
+    "ondragenter",
+    "ondragleave",
+    "ondrop",
+];
+
 /// Extracts HTML event bindings from element attributes.
 fn bind_html_func(attributes: &Attributes) -> HtmlEventBindings {
     let inline = HtmlInlineEventBindings {
@@ -1781,43 +2065,182 @@ fn bind_html_func(attributes: &Attributes) -> HtmlEventBindings {
         ontouchstart: parse_inline_attribute(attributes, "ontouchstart"),
         ontouchmove: parse_inline_attribute(attributes, "ontouchmove"),
         ontouchend: parse_inline_attribute(attributes, "ontouchend"),
+        ondblclick: parse_inline_attribute(attributes, "ondblclick"),
+        oncontextmenu: parse_inline_attribute(attributes, "oncontextmenu"),
+        onlongpress: parse_inline_attribute(attributes, "onlongpress"),
+        ondragenter: parse_inline_attribute(attributes, "ondragenter"),
+        ondragleave: parse_inline_attribute(attributes, "ondragleave"),
+        ondrop: parse_inline_attribute(attributes, "ondrop"),
+    };
+
+    let mut args = HashMap::new();
+    let mut handler = |field: &str, keys: &[&str]| {
+        let (_, _, raw) = keys
+            .iter()
+            .find_map(|key| find_event_attribute(attributes, key, false))?;
+        if raw.trim_start().starts_with('$') {
+            return Some(raw.to_string());
+        }
+
+        match parse_html_handler_call(raw) {
+            Ok((name, call_args)) => {
+                if !call_args.is_empty() {
+                    args.insert(field.to_string(), call_args);
+                }
+                Some(name)
+            }
+            Err(err) => {
+                warn!("Invalid HTML handler call in attribute '{field}': {err}");
+                None
+            }
+        }
     };
 
     HtmlEventBindings {
-        onclick: attributes.get("onclick").map(|s| s.to_string()),
-        onmousedown: attributes.get("onmousedown").map(|s| s.to_string()),
-        onmouseup: attributes.get("onmouseup").map(|s| s.to_string()),
-        onmouseover: attributes
-            .get("onmouseover")
-            .or_else(|| attributes.get("onmouseenter"))
-            .map(|s| s.to_string()),
-        onmouseout: attributes
-            .get("onmouseout")
-            .or_else(|| attributes.get("onmouseleave"))
-            .map(|s| s.to_string()),
-        onchange: attributes.get("onchange").map(|s| s.to_string()),
-        oninit: attributes.get("oninit").map(|s| s.to_string()),
-        onfoucs: attributes
-            .get("onfoucs")
-            .or_else(|| attributes.get("onfocus"))
-            .map(|s| s.to_string()),
-        onscroll: attributes.get("onscroll").map(|s| s.to_string()),
-        onwheel: attributes
-            .get("onwheel")
-            .or_else(|| attributes.get("onmousewheel"))
-            .map(|s| s.to_string()),
-        onkeydown: attributes.get("onkeydown").map(|s| s.to_string()),
-        onkeyup: attributes.get("onkeyup").map(|s| s.to_string()),
-        ondragstart: attributes.get("ondragstart").map(|s| s.to_string()),
-        ondrag: attributes.get("ondrag").map(|s| s.to_string()),
-        ondragstop: attributes
-            .get("ondragstop")
-            .or_else(|| attributes.get("ondragend"))
-            .map(|s| s.to_string()),
-        ontouchstart: attributes.get("ontouchstart").map(|s| s.to_string()),
-        ontouchmove: attributes.get("ontouchmove").map(|s| s.to_string()),
-        ontouchend: attributes.get("ontouchend").map(|s| s.to_string()),
+        onclick: handler("onclick", &["onclick"]),
+        onmousedown: handler("onmousedown", &["onmousedown"]),
+        onmouseup: handler("onmouseup", &["onmouseup"]),
+        onmouseover: handler("onmouseover", &["onmouseover", "onmouseenter"]),
+        onmouseout: handler("onmouseout", &["onmouseout", "onmouseleave"]),
+        onchange: handler("onchange", &["onchange"]),
+        oninit: handler("oninit", &["oninit"]),
+        onfoucs: handler("onfoucs", &["onfoucs", "onfocus"]),
+        onscroll: handler("onscroll", &["onscroll"]),
+        onwheel: handler("onwheel", &["onwheel", "onmousewheel"]),
+        onkeydown: handler("onkeydown", &["onkeydown"]),
+        onkeyup: handler("onkeyup", &["onkeyup"]),
+        ondragstart: handler("ondragstart", &["ondragstart"]),
+        ondrag: handler("ondrag", &["ondrag"]),
+        ondragstop: handler("ondragstop", &["ondragstop", "ondragend"]),
+        ontouchstart: handler("ontouchstart", &["ontouchstart"]),
+        ontouchmove: handler("ontouchmove", &["ontouchmove"]),
+        ontouchend: handler("ontouchend", &["ontouchend"]),
+        ondblclick: handler("ondblclick", &["ondblclick"]),
+        oncontextmenu: handler("oncontextmenu", &["oncontextmenu"]),
+        onlongpress: handler("onlongpress", &["onlongpress"]),
+        ondragenter: handler("ondragenter", &["ondragenter"]),
+        ondragleave: handler("ondragleave", &["ondragleave"]),
+        ondrop: handler("ondrop", &["ondrop"]),
         inline,
+        args,
+        capture: parse_capture_attributes(attributes),
+        modifiers: parse_event_modifiers(attributes),
+    }
+}
+
+/// Bubbling event fields with the attribute names accepted for them.
+pub(crate) const BUBBLING_EVENT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("onclick", &["onclick"]),
+    ("onmousedown", &["onmousedown"]),
+    ("onmouseup", &["onmouseup"]),
+    ("onmouseover", &["onmouseover", "onmouseenter"]),
+    ("onmouseout", &["onmouseout", "onmouseleave"]),
+    ("onchange", &["onchange"]),
+    ("onwheel", &["onwheel", "onmousewheel"]),
+    ("onkeydown", &["onkeydown"]),
+    ("onkeyup", &["onkeyup"]),
+    ("ondragstart", &["ondragstart"]),
+    ("ondrag", &["ondrag"]),
+    ("ondragstop", &["ondragstop", "ondragend"]),
+    ("ontouchstart", &["ontouchstart"]),
+    ("ontouchmove", &["ontouchmove"]),
+    ("ontouchend", &["ontouchend"]),
+    ("ondblclick", &["ondblclick"]),
+    ("oncontextmenu", &["oncontextmenu"]),
+    ("onlongpress", &["onlongpress"]),
+    ("ondragenter", &["ondragenter"]),
+    ("ondragleave", &["ondragleave"]),
+    ("ondrop", &["ondrop"]),
+];
+
+/// Event fields that only reach their own widget.
+pub(crate) const DIRECT_EVENT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("oninit", &["oninit"]),
+    ("onfoucs", &["onfoucs", "onfocus"]),
+    ("onscroll", &["onscroll"]),
+];
+
+/// Finds the attribute bound to `key`, with or without modifiers such as
+/// `onchange.debounce.300ms`, returning its name, modifiers and value.
+fn find_event_attribute<'a>(
+    attributes: &'a Attributes,
+    key: &str,
+    capture: bool,
+) -> Option<(&'a str, Vec<&'a str>, &'a str)> {
+    attributes.map.iter().find_map(|(name, attribute)| {
+        let name: &str = name.local.as_ref();
+        let modifiers = match name.strip_prefix(key)? {
+            "" => Vec::new(),
+            rest => rest.strip_prefix('.')?.split('.').collect(),
+        };
+        (modifiers.contains(&"capture") == capture).then_some((
+            name,
+            modifiers,
+            attribute.value.as_str(),
+        ))
+    })
+}
+
+/// Parses `.once`, `.throttle` and `.debounce` modifiers of event attributes.
+fn parse_event_modifiers(attributes: &Attributes) -> HashMap<String, HtmlEventModifiers> {
+    let mut parsed = HashMap::new();
+    let fields = BUBBLING_EVENT_ATTRIBUTES
+        .iter()
+        .chain(DIRECT_EVENT_ATTRIBUTES);
+    for (field, keys) in fields {
+        for capture in [false, true] {
+            let Some((name, modifiers, _)) = keys
+                .iter()
+                .find_map(|key| find_event_attribute(attributes, key, capture))
+            else {
+                continue;
+            };
+
+            match HtmlEventModifiers::parse(modifiers) {
+                Ok(modifiers) if modifiers.is_empty() => {}
+                Ok(modifiers) => {
+                    let key = if capture {
+                        format!("{field}.capture")
+                    } else {
+                        field.to_string()
+                    };
+                    parsed.insert(key, modifiers);
+                }
+                Err(err) => warn!("Invalid HTML event attribute '{name}': {err}"),
+            }
+        }
+    }
+    parsed
+}
+
+/// Parses `onclick.capture="..."` style attributes into capture-phase handlers.
+fn parse_capture_attributes(attributes: &Attributes) -> HashMap<String, HtmlEventHandler> {
+    let mut capture = HashMap::new();
+    for (field, keys) in BUBBLING_EVENT_ATTRIBUTES {
+        let Some((key, _, raw)) = keys
+            .iter()
+            .find_map(|key| find_event_attribute(attributes, key, true))
+        else {
+            continue;
+        };
+
+        match parse_event_handler(raw) {
+            Ok(handler) => {
+                capture.insert(field.to_string(), handler);
+            }
+            Err(err) => warn!("Invalid HTML handler in attribute '{key}': {err}"),
+        }
+    }
+    capture
+}
+
+/// Parses an inline action (`$set(...)`) or a `handler(args...)` call.
+fn parse_event_handler(raw: &str) -> Result<HtmlEventHandler, String> {
+    let raw = raw.trim();
+    if raw.starts_with('$') {
+        parse_html_inline_action(raw).map(HtmlEventHandler::Inline)
+    } else {
+        parse_html_handler_call(raw).map(|(name, args)| HtmlEventHandler::Named { name, args })
     }
 }
 
@@ -1825,7 +2248,7 @@ fn parse_inline_attribute(
     attributes: &Attributes,
     key: &str,
 ) -> Option<crate::html::HtmlInlineAction> {
-    let raw = attributes.get(key)?;
+    let (_, _, raw) = find_event_attribute(attributes, key, false)?;
     let raw = raw.trim();
     if !raw.starts_with('$') {
         return None;
@@ -1899,6 +2322,77 @@ fn parse_validation_attributes(attributes: &Attributes) -> Option<ValidationRule
     rules
 }
 
+/// Collects all element attributes for CSS attribute selectors.
+fn parse_html_attributes(attributes: &Attributes) -> HtmlAttributes {
+    let mut parsed = HtmlAttributes::default();
+    for (name, attribute) in attributes.map.iter() {
+        parsed.set(name.local.as_ref(), attribute.value.clone());
+    }
+    parsed
+}
+
+/// Parses `draggable="true"` with its optional `data-drag` payload expression.
+fn parse_draggable_attributes(attributes: &Attributes) -> Option<HtmlDraggable> {
+    if !parse_bool_attribute(attributes, "draggable") {
+        return None;
+    }
+
+    let payload = attributes
+        .get("data-drag")
+        .filter(|raw| !raw.trim().is_empty())
+        .and_then(|raw| match parse_html_inline_expr(raw) {
+            Ok(expr) => Some(expr),
+            Err(err) => {
+                warn!("Invalid data-drag payload: {err}");
+                None
+            }
+        });
+    Some(HtmlDraggable { payload })
+}
+
+/// Collects the `hotkey`/`accesskey` chord of a widget and its direct
+/// `<shortcut keys="Ctrl+S" action="save_game">` children.
+fn parse_shortcuts(node: &NodeRef, attributes: &Attributes) -> Option<HtmlShortcuts> {
+    let mut shortcuts = Vec::new();
+    if let Some(raw) = attributes
+        .get("hotkey")
+        .or_else(|| attributes.get("accesskey"))
+    {
+        match HtmlKeyChord::parse(raw) {
+            Ok(chord) => shortcuts.push(HtmlShortcut {
+                chord,
+                handler: None,
+            }),
+            Err(err) => warn!("Invalid hotkey: {err}"),
+        }
+    }
+
+    for child in node.children() {
+        let Some(element) = child.as_element() else {
+            continue;
+        };
+        if !element.name.local.eq("shortcut") {
+            continue;
+        }
+        let attrs = element.attributes.borrow();
+        let (Some(keys), Some(action)) = (attrs.get("keys"), attrs.get("action")) else {
+            warn!("<shortcut> needs both `keys` and `action`");
+            continue;
+        };
+        match HtmlKeyChord::parse(keys).and_then(|chord| {
+            parse_event_handler(action).map(|handler| HtmlShortcut {
+                chord,
+                handler: Some(handler),
+            })
+        }) {
+            Ok(shortcut) => shortcuts.push(shortcut),
+            Err(err) => warn!("Invalid <shortcut>: {err}"),
+        }
+    }
+
+    (!shortcuts.is_empty()).then_some(HtmlShortcuts(shortcuts))
+}
+
 /// Parses boolean attributes with `true`/`false` semantics.
 fn parse_bool_attribute(attributes: &Attributes, key: &str) -> bool {
     if !attributes.contains(key) {
@@ -2359,7 +2853,8 @@ fn extract_direct_child_tags(inner_html: &str) -> Vec<String> {
 }
 
 const TEMPLATE_MAX_RECURSION: usize = 64;
-static TEMPLATE_USE_RE: Lazy<Regex> = Lazy::new(|| {
+/// Matches one `@use "path" [as alias];` line; group 1 is the target, group 2 the alias.
+pub static TEMPLATE_USE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"(?m)^[ \t]*@use[ \t]+"([^"]+)"(?:[ \t]+as[ \t]+([A-Za-z_][A-Za-z0-9_]*|\*))?[ \t]*;[ \t]*\r?$"#)
         .unwrap()
 });
@@ -2372,6 +2867,9 @@ static COMPONENT_LOCAL_TYPE_RE: Lazy<Regex> = Lazy::new(|| {
 
 #[derive(Debug, Clone)]
 struct TemplateUseDirective {
+    /// Index of the checked source the directive was declared in.
+    source: usize,
+    offset: usize,
     target: String,
     alias: String,
     wildcard: bool,
@@ -2387,6 +2885,8 @@ struct ExpandedUseTarget {
 #[derive(Debug, Clone)]
 struct TemplateValueContext {
     values: HashMap<String, JsonValue>,
+    /// Names bound by enclosing `@for` headers.
+    locals: HashSet<String>,
 }
 
 impl TemplateValueContext {
@@ -2395,6 +2895,7 @@ impl TemplateValueContext {
         shared: &UiSharedValues,
         use_directives: &[TemplateUseDirective],
         local_use_aliases: &HashMap<String, String>,
+        sink: &mut TemplateDiagnosticSink,
     ) -> Self {
         let mut values = HashMap::new();
         for (key, value) in &vars.vars {
@@ -2456,25 +2957,34 @@ impl TemplateValueContext {
                 resolve_shared_use_target(shared, &directive.target)
             else {
                 if !shared_use_target_known(shared, &directive.target) {
-                    warn!("Unknown @use target '{}'", directive.target);
+                    sink.directive_warning(
+                        directive,
+                        format!("Unknown @use target '{}'", directive.target),
+                    );
                 }
                 continue;
             };
 
             if directive.wildcard {
                 let Some(map) = shared_value.as_object() else {
-                    warn!(
-                        "@use \"{}\" as * requires an object/struct value",
-                        directive.target
+                    sink.directive_warning(
+                        directive,
+                        format!(
+                            "@use \"{}\" as * requires an object/struct value",
+                            directive.target
+                        ),
                     );
                     continue;
                 };
 
                 for (field, field_value) in map {
                     if values.contains_key(field) {
-                        warn!(
-                            "Duplicate @use field '{}' ignored (target '{}')",
-                            field, directive.target
+                        sink.directive_warning(
+                            directive,
+                            format!(
+                                "Duplicate @use field '{}' ignored (target '{}')",
+                                field, directive.target
+                            ),
                         );
                         continue;
                     }
@@ -2497,16 +3007,29 @@ impl TemplateValueContext {
                     continue;
                 }
                 if values.contains_key(&directive.alias) {
-                    warn!(
-                        "Alias '{}' from @use \"{}\" already exists in template context",
-                        directive.alias, directive.target
+                    sink.directive_warning(
+                        directive,
+                        format!(
+                            "Alias '{}' from @use \"{}\" already exists in template context",
+                            directive.alias, directive.target
+                        ),
                     );
                 }
                 values.insert(directive.alias.clone(), shared_value.clone());
             }
         }
 
-        Self { values }
+        // Reflected resources are always reachable as `res:Type`.
+        for (key, value) in &shared.values {
+            if key.starts_with(REFLECT_RESOURCE_PREFIX) {
+                values.insert(key.clone(), value.clone());
+            }
+        }
+
+        Self {
+            values,
+            locals: HashSet::new(),
+        }
     }
 
     fn with_iteration(
@@ -2517,14 +3040,17 @@ impl TemplateValueContext {
         index: usize,
     ) -> Self {
         let mut values = self.values.clone();
+        let mut locals = self.locals.clone();
         values.insert(item_name.to_string(), item_value);
+        locals.insert(item_name.to_string());
         if let Some(index_name) = index_name {
             values.insert(
                 index_name.to_string(),
                 JsonValue::Number(serde_json::Number::from(index as u64)),
             );
+            locals.insert(index_name.to_string());
         }
-        Self { values }
+        Self { values, locals }
     }
 }
 
@@ -2537,15 +3063,76 @@ fn parse_template_context_value(raw: &str) -> JsonValue {
     serde_json::from_str(trimmed).unwrap_or_else(|_| JsonValue::String(raw.to_string()))
 }
 
+/// Collects located diagnostics while a single template is preprocessed.
+///
+/// Directive offsets point into `source` (the template with `@use` lines
+/// stripped); `@use` offsets point into `use_source`. Both share line numbers.
+struct TemplateDiagnosticSink<'a> {
+    source_path: &'a str,
+    source: &'a str,
+    use_source: &'a str,
+    /// Index of the checked source; `@use` warnings from other sources are dropped.
+    source_index: usize,
+    diagnostics: Vec<TemplateDiagnostic>,
+}
+
+impl<'a> TemplateDiagnosticSink<'a> {
+    fn new(source_path: &'a str, use_source: &'a str) -> Self {
+        Self {
+            source_path,
+            source: use_source,
+            use_source,
+            source_index: 0,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn error(&mut self, offset: usize, message: impl Into<String>) {
+        let diagnostic = TemplateDiagnostic::error(self.source_path, self.source, offset, message);
+        self.push(diagnostic);
+    }
+
+    fn warning(&mut self, offset: usize, message: impl Into<String>) {
+        let diagnostic =
+            TemplateDiagnostic::warning(self.source_path, self.source, offset, message);
+        self.push(diagnostic);
+    }
+
+    fn use_warning(&mut self, offset: usize, message: impl Into<String>) {
+        let diagnostic =
+            TemplateDiagnostic::warning(self.source_path, self.use_source, offset, message);
+        self.push(diagnostic);
+    }
+
+    fn directive_warning(&mut self, directive: &TemplateUseDirective, message: impl Into<String>) {
+        if directive.source == self.source_index {
+            self.use_warning(directive.offset, message);
+        }
+    }
+
+    /// Adds a diagnostic once; `@for` bodies are rendered per item and would repeat it.
+    fn push(&mut self, diagnostic: TemplateDiagnostic) {
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct TemplateCursor<'a> {
     src: &'a str,
     idx: usize,
+    base: usize,
 }
 
 impl<'a> TemplateCursor<'a> {
-    fn new(src: &'a str) -> Self {
-        Self { src, idx: 0 }
+    fn new(src: &'a str, base: usize) -> Self {
+        Self { src, idx: 0, base }
+    }
+
+    /// Byte offset of the cursor inside the top-level template.
+    fn offset(&self) -> usize {
+        self.base + self.idx
     }
 
     fn is_eof(&self) -> bool {
@@ -2626,30 +3213,124 @@ pub fn preprocess_template_directives_with_shared(
 
 /// Preprocesses template directives with typed shared values, `@use` imports,
 /// and implicit aliases for types declared in the matching `*.component.rs`.
+///
+/// Template problems are logged; use
+/// [`preprocess_template_directives_with_diagnostics`] to receive them instead.
 pub fn preprocess_template_directives_with_shared_and_local_types<T: AsRef<str>>(
     template: &str,
     vars: &UiLangVariables,
     shared: &UiSharedValues,
     local_type_names: &[T],
 ) -> String {
-    let (use_directives, cleaned_template) = extract_template_use_directives(template);
+    let (rendered, diagnostics) = preprocess_template_directives_with_diagnostics(
+        template,
+        vars,
+        shared,
+        local_type_names,
+        "",
+    );
+    for diagnostic in &diagnostics {
+        diagnostic.log();
+    }
+    rendered
+}
+
+/// Preprocesses template directives and returns the rendered template together
+/// with located diagnostics for malformed directives and unresolved `@use` imports.
+///
+/// `source_path` is only used to label the diagnostics.
+pub fn preprocess_template_directives_with_diagnostics<T: AsRef<str>>(
+    template: &str,
+    vars: &UiLangVariables,
+    shared: &UiSharedValues,
+    local_type_names: &[T],
+    source_path: &str,
+) -> (String, Vec<TemplateDiagnostic>) {
+    let mut sink = TemplateDiagnosticSink::new(source_path, template);
+    let (use_directives, cleaned_template) = extract_template_use_directives(template, &mut sink);
+    sink.source = &cleaned_template;
     let local_use_aliases = build_local_use_aliases(shared, local_type_names);
-    let context =
-        TemplateValueContext::from_sources(vars, shared, &use_directives, &local_use_aliases);
-    render_template_with_context(&cleaned_template, &context, 0)
+    let context = TemplateValueContext::from_sources(
+        vars,
+        shared,
+        &use_directives,
+        &local_use_aliases,
+        &mut sink,
+    );
+    let rendered = render_template_with_context(&cleaned_template, &context, 0, 0, &mut sink);
+    (rendered, sink.diagnostics)
+}
+
+/// Collects template diagnostics for sources that are rendered as one document.
+///
+/// Every `(source_path, template)` pair is checked in its own coordinates, so
+/// a problem inside an inlined component points at the component file instead
+/// of the expanded page. `@use` imports of all sources share one value context,
+/// like they do after the templates are combined.
+pub fn template_diagnostics_for_sources<T: AsRef<str>>(
+    sources: &[(&str, &str)],
+    vars: &UiLangVariables,
+    shared: &UiSharedValues,
+    local_type_names: &[T],
+) -> Vec<TemplateDiagnostic> {
+    collect_source_template_diagnostics(sources, 0, vars, shared, local_type_names)
+}
+
+/// Renders the sources from index `first` on for diagnostics; `@use` imports
+/// of the skipped sources still take part in the shared value context.
+fn collect_source_template_diagnostics<T: AsRef<str>>(
+    sources: &[(&str, &str)],
+    first: usize,
+    vars: &UiLangVariables,
+    shared: &UiSharedValues,
+    local_type_names: &[T],
+) -> Vec<TemplateDiagnostic> {
+    let mut use_directives = Vec::new();
+    let mut extracted = Vec::with_capacity(sources.len());
+    for (index, (source_path, template)) in sources.iter().enumerate() {
+        let mut sink = TemplateDiagnosticSink::new(source_path, template);
+        sink.source_index = index;
+        let (directives, cleaned_template) = extract_template_use_directives(template, &mut sink);
+        use_directives.extend(directives);
+        extracted.push((cleaned_template, sink.diagnostics));
+    }
+
+    let local_use_aliases = build_local_use_aliases(shared, local_type_names);
+    let mut diagnostics = Vec::new();
+    for (index, ((source_path, template), (cleaned_template, use_diagnostics))) in
+        sources.iter().zip(extracted).enumerate().skip(first)
+    {
+        let mut sink = TemplateDiagnosticSink::new(source_path, template);
+        sink.source_index = index;
+        sink.diagnostics = use_diagnostics;
+        let context = TemplateValueContext::from_sources(
+            vars,
+            shared,
+            &use_directives,
+            &local_use_aliases,
+            &mut sink,
+        );
+        sink.source = &cleaned_template;
+        render_template_with_context(&cleaned_template, &context, 0, 0, &mut sink);
+        diagnostics.extend(sink.diagnostics);
+    }
+    diagnostics
 }
 
 fn render_template_with_context(
     template: &str,
     context: &TemplateValueContext,
     depth: usize,
+    base: usize,
+    sink: &mut TemplateDiagnosticSink,
 ) -> String {
     if depth > TEMPLATE_MAX_RECURSION {
+        sink.error(base, "template directives are nested too deeply");
         return template.to_string();
     }
 
-    let mut cursor = TemplateCursor::new(template);
-    render_template_segment(&mut cursor, context, depth, false)
+    let mut cursor = TemplateCursor::new(template, base);
+    render_template_segment(&mut cursor, context, depth, false, sink)
 }
 
 fn render_template_segment(
@@ -2657,6 +3338,7 @@ fn render_template_segment(
     context: &TemplateValueContext,
     depth: usize,
     stop_on_block_end: bool,
+    sink: &mut TemplateDiagnosticSink,
 ) -> String {
     let mut output = String::new();
 
@@ -2672,19 +3354,35 @@ fn render_template_segment(
         }
 
         if is_template_directive(cursor, "@if") {
-            if let Some(rendered) = parse_if_directive(cursor, context, depth + 1) {
+            if let Some(rendered) = parse_if_directive(cursor, context, depth + 1, sink) {
                 output.push_str(&rendered);
                 continue;
             }
         }
 
         if is_template_directive(cursor, "@for") {
-            if let Some(rendered) = parse_for_directive(cursor, context, depth + 1) {
+            if let Some(rendered) = parse_for_directive(cursor, context, depth + 1, sink) {
                 output.push_str(&rendered);
                 continue;
             }
         }
 
+        if cursor.starts_with("on")
+            && output.ends_with(char::is_whitespace)
+            && let Some(rendered) = render_handler_call_attribute(cursor, context)
+        {
+            output.push_str(&rendered);
+            continue;
+        }
+
+        if cursor.starts_with("data-drag")
+            && output.ends_with(char::is_whitespace)
+            && let Some(rendered) = render_drag_payload_attribute(cursor, context)
+        {
+            output.push_str(&rendered);
+            continue;
+        }
+
         if let Some(ch) = cursor.next_char() {
             output.push(ch);
         } else {
@@ -2695,6 +3393,96 @@ fn render_template_segment(
     output
 }
 
+/// Replaces `@for` variables in `onclick="handler(item.id, 'a')"` arguments
+/// with their values, so every rendered row passes its own item.
+///
+/// Other arguments stay as written and are resolved when the event fires.
+fn render_handler_call_attribute(
+    cursor: &mut TemplateCursor,
+    context: &TemplateValueContext,
+) -> Option<String> {
+    if context.locals.is_empty() {
+        return None;
+    }
+    let caps = HANDLER_CALL_ATTRIBUTE_RE.captures(cursor.remaining())?;
+    let raw_args = caps.get(3)?.as_str();
+    if raw_args.contains("{{") {
+        return None;
+    }
+
+    let args = split_top_level(raw_args, ',')
+        .iter()
+        .map(|arg| render_handler_argument(arg, context))
+        .collect::<Vec<_>>()
+        .join(", ");
+    cursor.idx += caps.get(0)?.end();
+    Some(format!("{}{}({args})\"", &caps[1], &caps[2]))
+}
+
+/// Replaces `@for` variables in `data-drag="item"` payloads with their values.
+fn render_drag_payload_attribute(
+    cursor: &mut TemplateCursor,
+    context: &TemplateValueContext,
+) -> Option<String> {
+    if context.locals.is_empty() {
+        return None;
+    }
+    let caps = DRAG_PAYLOAD_ATTRIBUTE_RE.captures(cursor.remaining())?;
+    let raw = caps.get(2)?.as_str();
+    if raw.contains("{{") {
+        return None;
+    }
+
+    let payload = render_handler_argument(raw, context);
+    cursor.idx += caps.get(0)?.end();
+    Some(format!("{}{payload}\"", &caps[1]))
+}
+
+fn render_handler_argument(raw: &str, context: &TemplateValueContext) -> String {
+    let reads_local = tokenize_expression(raw).is_some_and(|tokens| {
+        tokens.iter().enumerate().any(|(index, token)| {
+            matches!(token, ExprToken::Identifier(name) if context.locals.contains(name))
+                && (index == 0 || tokens[index - 1] != ExprToken::Dot)
+        })
+    });
+    if !reads_local {
+        return raw.to_string();
+    }
+
+    match evaluate_expression(raw, context) {
+        Some(value) => serde_json::to_string(&whole_numbers_as_integers(value))
+            .unwrap_or_default()
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;"),
+        None => raw.to_string(),
+    }
+}
+
+/// Template numbers are `f64`; emit whole ones as integers so handlers can
+/// deserialize them into integer types.
+fn whole_numbers_as_integers(value: JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Number(number) if !number.is_i64() && !number.is_u64() => {
+            match number.as_f64() {
+                Some(float) if float.fract() == 0.0 && float.abs() < i64::MAX as f64 => {
+                    JsonValue::from(float as i64)
+                }
+                _ => JsonValue::Number(number),
+            }
+        }
+        JsonValue::Array(items) => {
+            JsonValue::Array(items.into_iter().map(whole_numbers_as_integers).collect())
+        }
+        JsonValue::Object(map) => JsonValue::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, whole_numbers_as_integers(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
 fn is_template_directive(cursor: &TemplateCursor, keyword: &str) -> bool {
     if !cursor.starts_with(keyword) {
         return false;
@@ -2708,13 +3496,15 @@ fn parse_if_directive(
     cursor: &mut TemplateCursor,
     context: &TemplateValueContext,
     depth: usize,
+    sink: &mut TemplateDiagnosticSink,
 ) -> Option<String> {
     let checkpoint = cursor.idx;
+    let directive_offset = cursor.offset();
     if !cursor.consume_str("@if") {
         return None;
     }
     cursor.skip_whitespace();
-    let condition_raw = match extract_group_content(cursor, '(', ')') {
+    let condition_raw = match extract_directive_group(cursor, "@if condition", sink) {
         Some(value) => value,
         None => {
             cursor.idx = checkpoint;
@@ -2722,7 +3512,8 @@ fn parse_if_directive(
         }
     };
     cursor.skip_whitespace();
-    let then_source = match extract_block_content(cursor) {
+    let then_offset = cursor.offset() + 1;
+    let then_source = match extract_directive_block(cursor, "@if", sink) {
         Some(value) => value,
         None => {
             cursor.idx = checkpoint;
@@ -2732,10 +3523,12 @@ fn parse_if_directive(
 
     let mut else_source = None;
     cursor.skip_whitespace();
+    let mut else_offset = then_offset;
     if is_template_directive(cursor, "@else") {
         cursor.consume_str("@else");
         cursor.skip_whitespace();
-        else_source = match extract_block_content(cursor) {
+        else_offset = cursor.offset() + 1;
+        else_source = match extract_directive_block(cursor, "@else", sink) {
             Some(value) => Some(value),
             None => {
                 cursor.idx = checkpoint;
@@ -2744,27 +3537,40 @@ fn parse_if_directive(
         };
     }
 
+    if condition_raw.trim().is_empty() {
+        sink.error(directive_offset, "@if condition is empty");
+    }
+
     let condition = evaluate_condition_expression(condition_raw.as_str(), context);
-    let selected = if condition {
-        then_source
+    let (selected, selected_offset) = if condition {
+        (then_source, then_offset)
     } else {
-        else_source.unwrap_or_default()
+        (else_source.unwrap_or_default(), else_offset)
     };
 
-    Some(render_template_with_context(&selected, context, depth))
+    Some(render_template_with_context(
+        &selected,
+        context,
+        depth,
+        selected_offset,
+        sink,
+    ))
 }
 
 fn parse_for_directive(
     cursor: &mut TemplateCursor,
     context: &TemplateValueContext,
     depth: usize,
+    sink: &mut TemplateDiagnosticSink,
 ) -> Option<String> {
     let checkpoint = cursor.idx;
+    let directive_offset = cursor.offset();
     if !cursor.consume_str("@for") {
         return None;
     }
     cursor.skip_whitespace();
-    let header = match extract_group_content(cursor, '(', ')') {
+    let header_offset = cursor.offset() + 1;
+    let header = match extract_directive_group(cursor, "@for header", sink) {
         Some(value) => value,
         None => {
             cursor.idx = checkpoint;
@@ -2772,7 +3578,8 @@ fn parse_for_directive(
         }
     };
     cursor.skip_whitespace();
-    let block_source = match extract_block_content(cursor) {
+    let block_offset = cursor.offset() + 1;
+    let block_source = match extract_directive_block(cursor, "@for", sink) {
         Some(value) => value,
         None => {
             cursor.idx = checkpoint;
@@ -2783,12 +3590,22 @@ fn parse_for_directive(
     let (item_name, index_name, iterable_expression) = match parse_for_header(&header) {
         Some(value) => value,
         None => {
+            sink.error(
+                header_offset,
+                format!(
+                    "invalid @for header `{}`; expected `item in list` or `item, index in list`",
+                    header.trim()
+                ),
+            );
             cursor.idx = checkpoint;
             return None;
         }
     };
     let Some(iterable_value) = evaluate_expression(&iterable_expression, context) else {
-        warn!("Failed to evaluate @for expression: {iterable_expression}");
+        sink.warning(
+            directive_offset,
+            format!("Failed to evaluate @for expression: {iterable_expression}"),
+        );
         return Some(String::new());
     };
 
@@ -2796,7 +3613,13 @@ fn parse_for_directive(
     for (index, item) in iterable_items(iterable_value).into_iter().enumerate() {
         let iteration_context =
             context.with_iteration(&item_name, item, index_name.as_deref(), index);
-        let nested = render_template_with_context(&block_source, &iteration_context, depth);
+        let nested = render_template_with_context(
+            &block_source,
+            &iteration_context,
+            depth,
+            block_offset,
+            sink,
+        );
         rendered.push_str(&interpolate_inline_placeholders(
             &nested,
             &iteration_context,
@@ -2815,29 +3638,37 @@ fn parse_for_header(header: &str) -> Option<(String, Option<String>, String)> {
     Some((item_name, index_name, iterable_expression))
 }
 
-fn extract_template_use_directives(template: &str) -> (Vec<TemplateUseDirective>, String) {
+fn extract_template_use_directives(
+    template: &str,
+    sink: &mut TemplateDiagnosticSink,
+) -> (Vec<TemplateUseDirective>, String) {
     let mut directives = Vec::new();
 
     for captures in TEMPLATE_USE_RE.captures_iter(template) {
-        let Some(target) = captures.get(1).map(|m| m.as_str().trim().to_string()) else {
+        let Some(target_match) = captures.get(1) else {
             continue;
         };
+        let offset = target_match.start();
+        let target = target_match.as_str().trim().to_string();
         let line_alias = captures.get(2).map(|m| m.as_str().trim().to_string());
         let expanded_targets = expand_use_targets(&target);
 
         if line_alias.is_some() && expanded_targets.len() > 1 {
-            warn!(
-                "@use \"{}\" imports multiple targets; ignoring shared alias",
-                target
+            sink.use_warning(
+                offset,
+                format!("@use \"{target}\" imports multiple targets; ignoring shared alias"),
             );
         }
 
         for expanded_target in expanded_targets {
             let path_wildcard = is_path_wildcard_use_target(&expanded_target.target);
             if path_wildcard && (line_alias.is_some() || expanded_target.alias.as_ref().is_some()) {
-                warn!(
-                    "@use \"{}\" imports a path wildcard; ignoring alias",
-                    expanded_target.target
+                sink.use_warning(
+                    offset,
+                    format!(
+                        "@use \"{}\" imports a path wildcard; ignoring alias",
+                        expanded_target.target
+                    ),
                 );
             }
             let alias = if path_wildcard {
@@ -2854,6 +3685,8 @@ fn extract_template_use_directives(template: &str) -> (Vec<TemplateUseDirective>
             };
 
             directives.push(TemplateUseDirective {
+                source: sink.source_index,
+                offset,
                 target: expanded_target.target,
                 wildcard: alias == "*" && !path_wildcard,
                 path_wildcard,
@@ -3312,6 +4145,45 @@ fn extract_block_content(cursor: &mut TemplateCursor) -> Option<String> {
     extract_group_content(cursor, '{', '}')
 }
 
+/// Extracts a `( ... )` directive group and reports a missing or unclosed parenthesis.
+fn extract_directive_group(
+    cursor: &mut TemplateCursor,
+    label: &str,
+    sink: &mut TemplateDiagnosticSink,
+) -> Option<String> {
+    let offset = cursor.offset();
+    if cursor.peek_char() != Some('(') {
+        // Plain text such as "@if" in prose is kept as-is, so this is only a warning.
+        sink.warning(offset, format!("expected `(` to open {label}"));
+        return None;
+    }
+
+    let content = extract_group_content(cursor, '(', ')');
+    if content.is_none() {
+        sink.error(offset, format!("unclosed `(` in {label}"));
+    }
+    content
+}
+
+/// Extracts a `{ ... }` directive block and reports a missing or unclosed brace.
+fn extract_directive_block(
+    cursor: &mut TemplateCursor,
+    label: &str,
+    sink: &mut TemplateDiagnosticSink,
+) -> Option<String> {
+    let offset = cursor.offset();
+    if cursor.peek_char() != Some('{') {
+        sink.error(offset, format!("expected `{{` to open {label} block"));
+        return None;
+    }
+
+    let content = extract_block_content(cursor);
+    if content.is_none() {
+        sink.error(offset, format!("unclosed `{{` in {label} block"));
+    }
+    content
+}
+
 fn interpolate_inline_placeholders(value: &str, context: &TemplateValueContext) -> String {
     INNER_BINDING_RE
         .replace_all(value, |caps: &regex::Captures| {
@@ -3556,6 +4428,26 @@ fn tokenize_expression(expression: &str) -> Option<Vec<ExprToken>> {
                 chars.next();
             }
 
+            // `res:Type` names a reflected resource and stays a single identifier.
+            if ident == "res" {
+                let mut lookahead = chars.clone();
+                if lookahead.next() == Some(':')
+                    && lookahead
+                        .next()
+                        .is_some_and(|next| next.is_ascii_alphabetic() || next == '_')
+                {
+                    chars.next();
+                    ident.push(':');
+                    while let Some(next) = chars.peek().copied() {
+                        if !(next.is_ascii_alphanumeric() || next == '_') {
+                            break;
+                        }
+                        ident.push(next);
+                        chars.next();
+                    }
+                }
+            }
+
             let lower = ident.to_ascii_lowercase();
             if lower == "true" {
                 tokens.push(ExprToken::Bool(true));
@@ -4110,13 +5002,109 @@ fn source_requires_value_reparse(
         .is_none_or(|profile| profile.requires_reparse(changed_roots))
 }
 
+/// Returns `true` when a `{{ ... }}` binding reads one of the changed paths,
+/// a parent of one, or a value below one.
+fn binding_affected_by(binding: &str, changed_paths: &HashSet<String>) -> bool {
+    binding_paths(binding).iter().any(|path| {
+        changed_paths
+            .iter()
+            .any(|changed| binding_paths_overlap(path, changed))
+    })
+}
+
+fn binding_paths_overlap(a: &str, b: &str) -> bool {
+    let is_parent = |parent: &str, child: &str| {
+        child.len() > parent.len()
+            && child.starts_with(parent)
+            && child.as_bytes()[parent.len()] == b'.'
+    };
+    a == b || is_parent(a, b) || is_parent(b, a)
+}
+
+/// Collects the data paths read by a binding expression.
+///
+/// `player.name.startsWith("A")` reads `player.name`; method calls end a path
+/// because getters like `get_name()` map to fields at evaluation time.
+fn binding_paths(binding: &str) -> Vec<String> {
+    let expression = binding
+        .trim()
+        .trim_start_matches("{{")
+        .trim_end_matches("}}");
+    let chars = expression.chars().collect::<Vec<_>>();
+    let is_ident = |ch: char| ch == '_' || ch.is_alphanumeric();
+    let is_reflect_prefix = |index: usize| {
+        chars[index..].starts_with(&['r', 'e', 's', ':'])
+            && chars.get(index + 4).is_some_and(|ch| is_ident(*ch))
+    };
+    let mut paths = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let ch = chars[index];
+        if let Some(active_quote) = quote {
+            if ch == '\\' {
+                index += 2;
+                continue;
+            }
+            if ch == active_quote {
+                quote = None;
+            }
+            index += 1;
+            continue;
+        }
+        if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+            index += 1;
+            continue;
+        }
+        if !(ch == '_' || ch.is_alphabetic()) {
+            index += 1;
+            continue;
+        }
+
+        // Segments after a call result (`items.first().name`) are not store paths.
+        let after_call = index > 0 && chars[index - 1] == '.';
+        let mut segments = Vec::new();
+        loop {
+            let start = index;
+            if segments.is_empty() && is_reflect_prefix(index) {
+                index += REFLECT_RESOURCE_PREFIX.len();
+            }
+            while index < chars.len() && is_ident(chars[index]) {
+                index += 1;
+            }
+            if chars.get(index) == Some(&'(') {
+                break;
+            }
+            segments.push(chars[start..index].iter().collect::<String>());
+            if chars.get(index) == Some(&'.')
+                && chars.get(index + 1).is_some_and(|ch| is_ident(*ch))
+            {
+                index += 1;
+                continue;
+            }
+            break;
+        }
+
+        if !after_call && !segments.is_empty() {
+            paths.push(segments.join("."));
+        }
+    }
+
+    paths
+}
+
 fn binding_root(binding: &str) -> String {
     let trimmed = binding
         .trim()
         .trim_start_matches("{{")
         .trim_end_matches("}}")
         .trim();
-    let mut root = String::new();
+    let (mut root, trimmed) = match trimmed.strip_prefix(REFLECT_RESOURCE_PREFIX) {
+        Some(rest) => (REFLECT_RESOURCE_PREFIX.to_string(), rest),
+        None => (String::new(), trimmed),
+    };
     for ch in trimmed.chars() {
         if ch == '_' || ch.is_ascii_alphanumeric() {
             root.push(ch);
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use std::fmt;
+
+/// Severity of a [`TemplateDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TemplateDiagnosticSeverity {
+    /// Recoverable problem; the template is still rendered.
+    Warning,
+    /// Template could not be compiled as written.
+    Error,
+}
+
+impl fmt::Display for TemplateDiagnosticSeverity {
+    /// Formats the severity as a lowercase label.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// Structured template compile diagnostic with a source location.
+///
+/// `line` and `column` are 1-based; `column` counts characters, not bytes.
+/// Emitted as a message from the HTML converter for every template problem.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct TemplateDiagnostic {
+    pub source_path: String,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub severity: TemplateDiagnosticSeverity,
+    pub message: String,
+}
+
+impl TemplateDiagnostic {
+    /// Creates a diagnostic for the byte `offset` inside `source`.
+    pub fn at(
+        source_path: impl Into<String>,
+        source: &str,
+        offset: usize,
+        severity: TemplateDiagnosticSeverity,
+        message: impl Into<String>,
+    ) -> Self {
+        let (line, column) = line_column(source, offset);
+        Self {
+            source_path: source_path.into(),
+            line,
+            column,
+            snippet: line_snippet(source, offset),
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error diagnostic for the byte `offset` inside `source`.
+    pub fn error(
+        source_path: impl Into<String>,
+        source: &str,
+        offset: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::at(
+            source_path,
+            source,
+            offset,
+            TemplateDiagnosticSeverity::Error,
+            message,
+        )
+    }
+
+    /// Creates a warning diagnostic for the byte `offset` inside `source`.
+    pub fn warning(
+        source_path: impl Into<String>,
+        source: &str,
+        offset: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::at(
+            source_path,
+            source,
+            offset,
+            TemplateDiagnosticSeverity::Warning,
+            message,
+        )
+    }
+
+    /// Returns `true` for error diagnostics.
+    pub fn is_error(&self) -> bool {
+        self.severity == TemplateDiagnosticSeverity::Error
+    }
+
+    /// Logs the diagnostic with the matching log level.
+    pub fn log(&self) {
+        match self.severity {
+            TemplateDiagnosticSeverity::Warning => warn!("{self}"),
+            TemplateDiagnosticSeverity::Error => error!("{self}"),
+        }
+    }
+}
+
+impl fmt::Display for TemplateDiagnostic {
+    /// Formats the diagnostic as `path:line:column: severity: message`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.source_path.is_empty() {
+            "<template>"
+        } else {
+            self.source_path.as_str()
+        };
+        write!(
+            f,
+            "{path}:{}:{}: {}: {}",
+            self.line, self.column, self.severity, self.message
+        )?;
+        if !self.snippet.is_empty() {
+            write!(f, "\n    | {}", self.snippet)?;
+        }
+        Ok(())
+    }
+}
+
+/// Controls how template diagnostics are handled at runtime.
+///
+/// In `strict` mode the first error diagnostic aborts with a panic instead of
+/// skipping the broken template.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TemplateDiagnosticsConfig {
+    pub strict: bool,
+}
+
+/// Returns the 1-based line and character column for a byte offset.
+pub fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let offset = clamp_to_char_boundary(source, offset);
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Returns the trimmed source line containing a byte offset.
+fn line_snippet(source: &str, offset: usize) -> String {
+    let offset = clamp_to_char_boundary(source, offset);
+    let line_start = source[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |idx| offset + idx);
+    source[line_start..line_end].trim_end().to_string()
+}
+
+/// Clamps `offset` into `source` and moves it back to a char boundary.
+fn clamp_to_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
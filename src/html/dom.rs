@@ -0,0 +1,559 @@
+use bevy::ecs::component::Mutable;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::html::builder::spawn_widget_node;
+use crate::html::converter::parse_html_fragment;
+use crate::html::snapshot::{UiSnapshotNode, write_html, write_snapshot};
+use crate::html::ui::UiNode;
+use crate::html::{
+    HtmlAttributes, HtmlID, HtmlInnerContent, HtmlSource, HtmlStyle, HtmlTextBinding,
+};
+use crate::io::DefaultCssHandle;
+use crate::services::css_service::{CssParentSelectorEntry, matches_css_selector};
+use crate::styles::parser::apply_declaration_to_style;
+use crate::styles::{CssClass, CssID, CssSource, CssVariables, Style, TagName};
+use crate::widgets::body::BodyContentRoot;
+use crate::widgets::div::DivContentRoot;
+use crate::widgets::{Body, Button, Headline, Paragraph, ToolTip, UIWidgetState, Widget};
+
+/// Attributes that are on while present, e.g. `disabled=""`.
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "disabled", "readonly", "checked", "open", "hidden", "required",
+];
+
+type UiDomNode<'a> = (
+    Entity,
+    Option<&'a CssID>,
+    Option<&'a CssClass>,
+    &'a TagName,
+    Option<&'a ChildOf>,
+);
+
+type UiDomHost<'a> = (
+    Option<&'a CssSource>,
+    Option<&'a Widget>,
+    Option<&'a BodyContentRoot>,
+    Option<&'a DivContentRoot>,
+);
+
+/// Where [`UiDom::insert_html`] places a fragment, named like the DOM's
+/// `insertAdjacentHTML`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HtmlInsertPosition {
+    /// Before the target, as its previous sibling.
+    BeforeBegin,
+    /// Inside the target, before its first child.
+    AfterBegin,
+    /// Inside the target, after its last child.
+    #[default]
+    BeforeEnd,
+    /// After the target, as its next sibling.
+    AfterEnd,
+}
+
+/// Queries and mutates spawned widgets by CSS selector.
+///
+/// Selectors are matched like stylesheet rules (`#inventory .slot`,
+/// `.hotbar > .slot`, comma lists). Mutations are applied when the
+/// system's commands are flushed.
+///
+/// ```ignore
+/// fn refresh_slots(mut dom: UiDom) {
+///     for slot in dom.query("#inventory .slot") {
+///         dom.toggle_class(slot, "empty");
+///     }
+///     if let Some(title) = dom.by_id("title") {
+///         dom.set_text(title, "Inventory");
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct UiDom<'w, 's> {
+    commands: Commands<'w, 's>,
+    nodes: Query<'w, 's, UiDomNode<'static>>,
+    parents: Query<'w, 's, CssParentSelectorEntry<'static>>,
+    children: Query<'w, 's, &'static Children>,
+    hosts: Query<'w, 's, UiDomHost<'static>>,
+    snapshot: Query<'w, 's, UiSnapshotNode<'static>>,
+    asset_server: Option<Res<'w, AssetServer>>,
+    default_css: Option<Res<'w, DefaultCssHandle>>,
+}
+
+impl UiDom<'_, '_> {
+    /// Returns all widgets matching `selector` in document order.
+    pub fn query(&self, selector: &str) -> Vec<Entity> {
+        let mut found = Vec::new();
+        for (entity, ..) in self.nodes.iter() {
+            if self.is_root(entity) {
+                self.collect(entity, selector, &mut found);
+            }
+        }
+        found
+    }
+
+    /// Returns the first widget matching `selector`.
+    pub fn query_one(&self, selector: &str) -> Option<Entity> {
+        self.query(selector).into_iter().next()
+    }
+
+    /// Returns the widget declared with `id="..."`.
+    pub fn by_id(&self, id: &str) -> Option<Entity> {
+        self.nodes
+            .iter()
+            .find(|(_, css_id, ..)| css_id.is_some_and(|css_id| css_id.0 == id))
+            .map(|(entity, ..)| entity)
+    }
+
+    /// Returns `true` when `entity` matches `selector`.
+    pub fn matches(&self, entity: Entity, selector: &str) -> bool {
+        self.nodes.contains(entity) && matches_css_selector(selector, entity, &self.parents)
+    }
+
+    /// Adds `class` to the widget's `CssClass`.
+    pub fn add_class(&mut self, entity: Entity, class: &str) {
+        let class = class.to_string();
+        self.edit_classes(entity, move |classes| {
+            if !classes.contains(&class) {
+                classes.push(class);
+            }
+        });
+    }
+
+    /// Removes `class` from the widget's `CssClass`.
+    pub fn remove_class(&mut self, entity: Entity, class: &str) {
+        let class = class.to_string();
+        self.edit_classes(entity, move |classes| {
+            classes.retain(|existing| *existing != class)
+        });
+    }
+
+    /// Adds `class` when missing, removes it otherwise.
+    pub fn toggle_class(&mut self, entity: Entity, class: &str) {
+        let class = class.to_string();
+        self.edit_classes(entity, move |classes| {
+            if let Some(index) = classes.iter().position(|existing| *existing == class) {
+                classes.remove(index);
+            } else {
+                classes.push(class);
+            }
+        });
+    }
+
+    /// Sets an HTML attribute on a spawned widget.
+    ///
+    /// The attribute is stored verbatim in `HtmlAttributes` for CSS attribute
+    /// selectors. `id`, `class`, `style` and the boolean attributes `hidden`,
+    /// `disabled`, `readonly`, `checked`, `open` and `required` are applied to
+    /// the widget as well; `"false"` removes a boolean attribute.
+    pub fn set_attr(&mut self, entity: Entity, name: &str, value: &str) {
+        let name = name.to_ascii_lowercase();
+        if BOOLEAN_ATTRIBUTES.contains(&name.as_str()) && value.eq_ignore_ascii_case("false") {
+            self.remove_attr(entity, &name);
+            return;
+        }
+
+        let (key, stored) = (name.clone(), value.to_string());
+        self.edit_attributes(entity, move |attributes| attributes.set(&key, stored));
+
+        match name.as_str() {
+            "id" => self.sync(entity, CssID(value.to_string())),
+            "class" => self.sync(
+                entity,
+                CssClass(value.split_whitespace().map(str::to_string).collect()),
+            ),
+            "style" => self.sync(entity, HtmlStyle::from_str(value)),
+            _ => self.set_boolean_attr(entity, &name, true),
+        }
+    }
+
+    /// Removes an HTML attribute from a spawned widget; boolean attributes
+    /// switch their state off again.
+    pub fn remove_attr(&mut self, entity: Entity, name: &str) {
+        let name = name.to_ascii_lowercase();
+        let key = name.clone();
+        self.edit_attributes(entity, move |attributes| {
+            attributes.remove(&key);
+        });
+        self.set_boolean_attr(entity, &name, false);
+    }
+
+    /// Applies a boolean attribute to the widget's visibility or state.
+    fn set_boolean_attr(&mut self, entity: Entity, name: &str, enabled: bool) {
+        match name {
+            "hidden" => self.sync(
+                entity,
+                if enabled {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                },
+            ),
+            "disabled" => self.edit_state(entity, move |state| state.disabled = enabled),
+            "readonly" => self.edit_state(entity, move |state| state.readonly = enabled),
+            "checked" => self.edit_state(entity, move |state| state.checked = enabled),
+            "open" => self.edit_state(entity, move |state| state.open = enabled),
+            "required" => self.edit_state(entity, move |state| state.required = enabled),
+            _ => {}
+        }
+    }
+
+    /// Replaces the text of a widget, updating its `HtmlInnerContent`.
+    ///
+    /// Drops the `{{ ... }}` text binding of the widget, if any.
+    pub fn set_text(&mut self, entity: Entity, text: impl Into<String>) {
+        let text = text.into();
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                entity.remove::<HtmlTextBinding>();
+                if let Some(mut content) = entity.get_mut::<HtmlInnerContent>() {
+                    content.set_inner_text(text.clone());
+                    content.set_inner_html(text.clone());
+                    content.set_inner_bindings(Vec::new());
+                }
+
+                macro_rules! set_widget_text {
+                    ($($widget:ty),*) => {$(
+                        if let Some(mut widget) = entity.get_mut::<$widget>()
+                            && widget.text != text
+                        {
+                            widget.text = text.clone();
+                        }
+                    )*};
+                }
+                set_widget_text!(Paragraph, Headline, Button, ToolTip);
+            });
+    }
+
+    /// Enables or disables a widget through its `UIWidgetState`.
+    pub fn set_disabled(&mut self, entity: Entity, disabled: bool) {
+        self.edit_state(entity, move |state| state.disabled = disabled);
+    }
+
+    /// Sets one inline style property, like `style="..."` would.
+    pub fn set_style(&mut self, entity: Entity, property: &str, value: &str) {
+        let (property, value) = (property.to_string(), value.to_string());
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                let mut style = entity
+                    .get::<HtmlStyle>()
+                    .cloned()
+                    .unwrap_or_else(|| HtmlStyle(Style::default()));
+                apply_declaration_to_style(&mut style.0, &property, &value, &HashMap::new());
+                sync_entity_component(&mut entity, style);
+            });
+    }
+
+    /// Sets a CSS custom property on a spawned widget, e.g. `set_var(hp_bar, "--hp", "40%")`.
+    ///
+    /// The value overrides custom properties declared by CSS and is inherited by
+    /// descendants; `var(--hp)` is resolved when their styles are applied.
+    pub fn set_var(&mut self, entity: Entity, name: &str, value: &str) {
+        let (name, value) = (name.to_string(), value.to_string());
+        self.edit_vars(entity, move |vars| {
+            vars.insert(name, value);
+        });
+    }
+
+    /// Removes a custom property set with [`UiDom::set_var`].
+    pub fn remove_var(&mut self, entity: Entity, name: &str) {
+        let name = name.to_string();
+        self.edit_vars(entity, move |vars| {
+            vars.remove(&name);
+        });
+    }
+
+    /// Parses `html` and spawns its widgets at `position` relative to `target`.
+    ///
+    /// The fragment uses the stylesheets and controller of `target`;
+    /// `{{ ... }}` placeholders and event attributes work like in the page.
+    /// Fragments are not part of the template, so page rebuilds keep but
+    /// never update them.
+    pub fn insert_html(&mut self, target: Entity, position: HtmlInsertPosition, html: &str) {
+        let html = html.to_string();
+        self.commands.queue(move |world: &mut World| {
+            insert_html_fragment(world, target, position, &html);
+        });
+    }
+
+    /// Despawns every widget matching `selector` and returns how many matched.
+    pub fn remove(&mut self, selector: &str) -> usize {
+        let matched = self.query(selector);
+        for entity in &matched {
+            self.commands.entity(*entity).try_despawn();
+        }
+        matched.len()
+    }
+
+    /// Replaces every widget matching `selector` with the `html` fragment and
+    /// returns how many matched.
+    pub fn replace(&mut self, selector: &str, html: &str) -> usize {
+        let matched = self.query(selector);
+        for entity in &matched {
+            self.insert_html(*entity, HtmlInsertPosition::BeforeBegin, html);
+            self.commands.entity(*entity).try_despawn();
+        }
+        matched.len()
+    }
+
+    /// Spawns a [`UiNode`] tree as a new root using the default stylesheet.
+    ///
+    /// Returns `None` without an `AssetServer`.
+    pub fn spawn(&mut self, node: UiNode) -> Option<Entity> {
+        let Some(asset_server) = self.asset_server.as_deref() else {
+            warn!("UiDom::spawn needs the AssetServer");
+            return None;
+        };
+        let css = self
+            .default_css
+            .as_ref()
+            .map(|css| vec![css.0.clone()])
+            .unwrap_or_default();
+        let node = node.build(&mut self.commands, &css, &Widget::default());
+        Some(spawn_widget_node(
+            &mut self.commands,
+            &node,
+            asset_server,
+            None,
+            false,
+        ))
+    }
+
+    /// Spawns a [`UiNode`] tree as the last child of `parent`.
+    ///
+    /// The tree uses the stylesheets and controller of `parent`; like
+    /// [`UiDom::insert_html`] fragments, page rebuilds keep it. Returns `None`
+    /// without an `AssetServer`.
+    pub fn append(&mut self, parent: Entity, node: UiNode) -> Option<Entity> {
+        let Some(asset_server) = self.asset_server.as_deref() else {
+            warn!("UiDom::append needs the AssetServer");
+            return None;
+        };
+        let (css, widget, body_root, div_root) = self.hosts.get(parent).unwrap_or_default();
+        let css = css.map(|css| css.0.clone()).unwrap_or_else(|| {
+            self.default_css
+                .as_ref()
+                .map(|css| vec![css.0.clone()])
+                .unwrap_or_default()
+        });
+        let widget = widget.cloned().unwrap_or_default();
+        let container = body_root
+            .map(|root| root.0)
+            .or(div_root.map(|root| root.0))
+            .unwrap_or(parent);
+
+        let node = node.build(&mut self.commands, &css, &widget);
+        let entity = spawn_widget_node(&mut self.commands, &node, asset_server, None, false);
+        self.commands.entity(entity).remove::<HtmlID>();
+        self.commands.entity(container).add_child(entity);
+        Some(entity)
+    }
+
+    /// Returns `root` and its widgets as normalized HTML with their current
+    /// values and states.
+    pub fn serialize(&self, root: Entity) -> String {
+        let mut html = String::new();
+        write_html(&mut html, root, 0, &self.snapshot, &self.children);
+        html
+    }
+
+    /// Returns a text snapshot of `root` listing each widget with its state
+    /// and computed style, one property per line.
+    pub fn snapshot(&self, root: Entity) -> String {
+        let mut text = String::new();
+        write_snapshot(&mut text, root, 0, &self.snapshot, &self.children);
+        text
+    }
+
+    fn is_root(&self, entity: Entity) -> bool {
+        std::iter::successors(self.parents.get(entity).ok(), |(.., parent)| {
+            parent.and_then(|parent| self.parents.get(parent.parent()).ok())
+        })
+        .skip(1)
+        .all(|(_, _, tag, ..)| tag.is_none())
+    }
+
+    fn collect(&self, entity: Entity, selector: &str, found: &mut Vec<Entity>) {
+        if self.matches(entity, selector) {
+            found.push(entity);
+        }
+        if let Ok(children) = self.children.get(entity) {
+            for child in children.iter() {
+                self.collect(child, selector, found);
+            }
+        }
+    }
+
+    fn sync<T: Component<Mutability = Mutable> + PartialEq>(&mut self, entity: Entity, value: T) {
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                sync_entity_component(&mut entity, value);
+            });
+    }
+
+    fn edit_classes(
+        &mut self,
+        entity: Entity,
+        edit: impl FnOnce(&mut Vec<String>) + Send + 'static,
+    ) {
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                let mut classes = entity
+                    .get::<CssClass>()
+                    .map(|classes| classes.0.clone())
+                    .unwrap_or_default();
+                edit(&mut classes);
+                sync_entity_component(&mut entity, CssClass(classes));
+            });
+    }
+
+    fn edit_attributes(
+        &mut self,
+        entity: Entity,
+        edit: impl FnOnce(&mut HtmlAttributes) + Send + 'static,
+    ) {
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                let mut attributes = entity.get::<HtmlAttributes>().cloned().unwrap_or_default();
+                edit(&mut attributes);
+                sync_entity_component(&mut entity, attributes);
+            });
+    }
+
+    fn edit_vars(
+        &mut self,
+        entity: Entity,
+        edit: impl FnOnce(&mut HashMap<String, String>) + Send + 'static,
+    ) {
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                let mut vars = entity
+                    .get::<CssVariables>()
+                    .map(|vars| vars.0.clone())
+                    .unwrap_or_default();
+                edit(&mut vars);
+                sync_entity_component(&mut entity, CssVariables(vars));
+            });
+    }
+
+    fn edit_state(
+        &mut self,
+        entity: Entity,
+        edit: impl FnOnce(&mut UIWidgetState) + Send + 'static,
+    ) {
+        self.commands
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                let mut state = entity.get::<UIWidgetState>().cloned().unwrap_or_default();
+                edit(&mut state);
+                sync_entity_component(&mut entity, state);
+            });
+    }
+}
+
+/// Spawns the widgets of `html` next to or inside `target`.
+fn insert_html_fragment(
+    world: &mut World,
+    target: Entity,
+    position: HtmlInsertPosition,
+    html: &str,
+) {
+    if world.get_entity(target).is_err() {
+        return;
+    }
+    let Some(asset_server) = world.get_resource::<AssetServer>().cloned() else {
+        warn!("UiDom::insert_html needs the AssetServer");
+        return;
+    };
+
+    let (container, index) = match position {
+        HtmlInsertPosition::AfterBegin | HtmlInsertPosition::BeforeEnd => {
+            let container = world
+                .get::<BodyContentRoot>(target)
+                .map(|root| root.0)
+                .or_else(|| world.get::<DivContentRoot>(target).map(|root| root.0))
+                .unwrap_or(target);
+            let index = match position {
+                HtmlInsertPosition::AfterBegin => 0,
+                _ => world
+                    .get::<Children>(container)
+                    .map_or(0, |children| children.len()),
+            };
+            (container, index)
+        }
+        HtmlInsertPosition::BeforeBegin | HtmlInsertPosition::AfterEnd => {
+            let Some(parent) = world.get::<ChildOf>(target).map(ChildOf::parent) else {
+                warn!("UiDom::insert_html: {target} has no parent to insert next to");
+                return;
+            };
+            let index = world
+                .get::<Children>(parent)
+                .and_then(|children| children.iter().position(|child| child == target))
+                .unwrap_or(0);
+            let offset = usize::from(position == HtmlInsertPosition::AfterEnd);
+            (parent, index + offset)
+        }
+    };
+
+    let css = world
+        .get::<CssSource>(target)
+        .map(|source| source.0.clone())
+        .unwrap_or_default();
+    let key = std::iter::successors(Some(target), |entity| {
+        world.get::<ChildOf>(*entity).map(ChildOf::parent)
+    })
+    .find_map(|entity| world.get::<Body>(entity)?.html_key.clone())
+    .unwrap_or_default();
+    let mut sources = world.query::<&HtmlSource>();
+    let mut source = sources
+        .iter(world)
+        .find(|source| source.source_id == key)
+        .cloned()
+        .unwrap_or_else(|| HtmlSource::from_handle(Handle::default()));
+    source.controller = world
+        .get::<Widget>(target)
+        .and_then(|widget| widget.0.clone());
+
+    let nodes = parse_html_fragment(world, html, &css, &key, &source);
+    if nodes.is_empty() {
+        return;
+    }
+
+    let mut queue = CommandQueue::default();
+    {
+        let mut commands = Commands::new(&mut queue, world);
+        let roots = nodes
+            .iter()
+            .map(|node| {
+                let entity = spawn_widget_node(&mut commands, node, &asset_server, None, false);
+                // Without an `HtmlID` the rebuild of the page keeps the fragment.
+                commands.entity(entity).remove::<HtmlID>();
+                entity
+            })
+            .collect::<Vec<_>>();
+        commands.entity(container).insert_children(index, &roots);
+    }
+    queue.apply(world);
+}
+
+/// Inserts `value`, touching the existing component only when it differs.
+fn sync_entity_component<T: Component<Mutability = Mutable> + PartialEq>(
+    entity: &mut EntityWorldMut,
+    value: T,
+) {
+    if let Some(mut existing) = entity.get_mut::<T>() {
+        if *existing != value {
+            *existing = value;
+        }
+    } else {
+        entity.insert(value);
+    }
+}
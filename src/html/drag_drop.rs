@@ -0,0 +1,289 @@
+use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+use crate::html::inline_functions::resolve_html_handler_args;
+use crate::html::propagation::HtmlEventPath;
+use crate::html::{HtmlDragEnter, HtmlDragLeave, HtmlDrop, HtmlInlineExpr, HtmlSystemSet};
+use crate::widgets::UIWidgetState;
+
+/// Component for widgets declared with `draggable="true"`.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct HtmlDraggable {
+    /// `data-drag` expression, evaluated when the drag starts.
+    pub payload: Option<HtmlInlineExpr>,
+}
+
+/// Marker for the preview node that follows the pointer while dragging.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HtmlDragGhost;
+
+/// Resource holding the running drag of every pointer.
+#[derive(Resource, Default)]
+pub(crate) struct HtmlDragSessions(HashMap<PointerId, HtmlDragSession>);
+
+struct HtmlDragSession {
+    source: Entity,
+    payload: JsonValue,
+    position: Vec2,
+    size: Vec2,
+    ghost: Entity,
+    over: Option<Entity>,
+}
+
+/// Opacity of the drag preview.
+const DRAG_GHOST_ALPHA: f32 = 0.6;
+
+/// Event attributes that turn a widget into a drop target.
+const DROP_TARGET_ATTRIBUTES: [&str; 3] = ["ondrop", "ondragenter", "ondragleave"];
+
+/// Plugin wiring `draggable` widgets to drop targets.
+pub struct HtmlDragDropPlugin;
+
+impl Plugin for HtmlDragDropPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HtmlDragSessions>();
+        app.add_observer(start_html_drag);
+        app.add_observer(track_html_drag);
+        app.add_observer(finish_html_drag);
+        app.add_systems(
+            Update,
+            update_html_drag_targets.in_set(HtmlSystemSet::Bindings),
+        );
+    }
+}
+
+macro_rules! html_drag_payload {
+    ($($event:ty),*) => {$(
+        impl $event {
+            /// Deserializes the `data-drag` payload, e.g. into a slot index.
+            pub fn payload_as<T: DeserializeOwned>(&self) -> Option<T> {
+                serde_json::from_value(self.payload.clone()).ok()
+            }
+        }
+    )*};
+}
+
+html_drag_payload!(HtmlDragEnter, HtmlDragLeave, HtmlDrop);
+
+/// Starts a drag session for the closest `draggable` widget and spawns its
+/// ghost preview.
+fn start_html_drag(
+    ev: On<Pointer<DragStart>>,
+    mut commands: Commands,
+    sessions: Res<HtmlDragSessions>,
+    q_draggable: Query<(&HtmlDraggable, Option<&UIWidgetState>)>,
+) {
+    if ev.button != PointerButton::Primary || sessions.0.contains_key(&ev.pointer_id) {
+        return;
+    }
+
+    let source = ev.event().entity;
+    let Ok((draggable, state)) = q_draggable.get(source) else {
+        return;
+    };
+    if state.is_some_and(|state| state.disabled) {
+        return;
+    }
+
+    let pointer = ev.pointer_id;
+    let position = ev.pointer_location.position;
+    let payload = draggable.payload.clone();
+    commands.queue(move |world: &mut World| {
+        // Nested draggables see the same pointer event; the innermost wins.
+        if world
+            .resource::<HtmlDragSessions>()
+            .0
+            .contains_key(&pointer)
+        {
+            return;
+        }
+
+        let payload = payload
+            .map(|expr| resolve_html_handler_args(world, source, &[expr]))
+            .and_then(|mut values| values.pop())
+            .unwrap_or(JsonValue::Null);
+        let size = world
+            .get::<ComputedNode>(source)
+            .map(|node| node.size() * node.inverse_scale_factor())
+            .unwrap_or(Vec2::ZERO);
+        let ghost = spawn_html_drag_ghost(world, source, position, size);
+
+        world.resource_mut::<HtmlDragSessions>().0.insert(
+            pointer,
+            HtmlDragSession {
+                source,
+                payload,
+                position,
+                size,
+                ghost,
+                over: None,
+            },
+        );
+    });
+}
+
+/// Spawns a translucent copy of the source's background and image.
+fn spawn_html_drag_ghost(world: &mut World, source: Entity, position: Vec2, size: Vec2) -> Entity {
+    let background = world.get::<BackgroundColor>(source).map(|bg| bg.0);
+    let image = world.get::<ImageNode>(source).cloned();
+
+    let mut ghost = world.spawn((
+        Name::new("Html-Drag-Ghost"),
+        HtmlDragGhost,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            left: Val::Px(position.x - size.x / 2.0),
+            top: Val::Px(position.y - size.y / 2.0),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX),
+        Pickable::IGNORE,
+    ));
+    if let Some(color) = background {
+        ghost.insert(BackgroundColor(
+            color.with_alpha(color.alpha() * DRAG_GHOST_ALPHA),
+        ));
+    }
+    if let Some(mut image) = image {
+        image.color = image
+            .color
+            .with_alpha(image.color.alpha() * DRAG_GHOST_ALPHA);
+        ghost.insert(image);
+    }
+    ghost.id()
+}
+
+/// Records the pointer position of a running drag.
+fn track_html_drag(ev: On<Pointer<Drag>>, mut sessions: ResMut<HtmlDragSessions>) {
+    if let Some(session) = sessions.0.get_mut(&ev.pointer_id) {
+        session.position = ev.pointer_location.position;
+    }
+}
+
+/// Moves the ghost and emits enter/leave events when the hovered drop
+/// target changes.
+///
+/// A drag whose source was despawned ends without a drop; a despawned drop
+/// target is forgotten without `ondragleave`.
+fn update_html_drag_targets(
+    mut commands: Commands,
+    hover_map: Option<Res<HoverMap>>,
+    path: HtmlEventPath,
+    mut sessions: ResMut<HtmlDragSessions>,
+    q_alive: Query<Entity>,
+    mut q_ghost: Query<&mut Node, With<HtmlDragGhost>>,
+    mut q_state: Query<&mut UIWidgetState>,
+) {
+    sessions.0.retain(|_, session| {
+        if q_alive.contains(session.source) {
+            return true;
+        }
+        commands.entity(session.ghost).try_despawn();
+        if let Some(target) = session.over {
+            set_drag_over(&mut q_state, target, false);
+        }
+        false
+    });
+
+    for (pointer, session) in sessions.0.iter_mut() {
+        if session.over.is_some_and(|over| !q_alive.contains(over)) {
+            session.over = None;
+        }
+        if let Ok(mut node) = q_ghost.get_mut(session.ghost) {
+            node.left = Val::Px(session.position.x - session.size.x / 2.0);
+            node.top = Val::Px(session.position.y - session.size.y / 2.0);
+        }
+
+        let over = hover_map
+            .as_ref()
+            .and_then(|map| map.get(pointer))
+            .and_then(|hits| {
+                let mut hits = hits.iter().collect::<Vec<_>>();
+                hits.sort_by(|(_, a), (_, b)| a.depth.total_cmp(&b.depth));
+                hits.into_iter()
+                    .find_map(|(hovered, _)| drop_target(&path, *hovered, session.source))
+            });
+        if over == session.over {
+            continue;
+        }
+
+        if let Some(previous) = session.over.take() {
+            set_drag_over(&mut q_state, previous, false);
+            commands.trigger(HtmlDragLeave {
+                entity: previous,
+                source: session.source,
+                payload: session.payload.clone(),
+                position: session.position,
+                propagation: default(),
+            });
+        }
+        if let Some(target) = over {
+            set_drag_over(&mut q_state, target, true);
+            commands.trigger(HtmlDragEnter {
+                entity: target,
+                source: session.source,
+                payload: session.payload.clone(),
+                position: session.position,
+                propagation: default(),
+            });
+        }
+        session.over = over;
+    }
+}
+
+/// Ends the drag of the released pointer, emitting `ondrop` on the target
+/// under it.
+fn finish_html_drag(
+    ev: On<Pointer<DragEnd>>,
+    mut commands: Commands,
+    mut sessions: ResMut<HtmlDragSessions>,
+    q_alive: Query<Entity>,
+    mut q_state: Query<&mut UIWidgetState>,
+) {
+    let Some(session) = sessions.0.remove(&ev.pointer_id) else {
+        return;
+    };
+    commands.entity(session.ghost).try_despawn();
+
+    let Some(target) = session.over.filter(|target| q_alive.contains(*target)) else {
+        return;
+    };
+    set_drag_over(&mut q_state, target, false);
+    if !q_alive.contains(session.source) {
+        return;
+    }
+    commands.trigger(HtmlDrop {
+        entity: target,
+        source: session.source,
+        payload: session.payload,
+        position: ev.pointer_location.position,
+        propagation: default(),
+    });
+}
+
+/// Returns the closest drop target above `hovered`, skipping the dragged
+/// widget and everything inside it.
+fn drop_target(path: &HtmlEventPath, hovered: Entity, source: Entity) -> Option<Entity> {
+    if path.ancestors(hovered).any(|entity| entity == source) {
+        return None;
+    }
+    path.ancestors(hovered).find(|entity| {
+        DROP_TARGET_ATTRIBUTES
+            .iter()
+            .any(|attribute| path.handles(*entity, attribute))
+    })
+}
+
+fn set_drag_over(q_state: &mut Query<&mut UIWidgetState>, entity: Entity, drag_over: bool) {
+    if let Ok(mut state) = q_state.get_mut(entity)
+        && state.drag_over != drag_over
+    {
+        state.drag_over = drag_over;
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::html::HtmlEventBindings;
+
+/// Delay used by `.debounce` without an explicit duration.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Interval used by `.throttle` without an explicit duration.
+const DEFAULT_THROTTLE: Duration = Duration::from_millis(100);
+
+/// Rate limits parsed from attributes like `onchange.debounce.300ms`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HtmlEventModifiers {
+    /// Runs the handler once the event stopped firing for this long.
+    pub debounce: Option<Duration>,
+    /// Runs the handler at most once per interval.
+    pub throttle: Option<Duration>,
+    /// Runs the handler only for the first event.
+    pub once: bool,
+}
+
+impl HtmlEventModifiers {
+    /// Parses the modifiers after the event name, e.g. `["debounce", "300ms"]`.
+    ///
+    /// `capture` is accepted and ignored here; it selects the handler phase.
+    pub fn parse<'a>(modifiers: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut modifiers = modifiers.into_iter().peekable();
+        while let Some(modifier) = modifiers.next() {
+            match modifier {
+                "capture" => {}
+                "once" => parsed.once = true,
+                "debounce" | "throttle" => {
+                    let duration = match modifiers.peek().and_then(|raw| parse_duration(raw)) {
+                        Some(duration) => {
+                            modifiers.next();
+                            duration
+                        }
+                        None if modifier == "debounce" => DEFAULT_DEBOUNCE,
+                        None => DEFAULT_THROTTLE,
+                    };
+                    if modifier == "debounce" {
+                        parsed.debounce = Some(duration);
+                    } else {
+                        parsed.throttle = Some(duration);
+                    }
+                }
+                other => return Err(format!("unknown event modifier '.{other}'")),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Returns `true` when no modifier is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Parses `300ms`, `1.5s` or a plain millisecond count.
+fn parse_duration(raw: &str) -> Option<Duration> {
+    let (value, scale) = if let Some(ms) = raw.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(secs) = raw.strip_suffix('s') {
+        (secs, 1.0)
+    } else {
+        (raw, 0.001)
+    };
+    let value = value.parse::<f64>().ok()?;
+    (value.is_finite() && value >= 0.0).then(|| Duration::from_secs_f64(value * scale))
+}
+
+type HtmlDeferredHandler = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+struct HtmlPendingDebounce {
+    due: f32,
+    run: HtmlDeferredHandler,
+}
+
+/// Runtime state of `.once`, `.throttle` and `.debounce` handlers, keyed by
+/// widget and modified attribute (`"onclick"`, `"onclick.capture"`).
+#[derive(Resource, Default)]
+pub(crate) struct HtmlEventModifierState {
+    fired: HashSet<(Entity, String)>,
+    last_run: HashMap<(Entity, String), f32>,
+    debounced: HashMap<(Entity, String), HtmlPendingDebounce>,
+}
+
+/// Runs `handler` for the `key` binding of `entity`, honoring its modifiers.
+///
+/// Debounced handlers are kept and run later with the latest event.
+pub(crate) fn run_html_handler_modified<F>(world: &mut World, entity: Entity, key: &str, handler: F)
+where
+    F: FnOnce(&mut World) + Send + Sync + 'static,
+{
+    let modifiers = world
+        .get::<HtmlEventBindings>(entity)
+        .and_then(|bindings| bindings.modifiers.get(key).copied())
+        .unwrap_or_default();
+    if modifiers.is_empty() {
+        handler(world);
+        return;
+    }
+
+    let now = world
+        .get_resource::<Time>()
+        .map_or(0.0, |time| time.elapsed_secs());
+    let id = (entity, key.to_string());
+    let mut state = world.get_resource_or_init::<HtmlEventModifierState>();
+
+    if modifiers.once && !state.fired.insert(id.clone()) {
+        return;
+    }
+    if let Some(interval) = modifiers.throttle {
+        if state
+            .last_run
+            .get(&id)
+            .is_some_and(|last| now - last < interval.as_secs_f32())
+        {
+            return;
+        }
+        state.last_run.insert(id.clone(), now);
+    }
+    if let Some(delay) = modifiers.debounce {
+        state.debounced.insert(
+            id,
+            HtmlPendingDebounce {
+                due: now + delay.as_secs_f32(),
+                run: Box::new(handler),
+            },
+        );
+        return;
+    }
+
+    handler(world);
+}
+
+/// Forgets the modifier state of widgets whose bindings are removed or despawned.
+pub(crate) fn clear_html_event_modifier_state(
+    ev: On<Remove, HtmlEventBindings>,
+    mut state: ResMut<HtmlEventModifierState>,
+) {
+    let entity = ev.entity;
+    state.fired.retain(|(id, _)| *id != entity);
+    state.last_run.retain(|(id, _), _| *id != entity);
+    state.debounced.retain(|(id, _), _| *id != entity);
+}
+
+/// Runs debounced handlers whose event stopped firing long enough.
+pub(crate) fn run_due_html_debounces(world: &mut World) {
+    let now = world.resource::<Time>().elapsed_secs();
+    let mut state = world.resource_mut::<HtmlEventModifierState>();
+    if state.debounced.is_empty() {
+        return;
+    }
+
+    let due = state
+        .debounced
+        .iter()
+        .filter(|(_, pending)| pending.due <= now)
+        .map(|(id, _)| id.clone())
+        .collect::<Vec<_>>();
+    let handlers = due
+        .into_iter()
+        .filter_map(|id| {
+            state
+                .debounced
+                .remove(&id)
+                .map(|pending| (id.0, pending.run))
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, run) in handlers {
+        if world.get_entity(entity).is_ok() {
+            run(world);
+        }
+    }
+}
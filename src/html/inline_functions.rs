@@ -3,7 +3,13 @@ use serde_json::{Number as JsonNumber, Value as JsonValue};
 
 #[cfg(feature = "extended-framework")]
 use crate::framework::UiBindingStore;
+use crate::html::propagation::HtmlEventPropagation;
+use crate::html::reflect_binding::{
+    REFLECT_RESOURCE_PREFIX, read_reflect_path, write_reflect_path,
+};
+use crate::html::{HtmlEmit, HtmlEvent, HtmlFunctionRegistry, run_html_handler_with_args};
 use crate::lang::UiSharedValues;
+use crate::routing::Router;
 use crate::widgets::{
     CheckBox, ChoiceBox, ChoiceOption, ColorPicker, DatePicker, FieldSelectionMulti,
     FieldSelectionSingle, InputValue, ListBox, ProgressBar, RadioButton, Slider, SwitchButton,
@@ -31,6 +37,45 @@ pub struct HtmlInlineEventBindings {
     pub ontouchstart: Option<HtmlInlineAction>,
     pub ontouchmove: Option<HtmlInlineAction>,
     pub ontouchend: Option<HtmlInlineAction>,
+    pub ondblclick: Option<HtmlInlineAction>,
+    pub oncontextmenu: Option<HtmlInlineAction>,
+    pub onlongpress: Option<HtmlInlineAction>,
+    pub ondragenter: Option<HtmlInlineAction>,
+    pub ondragleave: Option<HtmlInlineAction>,
+    pub ondrop: Option<HtmlInlineAction>,
+}
+
+impl HtmlInlineEventBindings {
+    /// Returns the inline action bound to `attribute`, e.g. `"onclick"`.
+    pub fn get(&self, attribute: &str) -> Option<&HtmlInlineAction> {
+        match attribute {
+            "onclick" => self.onclick.as_ref(),
+            "onmousedown" => self.onmousedown.as_ref(),
+            "onmouseup" => self.onmouseup.as_ref(),
+            "onmouseover" => self.onmouseover.as_ref(),
+            "onmouseout" => self.onmouseout.as_ref(),
+            "onchange" => self.onchange.as_ref(),
+            "oninit" => self.oninit.as_ref(),
+            "onfoucs" => self.onfoucs.as_ref(),
+            "onscroll" => self.onscroll.as_ref(),
+            "onwheel" => self.onwheel.as_ref(),
+            "onkeydown" => self.onkeydown.as_ref(),
+            "onkeyup" => self.onkeyup.as_ref(),
+            "ondragstart" => self.ondragstart.as_ref(),
+            "ondrag" => self.ondrag.as_ref(),
+            "ondragstop" => self.ondragstop.as_ref(),
+            "ontouchstart" => self.ontouchstart.as_ref(),
+            "ontouchmove" => self.ontouchmove.as_ref(),
+            "ontouchend" => self.ontouchend.as_ref(),
+            "ondblclick" => self.ondblclick.as_ref(),
+            "oncontextmenu" => self.oncontextmenu.as_ref(),
+            "onlongpress" => self.onlongpress.as_ref(),
+            "ondragenter" => self.ondragenter.as_ref(),
+            "ondragleave" => self.ondragleave.as_ref(),
+            "ondrop" => self.ondrop.as_ref(),
+            _ => None,
+        }
+    }
 }
 
 /// A semicolon-separated inline action list.
@@ -51,17 +96,86 @@ pub enum HtmlInlineFunction {
     Set,
     Add,
     Min,
+    Toggle,
+    Push,
+    Remove,
+    Clamp,
+    Undo,
+    Redo,
+    Navigate,
+    Emit,
+    OpenDialog,
+    Call,
+}
+
+impl HtmlInlineFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "set" => Some(Self::Set),
+            "add" => Some(Self::Add),
+            "min" => Some(Self::Min),
+            "toggle" => Some(Self::Toggle),
+            "push" => Some(Self::Push),
+            "remove" => Some(Self::Remove),
+            "clamp" => Some(Self::Clamp),
+            "undo" => Some(Self::Undo),
+            "redo" => Some(Self::Redo),
+            "navigate" => Some(Self::Navigate),
+            "emit" => Some(Self::Emit),
+            "open_dialog" => Some(Self::OpenDialog),
+            "call" => Some(Self::Call),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` when the first argument is a store path that is written.
+    pub fn writes_target(self) -> bool {
+        matches!(
+            self,
+            Self::Set
+                | Self::Add
+                | Self::Min
+                | Self::Toggle
+                | Self::Push
+                | Self::Remove
+                | Self::Clamp
+        )
+    }
+
+    /// Returns `true` when the first argument is a bare name, e.g. a handler.
+    fn names_first_arg(self) -> bool {
+        matches!(self, Self::Emit | Self::Call)
+    }
+
+    /// Returns the minimum and maximum argument count.
+    fn arity(self) -> (usize, usize) {
+        match self {
+            Self::Set | Self::Add | Self::Min | Self::Push | Self::Remove => (2, 2),
+            Self::Clamp => (3, 3),
+            Self::Toggle | Self::Navigate | Self::OpenDialog => (1, 1),
+            Self::Emit => (1, 2),
+            Self::Call => (1, usize::MAX),
+            Self::Undo | Self::Redo => (0, 0),
+        }
+    }
 }
 
 /// One parsed inline function call.
+///
+/// `target` and `value` are the written store path and the first value
+/// argument of `$set`, `$add`, `$toggle` and the other store writers.
+/// Functions without a store target leave `target` empty and `value` as
+/// `null`; `args` always holds every argument in order.
 #[derive(Clone, Debug, PartialEq)]
 pub struct HtmlInlineCall {
     pub function: HtmlInlineFunction,
     pub target: HtmlInlinePath,
     pub value: HtmlInlineExpr,
+    pub args: Vec<HtmlInlineExpr>,
 }
 
-/// Dot-separated target or source path, e.g. `info.value`.
+/// Dot-separated target or source path, e.g. `info.value` or
+/// `res:AudioSettings.master_volume`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct HtmlInlinePath {
     segments: Vec<String>,
@@ -76,12 +190,24 @@ impl HtmlInlinePath {
         }
     }
 
+    /// Placeholder target of calls that do not write a store path.
+    fn empty() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Returns `true` for the placeholder target of calls without a store path.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
     pub fn root(&self) -> &str {
-        &self.segments[0]
+        self.segments.first().map_or("", String::as_str)
     }
 
     pub fn tail(&self) -> &[String] {
-        &self.segments[1..]
+        self.segments.get(1..).unwrap_or_default()
     }
 
     pub fn as_dotted(&self) -> String {
@@ -120,62 +246,281 @@ pub fn parse_html_inline_action(raw: &str) -> Result<HtmlInlineAction, String> {
     Ok(HtmlInlineAction { calls })
 }
 
-/// Queues an inline action for execution with direct `World` access.
-pub(crate) fn queue_html_inline_action(
-    commands: &mut Commands,
+/// Parses a named handler attribute such as `select_slot(item.id, 'primary')`.
+///
+/// Returns the handler name and its argument expressions. A bare name such
+/// as `select_slot` has no arguments.
+pub fn parse_html_handler_call(raw: &str) -> Result<(String, Vec<HtmlInlineExpr>), String> {
+    let raw = raw.trim();
+    let Some(open) = raw.find('(') else {
+        return Ok((raw.to_string(), Vec::new()));
+    };
+    if !raw.ends_with(')') {
+        return Err(format!("handler call '{raw}' is missing ')'"));
+    }
+
+    let name = raw[..open].trim();
+    if !is_identifier(name) {
+        return Err(format!("invalid handler name '{name}'"));
+    }
+
+    let raw_args = &raw[open + 1..raw.len() - 1];
+    let mut args = Vec::new();
+    if !raw_args.trim().is_empty() {
+        for raw_arg in split_top_level(raw_args, ',') {
+            args.push(parse_expr(&raw_arg)?);
+        }
+    }
+
+    Ok((name.to_string(), args))
+}
+
+/// Parses one inline expression such as `item.id`, `'sword'` or `{"slot": 2}`.
+pub fn parse_html_inline_expr(raw: &str) -> Result<HtmlInlineExpr, String> {
+    parse_expr(raw)
+}
+
+/// Resolves handler arguments against the store and the event widget.
+///
+/// Arguments that cannot be resolved are passed as `null`.
+pub(crate) fn resolve_html_handler_args(
+    world: &World,
     entity: Entity,
-    action: HtmlInlineAction,
-) {
-    commands.queue(move |world: &mut World| {
-        execute_html_inline_action(world, entity, &action);
-    });
+    args: &[HtmlInlineExpr],
+) -> Vec<JsonValue> {
+    args.iter()
+        .map(|arg| resolve_expr(world, entity, arg).unwrap_or(JsonValue::Null))
+        .collect()
 }
 
-fn execute_html_inline_action(world: &mut World, entity: Entity, action: &HtmlInlineAction) {
+/// Executes an inline action with direct `World` access.
+pub(crate) fn execute_html_inline_action(
+    world: &mut World,
+    entity: Entity,
+    action: &HtmlInlineAction,
+) {
+    // All writes of one action form a single undo step.
+    let grouped = action
+        .calls()
+        .iter()
+        .all(|call| call.function.writes_target());
+    #[cfg(feature = "extended-framework")]
+    if grouped && let Some(mut store) = world.get_resource_mut::<UiBindingStore>() {
+        store.begin_group();
+    }
+
     for call in action.calls() {
-        let Some(value) = resolve_expr(world, entity, &call.value) else {
+        match call.function {
+            HtmlInlineFunction::Undo | HtmlInlineFunction::Redo => {
+                execute_inline_history(world, call.function);
+            }
+            HtmlInlineFunction::Navigate => execute_inline_navigate(world, entity, call),
+            HtmlInlineFunction::Emit => execute_inline_emit(world, entity, call),
+            HtmlInlineFunction::OpenDialog => execute_inline_open_dialog(world, entity, call),
+            HtmlInlineFunction::Call => execute_inline_handler_call(world, entity, call),
+            _ => execute_inline_write(world, entity, call),
+        }
+    }
+
+    #[cfg(feature = "extended-framework")]
+    if grouped && let Some(mut store) = world.get_resource_mut::<UiBindingStore>() {
+        store.end_group();
+    }
+    #[cfg(not(feature = "extended-framework"))]
+    let _ = grouped;
+}
+
+fn execute_inline_write(world: &mut World, entity: Entity, call: &HtmlInlineCall) {
+    let target = &call.target;
+    let mut values = Vec::with_capacity(call.args.len().saturating_sub(1));
+    for arg in &call.args[1..] {
+        let Some(value) = resolve_expr(world, entity, arg) else {
             warn!(
                 "Failed to resolve inline HTML value for target '{}'",
-                call.target.as_dotted()
+                target.as_dotted()
             );
-            continue;
+            return;
         };
+        values.push(value);
+    }
+    let current = read_store_path(world, target);
+
+    let next = match (call.function, values.as_slice()) {
+        (HtmlInlineFunction::Set, [value]) => Some(value.clone()),
+        (HtmlInlineFunction::Add, [value]) => current
+            .as_ref()
+            .and_then(|current| arithmetic_value(current, value, 1.0)),
+        (HtmlInlineFunction::Min, [value]) => current
+            .as_ref()
+            .and_then(|current| arithmetic_value(current, value, -1.0)),
+        (HtmlInlineFunction::Toggle, []) => match current.clone() {
+            Some(JsonValue::Bool(value)) => Some(JsonValue::Bool(!value)),
+            None | Some(JsonValue::Null) => Some(JsonValue::Bool(true)),
+            Some(_) => None,
+        },
+        (HtmlInlineFunction::Push, [value]) => match current.clone() {
+            Some(JsonValue::Array(mut items)) => {
+                items.push(value.clone());
+                Some(JsonValue::Array(items))
+            }
+            None | Some(JsonValue::Null) => Some(JsonValue::Array(vec![value.clone()])),
+            Some(_) => None,
+        },
+        (HtmlInlineFunction::Remove, [value]) => match current.clone() {
+            Some(JsonValue::Array(mut items)) => {
+                items.retain(|item| !json_values_match(item, value));
+                Some(JsonValue::Array(items))
+            }
+            _ => None,
+        },
+        (HtmlInlineFunction::Clamp, [low, high]) => current
+            .as_ref()
+            .and_then(json_to_f64)
+            .zip(json_to_f64(low).zip(json_to_f64(high)))
+            .and_then(|(value, (low, high))| {
+                number_json(value.clamp(low.min(high), high.max(low)))
+            }),
+        _ => None,
+    };
 
-        let next = match call.function {
-            HtmlInlineFunction::Set => value,
-            HtmlInlineFunction::Add | HtmlInlineFunction::Min => {
-                let Some(current) = read_store_path(world, &call.target) else {
-                    warn!(
-                        "Cannot apply inline HTML arithmetic to unknown target '{}'",
-                        call.target.as_dotted()
-                    );
-                    continue;
-                };
-                let factor = if call.function == HtmlInlineFunction::Add {
-                    1.0
-                } else {
-                    -1.0
-                };
-                match arithmetic_value(&current, &value, factor) {
-                    Some(value) => value,
-                    None => {
-                        warn!(
-                            "Cannot apply inline HTML arithmetic to non-numeric target '{}'",
-                            call.target.as_dotted()
-                        );
-                        continue;
-                    }
-                }
+    let Some(next) = next else {
+        warn!(
+            "Inline HTML function '{:?}' cannot be applied to target '{}' ({})",
+            call.function,
+            target.as_dotted(),
+            if current.is_none() {
+                "unknown target"
+            } else {
+                "unsupported value"
             }
+        );
+        return;
+    };
+    if current
+        .as_ref()
+        .is_some_and(|current| json_values_match(current, &next))
+    {
+        return;
+    }
+
+    if !write_store_path(world, target, next) {
+        warn!(
+            "Inline HTML function target '{}' could not be written",
+            target.as_dotted()
+        );
+    }
+}
+
+fn execute_inline_navigate(world: &mut World, entity: Entity, call: &HtmlInlineCall) {
+    let Some(path) = resolve_string_arg(world, entity, call, 0) else {
+        warn!("Inline HTML function '$navigate' expects a route path");
+        return;
+    };
+    let Some(mut router) = world.get_resource_mut::<Router>() else {
+        warn!("Inline HTML function '$navigate({path})' requires a Router resource");
+        return;
+    };
+    router.navigate(path);
+}
+
+fn execute_inline_emit(world: &mut World, entity: Entity, call: &HtmlInlineCall) {
+    let Some(name) = resolve_string_arg(world, entity, call, 0) else {
+        return;
+    };
+    let value = match call.args.get(1) {
+        Some(arg) => match resolve_expr(world, entity, arg) {
+            Some(value) => value,
+            None => {
+                warn!("Failed to resolve inline HTML value for '$emit({name}, ...)'");
+                return;
+            }
+        },
+        None => JsonValue::Null,
+    };
+    world.write_message(HtmlEmit {
+        entity,
+        name,
+        value,
+    });
+}
+
+fn execute_inline_open_dialog(world: &mut World, entity: Entity, call: &HtmlInlineCall) {
+    let Some(id) = resolve_string_arg(world, entity, call, 0) else {
+        warn!("Inline HTML function '$open_dialog' expects a dialog id");
+        return;
+    };
+
+    #[cfg(feature = "extended-dialog")]
+    if !crate::dialog::open_dialog_by_id(world, &id) {
+        warn!("Inline HTML function '$open_dialog' found no dialog with id '{id}'");
+    }
+    #[cfg(not(feature = "extended-dialog"))]
+    warn!("Inline HTML function '$open_dialog({id})' requires the 'extended-dialog' feature");
+}
+
+fn execute_inline_handler_call(world: &mut World, entity: Entity, call: &HtmlInlineCall) {
+    let Some(name) = resolve_string_arg(world, entity, call, 0) else {
+        return;
+    };
+    let mut args = Vec::with_capacity(call.args.len().saturating_sub(1));
+    for arg in &call.args[1..] {
+        let Some(value) = resolve_expr(world, entity, arg) else {
+            warn!("Failed to resolve inline HTML argument for '$call({name}, ...)'");
+            return;
         };
+        args.push(value);
+    }
 
-        if !write_store_path(world, &call.target, next) {
-            warn!(
-                "Inline HTML function target '{}' could not be written",
-                call.target.as_dotted()
-            );
+    let Some(registry) = world.get_resource::<HtmlFunctionRegistry>() else {
+        return;
+    };
+    let Some(system) = registry.find_untyped(&name) else {
+        if registry.has_typed(&name) {
+            warn!("'$call({name})' needs an `In<HtmlEvent>` handler; '{name}' takes a typed event");
+        } else {
+            warn!("'$call({name})' handler not registered via #[html_fn(...)]");
+        }
+        return;
+    };
+
+    run_html_handler_with_args(world, system, HtmlEvent { entity }, args);
+}
+
+fn resolve_string_arg(
+    world: &World,
+    entity: Entity,
+    call: &HtmlInlineCall,
+    index: usize,
+) -> Option<String> {
+    match resolve_expr(world, entity, call.args.get(index)?)? {
+        JsonValue::String(value) => Some(value),
+        JsonValue::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Compares JSON values, treating numbers as equal when their values match.
+fn json_values_match(left: &JsonValue, right: &JsonValue) -> bool {
+    match (left, right) {
+        (JsonValue::Number(left), JsonValue::Number(right)) => left.as_f64() == right.as_f64(),
+        _ => left == right,
+    }
+}
+
+fn execute_inline_history(world: &mut World, function: HtmlInlineFunction) {
+    #[cfg(feature = "extended-framework")]
+    if let Some(mut store) = world.get_resource_mut::<UiBindingStore>() {
+        if function == HtmlInlineFunction::Undo {
+            store.undo();
+        } else {
+            store.redo();
         }
+        return;
     }
+
+    #[cfg(not(feature = "extended-framework"))]
+    let _ = world;
+    warn!("Inline HTML function '{function:?}' requires the 'extended-framework' feature");
 }
 
 fn parse_inline_call(raw: &str) -> Result<HtmlInlineCall, String> {
@@ -191,28 +536,58 @@ fn parse_inline_call(raw: &str) -> Result<HtmlInlineCall, String> {
     }
 
     let name = rest[..open].trim();
-    let function = match name {
-        "set" => HtmlInlineFunction::Set,
-        "add" => HtmlInlineFunction::Add,
-        "min" => HtmlInlineFunction::Min,
-        _ => return Err(format!("unknown inline HTML function '${name}'")),
+    let function = HtmlInlineFunction::from_name(name)
+        .ok_or_else(|| format!("unknown inline HTML function '${name}'"))?;
+
+    let raw_args = &rest[open + 1..rest.len() - 1];
+    let raw_args = if raw_args.trim().is_empty() {
+        Vec::new()
+    } else {
+        split_top_level(raw_args, ',')
     };
-
-    let args = split_top_level(&rest[open + 1..rest.len() - 1], ',');
-    if args.len() != 2 {
-        return Err(format!(
-            "inline function '${name}' expects exactly 2 arguments"
-        ));
+    let (min_args, max_args) = function.arity();
+    if raw_args.len() < min_args || raw_args.len() > max_args {
+        return Err(if min_args == max_args {
+            format!("inline function '${name}' expects exactly {min_args} arguments")
+        } else {
+            format!("inline function '${name}' expects {min_args} to {max_args} arguments")
+        });
+    }
+
+    let mut args = Vec::with_capacity(raw_args.len());
+    for (index, raw_arg) in raw_args.iter().enumerate() {
+        let raw_arg = raw_arg.trim();
+        if index == 0 && function.writes_target() {
+            let target = parse_path(raw_arg)
+                .ok_or_else(|| format!("invalid inline function target '{raw_arg}'"))?;
+            args.push(HtmlInlineExpr::Path(target));
+        } else if index == 0 && function.names_first_arg() {
+            let name = match parse_literal(raw_arg) {
+                Some(JsonValue::String(name)) => name,
+                _ if is_identifier(raw_arg) => raw_arg.to_string(),
+                _ => return Err(format!("invalid inline function name '{raw_arg}'")),
+            };
+            args.push(HtmlInlineExpr::Literal(JsonValue::String(name)));
+        } else {
+            args.push(parse_expr(raw_arg)?);
+        }
     }
 
-    let target = parse_path(args[0].trim())
-        .ok_or_else(|| format!("invalid inline function target '{}'", args[0].trim()))?;
-    let value = parse_expr(args[1].trim())?;
+    let target = match args.first() {
+        Some(HtmlInlineExpr::Path(path)) if function.writes_target() => path.clone(),
+        _ => HtmlInlinePath::empty(),
+    };
+    let value = args
+        .get(1)
+        .filter(|_| function.writes_target())
+        .cloned()
+        .unwrap_or(HtmlInlineExpr::Literal(JsonValue::Null));
 
     Ok(HtmlInlineCall {
         function,
         target,
         value,
+        args,
     })
 }
 
@@ -260,11 +635,16 @@ fn parse_literal(raw: &str) -> Option<JsonValue> {
     None
 }
 
-fn parse_path(raw: &str) -> Option<HtmlInlinePath> {
+pub(crate) fn parse_path(raw: &str) -> Option<HtmlInlinePath> {
     let mut segments = Vec::new();
-    for segment in raw.split('.') {
+    for (index, segment) in raw.split('.').enumerate() {
         let segment = segment.trim();
-        if !is_identifier(segment) {
+        // Only the root may name a reflected resource (`res:AudioSettings`).
+        let name = match segment.strip_prefix(REFLECT_RESOURCE_PREFIX) {
+            Some(name) if index == 0 => name,
+            _ => segment,
+        };
+        if !is_identifier(name) {
             return None;
         }
         segments.push(segment.to_string());
@@ -283,7 +663,7 @@ fn is_identifier(value: &str) -> bool {
     chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
 }
 
-fn split_top_level(raw: &str, delimiter: char) -> Vec<String> {
+pub(crate) fn split_top_level(raw: &str, delimiter: char) -> Vec<String> {
     let mut result = Vec::new();
     let mut current = String::new();
     let mut depth = 0usize;
@@ -364,6 +744,10 @@ fn resolve_expr(world: &World, entity: Entity, expr: &HtmlInlineExpr) -> Option<
 fn event_path_value(world: &World, entity: Entity, path: &HtmlInlinePath) -> Option<JsonValue> {
     match path.root() {
         "value" => widget_value(world, entity),
+        "payload" => world
+            .get_resource::<HtmlEventPropagation>()
+            .and_then(HtmlEventPropagation::payload)
+            .and_then(|payload| resolve_json_path(payload.clone(), path.tail())),
         "checked" | "selected" => widget_checked(world, entity),
         "text" => widget_text(world, entity),
         "red" | "green" | "blue" | "alpha" => color_channel_value(world, entity, path.root()),
@@ -527,6 +911,10 @@ fn widget_value_to_json(value: &WidgetValue) -> Option<JsonValue> {
 }
 
 fn read_store_path(world: &World, path: &HtmlInlinePath) -> Option<JsonValue> {
+    if path.root().starts_with(REFLECT_RESOURCE_PREFIX) {
+        return read_reflect_path(world, &path.as_dotted());
+    }
+
     #[cfg(feature = "extended-framework")]
     if let Some(store) = world.get_resource::<UiBindingStore>() {
         if let Some(value) = store.json_path(&path.as_dotted()) {
@@ -540,6 +928,10 @@ fn read_store_path(world: &World, path: &HtmlInlinePath) -> Option<JsonValue> {
 }
 
 fn write_store_path(world: &mut World, path: &HtmlInlinePath, value: JsonValue) -> bool {
+    if path.root().starts_with(REFLECT_RESOURCE_PREFIX) {
+        return write_reflect_path(world, &path.as_dotted(), value);
+    }
+
     #[cfg(feature = "extended-framework")]
     if let Some(mut store) = world.get_resource_mut::<UiBindingStore>() {
         let path = path.as_dotted();
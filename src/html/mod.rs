@@ -1,19 +1,44 @@
 mod bindings;
 pub mod builder;
 pub mod converter;
+pub mod diagnostics;
+pub mod dom;
+pub mod drag_drop;
+pub mod event_modifiers;
 pub mod inline_functions;
+pub mod propagation;
+pub mod reflect_binding;
+pub mod reflect_form;
 pub mod reload;
+pub mod shortcuts;
+mod snapshot;
+pub mod ui;
 
 pub use bindings::HtmlEventBindingsPlugin;
+pub use diagnostics::{TemplateDiagnostic, TemplateDiagnosticSeverity, TemplateDiagnosticsConfig};
+pub use dom::{HtmlInsertPosition, UiDom};
+pub use drag_drop::{HtmlDragDropPlugin, HtmlDragGhost, HtmlDraggable};
+pub use event_modifiers::HtmlEventModifiers;
 pub use inline_functions::{
-    HtmlInlineAction, HtmlInlineEventBindings, HtmlInlineFunction, parse_html_inline_action,
+    HtmlInlineAction, HtmlInlineEventBindings, HtmlInlineExpr, HtmlInlineFunction,
+    parse_html_handler_call, parse_html_inline_action, parse_html_inline_expr,
 };
 pub use inventory;
+pub use propagation::{HtmlEventControl, HtmlEventHandler, HtmlEventPhase, HtmlEventPropagation};
+pub use reflect_binding::{
+    REFLECT_RESOURCE_PREFIX, UiReflectBindings, read_reflect_path, write_reflect_path,
+};
+pub use reflect_form::{expand_reflect_forms, reflect_form_diagnostics};
+pub use shortcuts::{HtmlKeyChord, HtmlShortcut, HtmlShortcuts};
+pub use ui::{UiNode, UiWidget};
 
 #[cfg(feature = "extended-framework")]
 use crate::framework::sync_ui_binding_store_values;
 use crate::html::builder::HtmlBuilderSystem;
 use crate::html::converter::HtmlConverterSystem;
+use crate::html::reflect_binding::{
+    sync_reflect_resource_values, watch_reflect_resources_in_assets,
+};
 use crate::html::reload::HtmlReloadPlugin;
 use crate::lang::{UILang, UiLangState, UiLangVariables, UiSharedValues, refresh_shared_values};
 use bevy::ecs::system::SystemId;
@@ -25,7 +50,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::dialog::DialogWidget;
 use crate::io::{CssAsset, HtmlAsset};
 use crate::styles::Style;
-use crate::styles::parser::apply_property_to_style;
+use crate::styles::parser::apply_declaration_to_style;
 use crate::widgets::{
     Badge, Body, Button, CheckBox, ChoiceBox, ColorPicker, DatePicker, Div, Divider, FieldSet,
     Form, Headline, HyperLink, Img, InputField, ListBox, Paragraph, ProgressBar, RadioButton,
@@ -54,6 +79,8 @@ pub struct HtmlSource {
     pub handle: Handle<HtmlAsset>,
     pub source_id: String,
     pub controller: Option<String>,
+    /// Path used instead of the asset path for sources built from strings.
+    pub virtual_path: Option<String>,
 }
 
 impl HtmlSource {
@@ -63,6 +90,26 @@ impl HtmlSource {
             handle,
             source_id: String::new(),
             controller: None,
+            virtual_path: None,
+        }
+    }
+
+    /// Creates an `HtmlSource` from in-memory HTML.
+    ///
+    /// `name` acts as the asset path of the document, e.g. `"mods/shop/shop.html"`;
+    /// relative `<link>` and `src` paths resolve against its directory.
+    pub fn from_string(
+        assets: &mut Assets<HtmlAsset>,
+        name: &str,
+        html: impl Into<String>,
+    ) -> Self {
+        let handle = assets.add(HtmlAsset {
+            html: html.into(),
+            stylesheets: Vec::new(),
+        });
+        Self {
+            virtual_path: Some(name.replace('\\', "/")),
+            ..Self::from_handle(handle)
         }
     }
 
@@ -72,6 +119,7 @@ impl HtmlSource {
         self.handle
             .path()
             .map(|asset_path| asset_path.path().to_string_lossy().replace('\\', "/"))
+            .or_else(|| self.virtual_path.clone())
             .unwrap_or_default()
     }
 }
@@ -92,6 +140,16 @@ pub struct HtmlInitEmitted;
 #[derive(Resource, Default)]
 pub struct HtmlInitDelay(pub Option<u8>);
 
+/// How long a pointer must stay pressed before `onlongpress` fires.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct HtmlLongPressDelay(pub std::time::Duration);
+
+impl Default for HtmlLongPressDelay {
+    fn default() -> Self {
+        Self(std::time::Duration::from_millis(500))
+    }
+}
+
 /// Marker component for nodes that should start hidden.
 #[derive(Component)]
 pub struct NeedHidden;
@@ -143,7 +201,7 @@ impl HtmlStyle {
                 continue;
             };
 
-            apply_property_to_style(&mut style, name, value);
+            apply_declaration_to_style(&mut style, name, value, &HashMap::new());
         }
 
         HtmlStyle(style)
@@ -158,6 +216,30 @@ pub struct HtmlTextBinding {
     pub bindings: Vec<String>,
 }
 
+/// Raw HTML attributes of an element, keyed by lowercase attribute name.
+///
+/// Used by CSS attribute selectors such as `input[type="password"]`.
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component)]
+pub struct HtmlAttributes(pub HashMap<String, String>);
+
+impl HtmlAttributes {
+    /// Returns the value of `name`, if present.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// Sets `name` to `value`.
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        self.0.insert(name.to_ascii_lowercase(), value.into());
+    }
+
+    /// Removes `name`, returning whether it was present.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.0.remove(&name.to_ascii_lowercase()).is_some()
+    }
+}
+
 /// Metadata collected from HTML attributes.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct HtmlMeta {
@@ -169,6 +251,9 @@ pub struct HtmlMeta {
     pub validation: Option<ValidationRules>,
     pub inner_content: HtmlInnerContent,
     pub text_binding: Option<HtmlTextBinding>,
+    pub draggable: Option<HtmlDraggable>,
+    pub shortcuts: Option<HtmlShortcuts>,
+    pub attributes: HtmlAttributes,
 }
 
 /// Captures textual and reactive inner content for an HTML element.
@@ -742,6 +827,36 @@ pub enum HtmlFnRegistration {
         name: &'static str,
         build: fn(&mut World) -> SystemId<In<HtmlTouchEnd>, ()>,
     },
+    /// Variant `HtmlDoubleClick`.
+    HtmlDoubleClick {
+        name: &'static str,
+        build: fn(&mut World) -> SystemId<In<HtmlDoubleClick>, ()>,
+    },
+    /// Variant `HtmlContextMenu`.
+    HtmlContextMenu {
+        name: &'static str,
+        build: fn(&mut World) -> SystemId<In<HtmlContextMenu>, ()>,
+    },
+    /// Variant `HtmlLongPress`.
+    HtmlLongPress {
+        name: &'static str,
+        build: fn(&mut World) -> SystemId<In<HtmlLongPress>, ()>,
+    },
+    /// Variant `HtmlDragEnter`.
+    HtmlDragEnter {
+        name: &'static str,
+        build: fn(&mut World) -> SystemId<In<HtmlDragEnter>, ()>,
+    },
+    /// Variant `HtmlDragLeave`.
+    HtmlDragLeave {
+        name: &'static str,
+        build: fn(&mut World) -> SystemId<In<HtmlDragLeave>, ()>,
+    },
+    /// Variant `HtmlDrop`.
+    HtmlDrop {
+        name: &'static str,
+        build: fn(&mut World) -> SystemId<In<HtmlDrop>, ()>,
+    },
 }
 
 inventory::collect!(HtmlFnRegistration);
@@ -767,6 +882,56 @@ impl HtmlEvent {
     }
 }
 
+/// Arguments of the HTML handler call that is currently running.
+///
+/// Filled for `onclick="handler(args...)"` and `$call(handler, args...)` and
+/// read by the handler through `Res<HtmlEventArgs>`. Empty for plain event
+/// handlers.
+#[derive(Resource, Default, Clone, Debug, PartialEq)]
+pub struct HtmlEventArgs {
+    values: Vec<serde_json::Value>,
+}
+
+impl HtmlEventArgs {
+    /// Returns all arguments as JSON values.
+    pub fn values(&self) -> &[serde_json::Value] {
+        &self.values
+    }
+
+    /// Deserializes the argument at `index`.
+    pub fn get<T: serde::de::DeserializeOwned>(&self, index: usize) -> Option<T> {
+        serde_json::from_value(self.values.get(index)?.clone()).ok()
+    }
+
+    /// Deserializes all arguments into a tuple, e.g. `(u32, String)`.
+    pub fn parse<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_value(serde_json::Value::Array(self.values.clone()))
+    }
+}
+
+/// Runs an HTML handler with `args` exposed as [`HtmlEventArgs`].
+pub(crate) fn run_html_handler_with_args<E: Send + 'static>(
+    world: &mut World,
+    system: SystemId<In<E>>,
+    event: E,
+    args: Vec<serde_json::Value>,
+) {
+    world.insert_resource(HtmlEventArgs { values: args });
+    if let Err(err) = world.run_system_with(system, event) {
+        warn!("HTML handler {system:?} failed: {err}");
+    }
+    world.insert_resource(HtmlEventArgs::default());
+}
+
+/// Message written by the inline `$emit(name, value)` function.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct HtmlEmit {
+    /// Entity that fired the event whose attribute ran `$emit`.
+    pub entity: Entity,
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
 /// Registry of HTML event handlers by name and event type.
 #[derive(Default, Resource)]
 pub struct HtmlFunctionRegistry {
@@ -789,6 +954,12 @@ pub struct HtmlFunctionRegistry {
     pub touchstart: HashMap<String, SystemId<In<HtmlEvent>>>,
     pub touchmove: HashMap<String, SystemId<In<HtmlEvent>>>,
     pub touchend: HashMap<String, SystemId<In<HtmlEvent>>>,
+    pub dblclick: HashMap<String, SystemId<In<HtmlEvent>>>,
+    pub contextmenu: HashMap<String, SystemId<In<HtmlEvent>>>,
+    pub longpress: HashMap<String, SystemId<In<HtmlEvent>>>,
+    pub dragenter: HashMap<String, SystemId<In<HtmlEvent>>>,
+    pub dragleave: HashMap<String, SystemId<In<HtmlEvent>>>,
+    pub drop: HashMap<String, SystemId<In<HtmlEvent>>>,
     pub click_typed: HashMap<String, SystemId<In<HtmlClick>>>,
     pub mousedown_typed: HashMap<String, SystemId<In<HtmlMouseDown>>>,
     pub mouseup_typed: HashMap<String, SystemId<In<HtmlMouseUp>>>,
@@ -808,6 +979,76 @@ pub struct HtmlFunctionRegistry {
     pub touchstart_typed: HashMap<String, SystemId<In<HtmlTouchStart>>>,
     pub touchmove_typed: HashMap<String, SystemId<In<HtmlTouchMove>>>,
     pub touchend_typed: HashMap<String, SystemId<In<HtmlTouchEnd>>>,
+    pub dblclick_typed: HashMap<String, SystemId<In<HtmlDoubleClick>>>,
+    pub contextmenu_typed: HashMap<String, SystemId<In<HtmlContextMenu>>>,
+    pub longpress_typed: HashMap<String, SystemId<In<HtmlLongPress>>>,
+    pub dragenter_typed: HashMap<String, SystemId<In<HtmlDragEnter>>>,
+    pub dragleave_typed: HashMap<String, SystemId<In<HtmlDragLeave>>>,
+    pub drop_typed: HashMap<String, SystemId<In<HtmlDrop>>>,
+}
+
+impl HtmlFunctionRegistry {
+    /// Finds an `HtmlEvent` handler by name, whichever event map it was registered in.
+    pub(crate) fn find_untyped(&self, name: &str) -> Option<SystemId<In<HtmlEvent>>> {
+        [
+            &self.click,
+            &self.mousedown,
+            &self.mouseup,
+            &self.over,
+            &self.out,
+            &self.change,
+            &self.submit,
+            &self.init,
+            &self.focus,
+            &self.scroll,
+            &self.wheel,
+            &self.keydown,
+            &self.keyup,
+            &self.dragstart,
+            &self.drag,
+            &self.dragstop,
+            &self.touchstart,
+            &self.touchmove,
+            &self.touchend,
+            &self.dblclick,
+            &self.contextmenu,
+            &self.longpress,
+            &self.dragenter,
+            &self.dragleave,
+            &self.drop,
+        ]
+        .into_iter()
+        .find_map(|handlers| handlers.get(name).copied())
+    }
+
+    /// Returns `true` when `name` is only known as a typed event handler.
+    pub(crate) fn has_typed(&self, name: &str) -> bool {
+        self.click_typed.contains_key(name)
+            || self.mousedown_typed.contains_key(name)
+            || self.mouseup_typed.contains_key(name)
+            || self.over_typed.contains_key(name)
+            || self.out_typed.contains_key(name)
+            || self.change_typed.contains_key(name)
+            || self.submit_typed.contains_key(name)
+            || self.init_typed.contains_key(name)
+            || self.focus_typed.contains_key(name)
+            || self.scroll_typed.contains_key(name)
+            || self.wheel_typed.contains_key(name)
+            || self.keydown_typed.contains_key(name)
+            || self.keyup_typed.contains_key(name)
+            || self.dragstart_typed.contains_key(name)
+            || self.drag_typed.contains_key(name)
+            || self.dragstop_typed.contains_key(name)
+            || self.touchstart_typed.contains_key(name)
+            || self.touchmove_typed.contains_key(name)
+            || self.touchend_typed.contains_key(name)
+            || self.dblclick_typed.contains_key(name)
+            || self.contextmenu_typed.contains_key(name)
+            || self.longpress_typed.contains_key(name)
+            || self.dragenter_typed.contains_key(name)
+            || self.dragleave_typed.contains_key(name)
+            || self.drop_typed.contains_key(name)
+    }
 }
 
 /// Component storing event handler names attached in HTML.
@@ -832,51 +1073,146 @@ pub struct HtmlEventBindings {
     pub ontouchstart: Option<String>,
     pub ontouchmove: Option<String>,
     pub ontouchend: Option<String>,
+    pub ondblclick: Option<String>,
+    pub oncontextmenu: Option<String>,
+    pub onlongpress: Option<String>,
+    pub ondragenter: Option<String>,
+    pub ondragleave: Option<String>,
+    pub ondrop: Option<String>,
     #[reflect(ignore)]
     pub inline: HtmlInlineEventBindings,
+    /// Arguments of `handler(args...)` calls, keyed by event field (`"onclick"`).
+    #[reflect(ignore)]
+    pub args: HashMap<String, Vec<HtmlInlineExpr>>,
+    /// Capture-phase handlers from `onclick.capture`, keyed by event field.
+    #[reflect(ignore)]
+    pub capture: HashMap<String, HtmlEventHandler>,
+    /// `.once`, `.throttle` and `.debounce` modifiers, keyed by event field
+    /// (`"onclick"`) or capture key (`"onclick.capture"`).
+    #[reflect(ignore)]
+    pub modifiers: HashMap<String, HtmlEventModifiers>,
+}
+
+impl HtmlEventBindings {
+    /// Returns the handler name bound to `attribute`, e.g. `"onclick"`.
+    pub fn handler(&self, attribute: &str) -> Option<&str> {
+        match attribute {
+            "onclick" => self.onclick.as_deref(),
+            "onmousedown" => self.onmousedown.as_deref(),
+            "onmouseup" => self.onmouseup.as_deref(),
+            "onmouseover" => self.onmouseover.as_deref(),
+            "onmouseout" => self.onmouseout.as_deref(),
+            "onchange" => self.onchange.as_deref(),
+            "oninit" => self.oninit.as_deref(),
+            "onfoucs" => self.onfoucs.as_deref(),
+            "onscroll" => self.onscroll.as_deref(),
+            "onwheel" => self.onwheel.as_deref(),
+            "onkeydown" => self.onkeydown.as_deref(),
+            "onkeyup" => self.onkeyup.as_deref(),
+            "ondragstart" => self.ondragstart.as_deref(),
+            "ondrag" => self.ondrag.as_deref(),
+            "ondragstop" => self.ondragstop.as_deref(),
+            "ontouchstart" => self.ontouchstart.as_deref(),
+            "ontouchmove" => self.ontouchmove.as_deref(),
+            "ontouchend" => self.ontouchend.as_deref(),
+            "ondblclick" => self.ondblclick.as_deref(),
+            "oncontextmenu" => self.oncontextmenu.as_deref(),
+            "onlongpress" => self.onlongpress.as_deref(),
+            "ondragenter" => self.ondragenter.as_deref(),
+            "ondragleave" => self.ondragleave.as_deref(),
+            "ondrop" => self.ondrop.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Binds the handler `name` to `attribute`; returns `false` for unknown
+    /// attributes.
+    pub fn set_handler(&mut self, attribute: &str, name: impl Into<String>) -> bool {
+        let slot = match attribute {
+            "onclick" => &mut self.onclick,
+            "onmousedown" => &mut self.onmousedown,
+            "onmouseup" => &mut self.onmouseup,
+            "onmouseover" => &mut self.onmouseover,
+            "onmouseout" => &mut self.onmouseout,
+            "onchange" => &mut self.onchange,
+            "oninit" => &mut self.oninit,
+            "onfoucs" => &mut self.onfoucs,
+            "onscroll" => &mut self.onscroll,
+            "onwheel" => &mut self.onwheel,
+            "onkeydown" => &mut self.onkeydown,
+            "onkeyup" => &mut self.onkeyup,
+            "ondragstart" => &mut self.ondragstart,
+            "ondrag" => &mut self.ondrag,
+            "ondragstop" => &mut self.ondragstop,
+            "ontouchstart" => &mut self.ontouchstart,
+            "ontouchmove" => &mut self.ontouchmove,
+            "ontouchend" => &mut self.ontouchend,
+            "ondblclick" => &mut self.ondblclick,
+            "oncontextmenu" => &mut self.oncontextmenu,
+            "onlongpress" => &mut self.onlongpress,
+            "ondragenter" => &mut self.ondragenter,
+            "ondragleave" => &mut self.ondragleave,
+            "ondrop" => &mut self.ondrop,
+            _ => return false,
+        };
+        *slot = Some(name.into());
+        true
+    }
+
+    /// Returns `true` when `attribute` has a handler in any phase.
+    pub fn listens(&self, attribute: &str) -> bool {
+        self.handler(attribute).is_some()
+            || self.inline.get(attribute).is_some()
+            || self.capture.contains_key(attribute)
+    }
 }
 
 /// Click event sent from HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlClick {
     #[event_target]
     pub entity: Entity,
     pub position: Vec2,
     pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Mouse-down event sent from HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlMouseDown {
     #[event_target]
     pub entity: Entity,
     pub button: PointerButton,
     pub position: Vec2,
     pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Mouse-up event sent from HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlMouseUp {
     #[event_target]
     pub entity: Entity,
     pub button: PointerButton,
     pub position: Vec2,
     pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Mouse-over event sent from HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlMouseOver {
     #[event_target]
     pub entity: Entity,
+    pub propagation: HtmlEventControl,
 }
 
 /// Mouse-out event sent from HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlMouseOut {
     #[event_target]
     pub entity: Entity,
+    pub propagation: HtmlEventControl,
 }
 
 /// Change action types for HTML change events.
@@ -891,11 +1227,12 @@ pub enum HtmlChangeAction {
 }
 
 /// Change event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlChange {
     #[event_target]
     pub entity: Entity,
     pub action: HtmlChangeAction,
+    pub propagation: HtmlEventControl,
 }
 
 /// Form submit event emitted by HTML forms.
@@ -943,7 +1280,7 @@ pub struct HtmlScroll {
 }
 
 /// Wheel event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlWheel {
     #[event_target]
     pub entity: Entity,
@@ -951,60 +1288,67 @@ pub struct HtmlWheel {
     pub delta: Vec2,
     pub position: Vec2,
     pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Key-down event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlKeyDown {
     #[event_target]
     pub entity: Entity,
     pub key: KeyCode,
+    pub propagation: HtmlEventControl,
 }
 
 /// Key-up event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlKeyUp {
     #[event_target]
     pub entity: Entity,
     pub key: KeyCode,
+    pub propagation: HtmlEventControl,
 }
 
 /// Drag-start event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlDragStart {
     #[event_target]
     pub entity: Entity,
     pub position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Drag event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlDrag {
     #[event_target]
     pub entity: Entity,
     pub position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Drag-stop event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlDragStop {
     #[event_target]
     pub entity: Entity,
     pub position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Touch-start event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlTouchStart {
     #[event_target]
     pub entity: Entity,
     pub touch_id: u64,
     pub position: Vec2,
     pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Touch-move event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlTouchMove {
     #[event_target]
     pub entity: Entity,
@@ -1012,16 +1356,88 @@ pub struct HtmlTouchMove {
     pub position: Vec2,
     pub inner_position: Vec2,
     pub delta: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Touch-end event emitted by HTML widgets.
-#[derive(EntityEvent, Clone, Copy)]
+#[derive(EntityEvent, Clone)]
 pub struct HtmlTouchEnd {
     #[event_target]
     pub entity: Entity,
     pub touch_id: u64,
     pub position: Vec2,
     pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
+}
+
+/// Double-click event emitted by HTML widgets.
+#[derive(EntityEvent, Clone)]
+pub struct HtmlDoubleClick {
+    #[event_target]
+    pub entity: Entity,
+    pub position: Vec2,
+    pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
+}
+
+/// Secondary-button click emitted by HTML widgets.
+#[derive(EntityEvent, Clone)]
+pub struct HtmlContextMenu {
+    #[event_target]
+    pub entity: Entity,
+    pub position: Vec2,
+    pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
+}
+
+/// Long-press event emitted once a touch or the primary mouse button was
+/// held for [`HtmlLongPressDelay`].
+#[derive(EntityEvent, Clone)]
+pub struct HtmlLongPress {
+    #[event_target]
+    pub entity: Entity,
+    pub position: Vec2,
+    pub inner_position: Vec2,
+    pub propagation: HtmlEventControl,
+}
+
+/// Emitted on a drop target when a `draggable` widget is dragged onto it.
+#[derive(EntityEvent, Clone)]
+pub struct HtmlDragEnter {
+    #[event_target]
+    pub entity: Entity,
+    /// The dragged widget.
+    pub source: Entity,
+    /// Evaluated `data-drag` payload, `null` without one.
+    pub payload: serde_json::Value,
+    pub position: Vec2,
+    pub propagation: HtmlEventControl,
+}
+
+/// Emitted on a drop target when the dragged widget leaves it again.
+#[derive(EntityEvent, Clone)]
+pub struct HtmlDragLeave {
+    #[event_target]
+    pub entity: Entity,
+    /// The dragged widget.
+    pub source: Entity,
+    /// Evaluated `data-drag` payload, `null` without one.
+    pub payload: serde_json::Value,
+    pub position: Vec2,
+    pub propagation: HtmlEventControl,
+}
+
+/// Emitted on the drop target a `draggable` widget was released over.
+#[derive(EntityEvent, Clone)]
+pub struct HtmlDrop {
+    #[event_target]
+    pub entity: Entity,
+    /// The dragged widget.
+    pub source: Entity,
+    /// Evaluated `data-drag` payload, `null` without one.
+    pub payload: serde_json::Value,
+    pub position: Vec2,
+    pub propagation: HtmlEventControl,
 }
 
 /// Main plugin for HTML UI: converter + builder + reload integration.
@@ -1041,12 +1457,14 @@ impl Plugin for ExtendedUiHtmlPlugin {
         app.init_resource::<UiLangState>();
         app.init_resource::<UiLangVariables>();
         app.init_resource::<UiSharedValues>();
+        app.init_resource::<UiReflectBindings>();
 
         app.register_type::<HtmlEventBindings>();
         app.register_type::<HtmlSource>();
         app.register_type::<HtmlStyle>();
         app.register_type::<HtmlInnerContent>();
         app.register_type::<HtmlTextBinding>();
+        app.register_type::<HtmlAttributes>();
 
         app.configure_sets(
             Update,
@@ -1063,9 +1481,13 @@ impl Plugin for ExtendedUiHtmlPlugin {
             HtmlBuilderSystem,
             HtmlReloadPlugin,
             HtmlEventBindingsPlugin,
+            HtmlDragDropPlugin,
         ));
 
-        app.add_systems(PreUpdate, sync_shared_values_system);
+        app.add_systems(
+            PreUpdate,
+            (watch_reflect_resources_in_assets, sync_shared_values_system).chain(),
+        );
 
         app.add_systems(Startup, (run_component_inits, register_html_fns));
     }
@@ -1075,6 +1497,7 @@ fn sync_shared_values_system(world: &mut World) {
     refresh_shared_values(world);
     #[cfg(feature = "extended-framework")]
     sync_ui_binding_store_values(world);
+    sync_reflect_resource_values(world);
 }
 
 /// Registers all HTML event handlers collected via `inventory`.
@@ -1220,6 +1643,48 @@ pub fn register_html_fns(world: &mut World) {
                     .touchend_typed
                     .insert((*name).to_string(), id);
             }
+            HtmlFnRegistration::HtmlDoubleClick { name, build } => {
+                let id = (*build)(world);
+                world
+                    .resource_mut::<HtmlFunctionRegistry>()
+                    .dblclick_typed
+                    .insert((*name).to_string(), id);
+            }
+            HtmlFnRegistration::HtmlContextMenu { name, build } => {
+                let id = (*build)(world);
+                world
+                    .resource_mut::<HtmlFunctionRegistry>()
+                    .contextmenu_typed
+                    .insert((*name).to_string(), id);
+            }
+            HtmlFnRegistration::HtmlLongPress { name, build } => {
+                let id = (*build)(world);
+                world
+                    .resource_mut::<HtmlFunctionRegistry>()
+                    .longpress_typed
+                    .insert((*name).to_string(), id);
+            }
+            HtmlFnRegistration::HtmlDragEnter { name, build } => {
+                let id = (*build)(world);
+                world
+                    .resource_mut::<HtmlFunctionRegistry>()
+                    .dragenter_typed
+                    .insert((*name).to_string(), id);
+            }
+            HtmlFnRegistration::HtmlDragLeave { name, build } => {
+                let id = (*build)(world);
+                world
+                    .resource_mut::<HtmlFunctionRegistry>()
+                    .dragleave_typed
+                    .insert((*name).to_string(), id);
+            }
+            HtmlFnRegistration::HtmlDrop { name, build } => {
+                let id = (*build)(world);
+                world
+                    .resource_mut::<HtmlFunctionRegistry>()
+                    .drop_typed
+                    .insert((*name).to_string(), id);
+            }
         }
     }
 
@@ -1242,6 +1707,12 @@ pub fn register_html_fns(world: &mut World) {
         reg.touchstart.insert(name.clone(), id);
         reg.touchmove.insert(name.clone(), id);
         reg.touchend.insert(name.clone(), id);
+        reg.dblclick.insert(name.clone(), id);
+        reg.contextmenu.insert(name.clone(), id);
+        reg.longpress.insert(name.clone(), id);
+        reg.dragenter.insert(name.clone(), id);
+        reg.dragleave.insert(name.clone(), id);
+        reg.drop.insert(name.clone(), id);
         reg.out.insert(name.clone(), id);
         reg.over.insert(name.clone(), id);
         debug!("Registered html fn '{name}' with id {id:?}");
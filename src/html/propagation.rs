@@ -0,0 +1,515 @@
+use bevy::ecs::system::{SystemId, SystemParam};
+use bevy::prelude::*;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::html::event_modifiers::run_html_handler_modified;
+use crate::html::inline_functions::{execute_html_inline_action, resolve_html_handler_args};
+use crate::html::{
+    HtmlChange, HtmlClick, HtmlContextMenu, HtmlDoubleClick, HtmlDrag, HtmlDragEnter,
+    HtmlDragLeave, HtmlDragStart, HtmlDragStop, HtmlDrop, HtmlEvent, HtmlEventBindings,
+    HtmlFunctionRegistry, HtmlInlineAction, HtmlInlineExpr, HtmlKeyDown, HtmlKeyUp, HtmlLongPress,
+    HtmlMouseDown, HtmlMouseOut, HtmlMouseOver, HtmlMouseUp, HtmlTouchEnd, HtmlTouchMove,
+    HtmlTouchStart, HtmlWheel, run_html_handler_with_args,
+};
+
+/// Phase in which an HTML event handler runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HtmlEventPhase {
+    /// Root to target, for `onclick.capture` handlers.
+    Capture,
+    /// Handlers on the event target itself.
+    #[default]
+    AtTarget,
+    /// Target to root, for plain `onclick` handlers on ancestors.
+    Bubble,
+}
+
+/// Propagation flags of one event dispatch, shared by every copy of the event.
+///
+/// Bubbling events carry it as their `propagation` field, so a typed handler
+/// calls `stop_propagation()` or `prevent_default()` on the event it receives.
+/// Each dispatch starts with fresh flags.
+#[derive(Clone, Debug, Default)]
+pub struct HtmlEventControl(Arc<HtmlEventControlFlags>);
+
+#[derive(Debug, Default)]
+struct HtmlEventControlFlags {
+    stopped: AtomicBool,
+    default_prevented: AtomicBool,
+}
+
+impl HtmlEventControl {
+    /// Stops the event from reaching handlers on further entities.
+    pub fn stop_propagation(&self) {
+        self.0.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once a handler stopped the event.
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.0.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Skips the built-in default action, e.g. the form submit of a
+    /// submit button click.
+    pub fn prevent_default(&self) {
+        self.0.default_prevented.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once a handler prevented the default action.
+    pub fn is_default_prevented(&self) -> bool {
+        self.0.default_prevented.load(Ordering::Relaxed)
+    }
+}
+
+/// Propagation state of the HTML events whose handlers are running.
+///
+/// Untyped handlers take `ResMut<HtmlEventPropagation>` to stop the event or
+/// skip its default action; it shares the flags of the event's
+/// [`HtmlEventControl`]. Every dispatch pushes its own entry, so an event
+/// triggered from inside a handler does not share state with the outer one.
+#[derive(Resource, Debug, Default)]
+pub struct HtmlEventPropagation {
+    dispatches: Vec<HtmlEventDispatch>,
+}
+
+#[derive(Clone, Debug)]
+struct HtmlEventDispatch {
+    target: Entity,
+    current_target: Entity,
+    phase: HtmlEventPhase,
+    control: HtmlEventControl,
+    payload: Option<JsonValue>,
+}
+
+impl HtmlEventPropagation {
+    /// Stops the event from reaching handlers on further entities.
+    pub fn stop_propagation(&mut self) {
+        if let Some(dispatch) = self.dispatches.last() {
+            dispatch.control.stop_propagation();
+        }
+    }
+
+    /// Returns `true` once a handler called [`HtmlEventPropagation::stop_propagation`].
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.dispatches
+            .last()
+            .is_some_and(|dispatch| dispatch.control.is_propagation_stopped())
+    }
+
+    /// Skips the built-in default action, e.g. the form submit of a
+    /// submit button click.
+    pub fn prevent_default(&mut self) {
+        if let Some(dispatch) = self.dispatches.last() {
+            dispatch.control.prevent_default();
+        }
+    }
+
+    /// Returns `true` once a handler called [`HtmlEventPropagation::prevent_default`].
+    pub fn is_default_prevented(&self) -> bool {
+        self.dispatches
+            .last()
+            .is_some_and(|dispatch| dispatch.control.is_default_prevented())
+    }
+
+    /// Widget that fired the event; the same as the handler's `entity`.
+    pub fn target(&self) -> Option<Entity> {
+        self.dispatches.last().map(|dispatch| dispatch.target)
+    }
+
+    /// Entity whose handler is currently running.
+    pub fn current_target(&self) -> Option<Entity> {
+        self.dispatches
+            .last()
+            .map(|dispatch| dispatch.current_target)
+    }
+
+    /// Phase of the handler that is currently running.
+    pub fn phase(&self) -> Option<HtmlEventPhase> {
+        self.dispatches.last().map(|dispatch| dispatch.phase)
+    }
+
+    /// Payload of drag and drop events, read by `$event.payload`.
+    pub fn payload(&self) -> Option<&JsonValue> {
+        self.dispatches.last()?.payload.as_ref()
+    }
+
+    fn enter(&mut self, entity: Entity, phase: HtmlEventPhase) {
+        if let Some(dispatch) = self.dispatches.last_mut() {
+            dispatch.current_target = entity;
+            dispatch.phase = phase;
+        }
+    }
+}
+
+/// Handler attached to one event attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HtmlEventHandler {
+    /// Inline action such as `$set(info.value, 1)`.
+    Inline(HtmlInlineAction),
+    /// `#[html_fn]` handler name with its `handler(args...)` arguments.
+    Named {
+        name: String,
+        args: Vec<HtmlInlineExpr>,
+    },
+}
+
+impl HtmlEventHandler {
+    /// Returns the bubble-phase handler of `attribute`, e.g. `"onclick"`.
+    fn bubble(bindings: &HtmlEventBindings, attribute: &str) -> Option<Self> {
+        if let Some(action) = bindings.inline.get(attribute) {
+            return Some(Self::Inline(action.clone()));
+        }
+        let name = bindings.handler(attribute)?;
+        Some(Self::Named {
+            name: name.to_string(),
+            args: bindings.args.get(attribute).cloned().unwrap_or_default(),
+        })
+    }
+}
+
+/// HTML events that travel from their target up the `ChildOf` chain.
+pub(crate) trait HtmlBubblingEvent: EntityEvent + Clone + Send + Sync + 'static {
+    /// Event attribute, e.g. `"onclick"`.
+    const ATTRIBUTE: &'static str;
+
+    fn typed_system(registry: &HtmlFunctionRegistry, name: &str) -> Option<SystemId<In<Self>>>;
+
+    fn control_mut(&mut self) -> &mut HtmlEventControl;
+
+    fn system(registry: &HtmlFunctionRegistry, name: &str) -> Option<SystemId<In<HtmlEvent>>>;
+
+    /// Value exposed to handlers as `$event.payload`.
+    fn payload(&self) -> Option<&JsonValue> {
+        None
+    }
+
+    /// Runs the built-in behaviour unless a handler prevented it.
+    fn default_action(&self, _world: &mut World) {}
+}
+
+macro_rules! html_bubbling_event {
+    ($event:ty, $attribute:literal, $system:ident, $typed:ident $(, payload: $payload:ident)?) => {
+        impl HtmlBubblingEvent for $event {
+            const ATTRIBUTE: &'static str = $attribute;
+
+            $(
+                fn payload(&self) -> Option<&JsonValue> {
+                    Some(&self.$payload)
+                }
+            )?
+
+            fn typed_system(
+                registry: &HtmlFunctionRegistry,
+                name: &str,
+            ) -> Option<SystemId<In<Self>>> {
+                registry.$typed.get(name).copied()
+            }
+
+            fn control_mut(&mut self) -> &mut HtmlEventControl {
+                &mut self.propagation
+            }
+
+            fn system(
+                registry: &HtmlFunctionRegistry,
+                name: &str,
+            ) -> Option<SystemId<In<HtmlEvent>>> {
+                registry.$system.get(name).copied()
+            }
+        }
+    };
+}
+
+html_bubbling_event!(HtmlMouseDown, "onmousedown", mousedown, mousedown_typed);
+html_bubbling_event!(HtmlMouseUp, "onmouseup", mouseup, mouseup_typed);
+html_bubbling_event!(HtmlMouseOver, "onmouseover", over, over_typed);
+html_bubbling_event!(HtmlMouseOut, "onmouseout", out, out_typed);
+html_bubbling_event!(HtmlChange, "onchange", change, change_typed);
+html_bubbling_event!(HtmlWheel, "onwheel", wheel, wheel_typed);
+html_bubbling_event!(HtmlKeyDown, "onkeydown", keydown, keydown_typed);
+html_bubbling_event!(HtmlKeyUp, "onkeyup", keyup, keyup_typed);
+html_bubbling_event!(HtmlDragStart, "ondragstart", dragstart, dragstart_typed);
+html_bubbling_event!(HtmlDrag, "ondrag", drag, drag_typed);
+html_bubbling_event!(HtmlDragStop, "ondragstop", dragstop, dragstop_typed);
+html_bubbling_event!(HtmlTouchStart, "ontouchstart", touchstart, touchstart_typed);
+html_bubbling_event!(HtmlTouchMove, "ontouchmove", touchmove, touchmove_typed);
+html_bubbling_event!(HtmlTouchEnd, "ontouchend", touchend, touchend_typed);
+html_bubbling_event!(HtmlDoubleClick, "ondblclick", dblclick, dblclick_typed);
+html_bubbling_event!(
+    HtmlContextMenu,
+    "oncontextmenu",
+    contextmenu,
+    contextmenu_typed
+);
+html_bubbling_event!(HtmlLongPress, "onlongpress", longpress, longpress_typed);
+html_bubbling_event!(
+    HtmlDragEnter,
+    "ondragenter",
+    dragenter,
+    dragenter_typed,
+    payload: payload
+);
+html_bubbling_event!(
+    HtmlDragLeave,
+    "ondragleave",
+    dragleave,
+    dragleave_typed,
+    payload: payload
+);
+html_bubbling_event!(HtmlDrop, "ondrop", drop, drop_typed, payload: payload);
+
+macro_rules! html_event_control {
+    ($($event:ty),* $(,)?) => {
+        $(
+            impl $event {
+                /// Stops the event from reaching handlers on further entities.
+                pub fn stop_propagation(&self) {
+                    self.propagation.stop_propagation();
+                }
+
+                /// Skips the built-in default action of the event.
+                pub fn prevent_default(&self) {
+                    self.propagation.prevent_default();
+                }
+            }
+        )*
+    };
+}
+
+html_event_control!(
+    HtmlClick,
+    HtmlMouseDown,
+    HtmlMouseUp,
+    HtmlMouseOver,
+    HtmlMouseOut,
+    HtmlChange,
+    HtmlWheel,
+    HtmlKeyDown,
+    HtmlKeyUp,
+    HtmlDragStart,
+    HtmlDrag,
+    HtmlDragStop,
+    HtmlTouchStart,
+    HtmlTouchMove,
+    HtmlTouchEnd,
+    HtmlDoubleClick,
+    HtmlContextMenu,
+    HtmlLongPress,
+    HtmlDragEnter,
+    HtmlDragLeave,
+    HtmlDrop,
+);
+
+/// Default action of a click, e.g. submitting the surrounding form.
+///
+/// Triggered after the `onclick` handlers ran, unless one of them called
+/// `prevent_default()`.
+#[derive(EntityEvent, Clone, Copy)]
+pub(crate) struct HtmlClickDefault {
+    #[event_target]
+    pub entity: Entity,
+}
+
+impl HtmlBubblingEvent for HtmlClick {
+    const ATTRIBUTE: &'static str = "onclick";
+
+    fn typed_system(registry: &HtmlFunctionRegistry, name: &str) -> Option<SystemId<In<Self>>> {
+        registry.click_typed.get(name).copied()
+    }
+
+    fn control_mut(&mut self) -> &mut HtmlEventControl {
+        &mut self.propagation
+    }
+
+    fn system(registry: &HtmlFunctionRegistry, name: &str) -> Option<SystemId<In<HtmlEvent>>> {
+        registry.click.get(name).copied()
+    }
+
+    fn default_action(&self, world: &mut World) {
+        world.trigger(HtmlClickDefault {
+            entity: self.entity,
+        });
+    }
+}
+
+/// Looks up HTML event handlers along the `ChildOf` chain.
+#[derive(SystemParam)]
+pub(crate) struct HtmlEventPath<'w, 's> {
+    parents: Query<'w, 's, &'static ChildOf>,
+    bindings: Query<'w, 's, &'static HtmlEventBindings>,
+}
+
+impl HtmlEventPath<'_, '_> {
+    /// Iterates `entity` and its ancestors, closest first.
+    pub(crate) fn ancestors(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        std::iter::successors(Some(entity), |entity| {
+            self.parents.get(*entity).ok().map(ChildOf::parent)
+        })
+    }
+
+    /// Returns `true` when `entity` itself handles `attribute`.
+    pub(crate) fn handles(&self, entity: Entity, attribute: &str) -> bool {
+        self.bindings
+            .get(entity)
+            .is_ok_and(|bindings| bindings.listens(attribute))
+    }
+
+    /// Returns `true` when `entity` or one of its ancestors handles `attribute`.
+    pub(crate) fn listens(&self, entity: Entity, attribute: &str) -> bool {
+        self.ancestors(entity)
+            .any(|entity| self.handles(entity, attribute))
+    }
+
+    /// Returns `true` when `entity` is the closest HTML widget above `original`.
+    ///
+    /// Pointer events bubble through every entity; only the first HTML widget
+    /// emits, the HTML dispatch takes care of its ancestors.
+    pub(crate) fn is_html_target(&self, original: Entity, entity: Entity) -> bool {
+        self.ancestors(original)
+            .find(|candidate| self.bindings.contains(*candidate))
+            == Some(entity)
+    }
+}
+
+/// Dispatches a bubbling HTML event to the capture and bubble handlers on
+/// its path.
+pub(crate) fn on_html_bubbling_event<E: HtmlBubblingEvent>(ev: On<E>, mut commands: Commands) {
+    let event = ev.event().clone();
+    commands.queue(move |world: &mut World| dispatch_html_event(world, event));
+}
+
+fn dispatch_html_event<E: HtmlBubblingEvent>(world: &mut World, mut event: E) {
+    let control = HtmlEventControl::default();
+    *event.control_mut() = control.clone();
+    let target = event.event_target();
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(parent) = world.get::<ChildOf>(current).map(ChildOf::parent) {
+        path.push(parent);
+        current = parent;
+    }
+
+    let mut handlers = Vec::new();
+    for &entity in path.iter().rev() {
+        let capture = world
+            .get::<HtmlEventBindings>(entity)
+            .and_then(|bindings| bindings.capture.get(E::ATTRIBUTE).cloned());
+        if let Some(handler) = capture {
+            handlers.push((entity, HtmlEventPhase::Capture, handler));
+        }
+    }
+    for (index, &entity) in path.iter().enumerate() {
+        let bubble = world
+            .get::<HtmlEventBindings>(entity)
+            .and_then(|bindings| HtmlEventHandler::bubble(bindings, E::ATTRIBUTE));
+        if let Some(handler) = bubble {
+            let phase = if index == 0 {
+                HtmlEventPhase::AtTarget
+            } else {
+                HtmlEventPhase::Bubble
+            };
+            handlers.push((entity, phase, handler));
+        }
+    }
+
+    world
+        .get_resource_or_init::<HtmlEventPropagation>()
+        .dispatches
+        .push(HtmlEventDispatch {
+            target,
+            current_target: target,
+            phase: HtmlEventPhase::AtTarget,
+            control: control.clone(),
+            payload: event.payload().cloned(),
+        });
+    for (entity, phase, handler) in handlers {
+        world
+            .resource_mut::<HtmlEventPropagation>()
+            .enter(entity, phase);
+        let key = match phase {
+            HtmlEventPhase::Capture => format!("{}.capture", E::ATTRIBUTE),
+            _ => E::ATTRIBUTE.to_string(),
+        };
+        // Debounced handlers run after this dispatch ended, so they get a
+        // copy of its frame; stopping propagation there has no effect.
+        let deferred = world
+            .get::<HtmlEventBindings>(entity)
+            .and_then(|bindings| bindings.modifiers.get(&key))
+            .is_some_and(|modifiers| modifiers.debounce.is_some());
+        let frame = deferred
+            .then(|| {
+                world
+                    .resource::<HtmlEventPropagation>()
+                    .dispatches
+                    .last()
+                    .cloned()
+            })
+            .flatten();
+        let event = event.clone();
+        run_html_handler_modified(world, entity, &key, move |world: &mut World| {
+            let Some(frame) = frame else {
+                run_html_event_handler(world, &event, &handler);
+                return;
+            };
+            world
+                .get_resource_or_init::<HtmlEventPropagation>()
+                .dispatches
+                .push(frame);
+            run_html_event_handler(world, &event, &handler);
+            world
+                .resource_mut::<HtmlEventPropagation>()
+                .dispatches
+                .pop();
+        });
+        if control.is_propagation_stopped() {
+            break;
+        }
+    }
+    world
+        .resource_mut::<HtmlEventPropagation>()
+        .dispatches
+        .pop();
+
+    if !control.is_default_prevented() {
+        event.default_action(world);
+    }
+}
+
+/// Runs one handler of a dispatch.
+///
+/// Typed handlers receive the event and untyped handlers `HtmlEvent`, both
+/// with `entity` set to the widget that fired it. The handling ancestor is
+/// available through [`HtmlEventPropagation::current_target`].
+fn run_html_event_handler<E: HtmlBubblingEvent>(
+    world: &mut World,
+    event: &E,
+    handler: &HtmlEventHandler,
+) {
+    let target = event.event_target();
+    let (name, args) = match handler {
+        HtmlEventHandler::Inline(action) => {
+            execute_html_inline_action(world, target, action);
+            return;
+        }
+        HtmlEventHandler::Named { name, args } => (name, args),
+    };
+
+    let Some(registry) = world.get_resource::<HtmlFunctionRegistry>() else {
+        return;
+    };
+    let typed = E::typed_system(registry, name);
+    let untyped = E::system(registry, name);
+    let values = resolve_html_handler_args(world, target, args);
+
+    if let Some(system) = typed {
+        run_html_handler_with_args(world, system, event.clone(), values);
+    } else if let Some(system) = untyped {
+        run_html_handler_with_args(world, system, HtmlEvent { entity: target }, values);
+    } else {
+        warn!(
+            "{} binding '{name}' not registered via #[html_fn(...)]",
+            E::ATTRIBUTE
+        );
+    }
+}
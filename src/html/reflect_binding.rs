@@ -0,0 +1,309 @@
+use bevy::ecs::change_detection::Tick;
+use bevy::prelude::*;
+use bevy::reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy::reflect::{GetPath, PartialReflect, ReflectRef, TypeRegistry};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::de::DeserializeSeed;
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+
+use crate::io::HtmlAsset;
+use crate::lang::UiSharedValues;
+use crate::styles::parser::convert_to_color;
+
+/// Root prefix of template paths that point at a reflected resource.
+pub const REFLECT_RESOURCE_PREFIX: &str = "res:";
+
+static REFLECT_RESOURCE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bres:([A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*)").unwrap()
+});
+
+/// Resource types that templates read through `res:Type` paths.
+///
+/// Only watched resources are mirrored into [`UiSharedValues`]. Types found
+/// in loaded HTML assets are watched automatically.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct UiReflectBindings {
+    types: HashSet<String>,
+    /// Change tick of each resource when it was last mirrored.
+    synced: HashMap<String, Tick>,
+}
+
+impl UiReflectBindings {
+    /// Starts mirroring the resource type `type_name` (short or full type path).
+    pub fn watch(&mut self, type_name: impl Into<String>) -> bool {
+        self.types.insert(type_name.into())
+    }
+
+    /// Returns `true` when `type_name` is mirrored for templates.
+    pub fn is_watched(&self, type_name: &str) -> bool {
+        self.types.contains(type_name)
+    }
+}
+
+/// Returns the type names of all `res:Type` paths used in `source`.
+pub fn collect_reflect_resource_names(source: &str) -> Vec<String> {
+    REFLECT_RESOURCE_RE
+        .captures_iter(source)
+        .filter_map(|caps| caps.get(1).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+/// Watches every `res:Type` used by added or modified HTML assets.
+pub(crate) fn watch_reflect_resources_in_assets(
+    mut bindings: ResMut<UiReflectBindings>,
+    html_assets: Res<Assets<HtmlAsset>>,
+    mut events: MessageReader<AssetEvent<HtmlAsset>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        let Some(asset) = html_assets.get(*id) else {
+            continue;
+        };
+
+        for type_name in collect_reflect_resource_names(&asset.html) {
+            if !bindings.is_watched(&type_name) {
+                bindings.watch(type_name);
+            }
+        }
+    }
+}
+
+/// Mirrors watched reflected resources into [`UiSharedValues`] as `res:Type`.
+///
+/// A resource is only serialized again after it changed since its last sync.
+pub(crate) fn sync_reflect_resource_values(world: &mut World) {
+    let Some(bindings) = world.get_resource::<UiReflectBindings>() else {
+        return;
+    };
+    if bindings.types.is_empty() {
+        return;
+    }
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+
+    let shared = world.resource::<UiSharedValues>();
+    let mut types = bindings.types.iter().cloned().collect::<Vec<_>>();
+    types.sort();
+    let changed = {
+        let registry = registry.read();
+        types
+            .into_iter()
+            .filter_map(|type_name| {
+                let tick = resource_changed_tick(world, &registry, &type_name)?;
+                let key = format!("{REFLECT_RESOURCE_PREFIX}{type_name}");
+                let synced = bindings.synced.get(&type_name) == Some(&tick);
+                (!synced || !shared.values.contains_key(&key)).then_some((type_name, key, tick))
+            })
+            .collect::<Vec<_>>()
+    };
+    if changed.is_empty() {
+        return;
+    }
+
+    let values = changed
+        .into_iter()
+        .map(|(type_name, key, tick)| {
+            let value = read_reflect_resource_json(world, &type_name);
+            (type_name, key, tick, value)
+        })
+        .collect::<Vec<_>>();
+
+    let mut bindings = world.resource_mut::<UiReflectBindings>();
+    for (type_name, _, tick, _) in &values {
+        bindings.synced.insert(type_name.clone(), *tick);
+    }
+    let mut shared = world.resource_mut::<UiSharedValues>();
+    for (_, key, _, value) in values {
+        let Some(value) = value else {
+            continue;
+        };
+        if shared.values.get(&key) != Some(&value) {
+            shared.values.insert(key, value);
+        }
+    }
+}
+
+/// Serializes the reflected resource `type_name` into JSON.
+///
+/// The type must be registered and carry `#[reflect(Resource)]`.
+pub fn read_reflect_resource_json(world: &World, type_name: &str) -> Option<JsonValue> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.clone();
+    let registry = registry.read();
+    let (entity, reflect_component) = resource_entity(world, &registry, type_name)?;
+    let resource = reflect_component.reflect(world.get_entity(entity).ok()?)?;
+    reflect_to_json(resource.as_partial_reflect(), &registry)
+}
+
+/// Serializes a reflected value, exposing `Color` fields as hex strings so
+/// they can be bound to a `<colorpicker>` directly.
+fn reflect_to_json(value: &dyn PartialReflect, registry: &TypeRegistry) -> Option<JsonValue> {
+    if let Some(color) = value.try_downcast_ref::<Color>() {
+        return Some(JsonValue::String(color.to_srgba().to_hex()));
+    }
+
+    if let ReflectRef::Struct(fields) = value.reflect_ref() {
+        let mut map = serde_json::Map::new();
+        for index in 0..fields.field_len() {
+            let (Some(name), Some(field)) = (fields.name_at(index), fields.field_at(index)) else {
+                continue;
+            };
+            map.insert(name.to_string(), reflect_to_json(field, registry)?);
+        }
+        return Some(JsonValue::Object(map));
+    }
+
+    let serializer = TypedReflectSerializer::new(value, registry);
+    serde_json::to_value(&serializer).ok()
+}
+
+/// Reads `res:Type.field.path` from a reflected resource.
+pub fn read_reflect_path(world: &World, path: &str) -> Option<JsonValue> {
+    let (type_name, field_path) = split_reflect_path(path)?;
+    let mut value = read_reflect_resource_json(world, type_name)?;
+    for segment in field_path.split('.').filter(|segment| !segment.is_empty()) {
+        value = match value {
+            JsonValue::Object(mut map) => map.remove(segment)?,
+            JsonValue::Array(mut items) => {
+                let index = segment.parse::<usize>().ok()?;
+                if index >= items.len() {
+                    return None;
+                }
+                items.swap_remove(index)
+            }
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Writes `value` to `res:Type.field.path` of a reflected resource.
+///
+/// The JSON is deserialized into the field's reflected type. Numeric strings
+/// and whole floats are coerced, so widget values can target integer fields;
+/// `Color` fields accept any CSS color string.
+/// Returns `false` when the resource, the field or the conversion is missing.
+pub fn write_reflect_path(world: &mut World, path: &str, value: JsonValue) -> bool {
+    let Some((type_name, field_path)) = split_reflect_path(path) else {
+        return false;
+    };
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return false;
+    };
+    let registry = registry.read();
+    let Some((entity, reflect_component)) = resource_entity(world, &registry, type_name) else {
+        return false;
+    };
+    let Ok(entity) = world.get_entity_mut(entity) else {
+        return false;
+    };
+    let Some(mut resource) = reflect_component.reflect_mut(entity) else {
+        return false;
+    };
+
+    let target = if field_path.is_empty() {
+        resource.as_partial_reflect_mut()
+    } else {
+        match resource.reflect_path_mut(field_path) {
+            Ok(target) => target,
+            Err(_) => return false,
+        }
+    };
+
+    if let Some(color) = target.try_downcast_mut::<Color>() {
+        let Some(parsed) = value
+            .as_str()
+            .and_then(|raw| convert_to_color(raw.to_string()))
+        else {
+            return false;
+        };
+        *color = parsed;
+        return true;
+    }
+
+    let Some(next) = deserialize_for_field(&registry, &*target, value) else {
+        return false;
+    };
+    target.try_apply(next.as_ref()).is_ok()
+}
+
+/// Splits `res:Type.a.b` into `("Type", "a.b")`.
+fn split_reflect_path(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix(REFLECT_RESOURCE_PREFIX)?;
+    let (type_name, field_path) = rest.split_once('.').unwrap_or((rest, ""));
+    if type_name.is_empty() {
+        return None;
+    }
+    Some((type_name, field_path))
+}
+
+/// Returns the entity that stores the reflected resource `type_name`.
+///
+/// Resources live on their own entity, so they are read and written through
+/// the `ReflectComponent` data that `#[reflect(Resource)]` registers.
+fn resource_entity(
+    world: &World,
+    registry: &TypeRegistry,
+    type_name: &str,
+) -> Option<(Entity, ReflectComponent)> {
+    let registration = registry
+        .get_with_short_type_path(type_name)
+        .or_else(|| registry.get_with_type_path(type_name))?;
+    registration.data::<ReflectResource>()?;
+    let reflect_component = registration.data::<ReflectComponent>()?.clone();
+    let component_id = world.components().get_id(registration.type_id())?;
+    let entity = world.resource_entities().get(component_id)?;
+    Some((entity, reflect_component))
+}
+
+/// Returns the tick at which the reflected resource `type_name` last changed.
+fn resource_changed_tick(world: &World, registry: &TypeRegistry, type_name: &str) -> Option<Tick> {
+    let registration = registry
+        .get_with_short_type_path(type_name)
+        .or_else(|| registry.get_with_type_path(type_name))?;
+    let component_id = world.components().get_id(registration.type_id())?;
+    world
+        .get_resource_change_ticks_by_id(component_id)
+        .map(|ticks| ticks.changed)
+}
+
+fn deserialize_for_field(
+    registry: &TypeRegistry,
+    target: &dyn PartialReflect,
+    value: JsonValue,
+) -> Option<Box<dyn PartialReflect>> {
+    let type_id = target.get_represented_type_info()?.type_id();
+    let registration = registry.get(type_id)?;
+    let deserialize = |value: JsonValue| {
+        TypedReflectDeserializer::new(registration, registry)
+            .deserialize(value)
+            .ok()
+    };
+
+    if let Some(reflected) = deserialize(value.clone()) {
+        return Some(reflected);
+    }
+
+    match value {
+        JsonValue::String(raw) => serde_json::from_str::<JsonValue>(raw.trim())
+            .ok()
+            .and_then(&deserialize),
+        JsonValue::Number(number) => {
+            let float = number.as_f64()?;
+            if float.fract() != 0.0 {
+                return None;
+            }
+            let whole = if float < 0.0 {
+                JsonValue::from(float as i64)
+            } else {
+                JsonValue::from(float as u64)
+            };
+            deserialize(whole)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,291 @@
+use bevy::prelude::*;
+use bevy::reflect::enums::VariantInfo;
+use bevy::reflect::structs::StructInfo;
+use bevy::reflect::{NamedField, TypeInfo, TypeRegistry};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::any::TypeId;
+use std::ops::RangeInclusive;
+
+use crate::html::TemplateDiagnostic;
+use crate::html::inline_functions::parse_path;
+
+static REFLECT_FORM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<reflect-form\b([^>]*?)(?:/>|>(.*?)</reflect-form\s*>)").unwrap()
+});
+static ATTRIBUTE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"([A-Za-z_:][-A-Za-z0-9_:.]*)\s*=\s*"([^"]*)""#).unwrap());
+
+/// Replaces every `<reflect-form type="..." bind="...">` with generated widgets.
+///
+/// Fields are read from the registered type's `TypeInfo`:
+/// - `bool` -> `<switch>`
+/// - numbers with a `#[reflect(@0.0..=1.0_f32)]` range -> `<slider>`
+/// - other numbers and `String` -> `<input>`
+/// - unit-only enums -> `<select>`
+/// - `Color` -> `<colorpicker>`
+///
+/// Every control reads `{{ bind.field }}` and writes back with `$set`, so
+/// `bind` may be a `res:Type` path or a `BeuStore` path. Unsupported fields
+/// are skipped. `bind` must be a plain path; `id` and `class` are escaped
+/// before they are written into the generated markup. Forms that cannot be
+/// generated are dropped; [`reflect_form_diagnostics`] reports why.
+pub fn expand_reflect_forms(html: &str, type_registry: &TypeRegistry) -> String {
+    if !html.contains("<reflect-form") {
+        return html.to_string();
+    }
+
+    REFLECT_FORM_RE
+        .replace_all(html, |caps: &Captures| {
+            let attributes = caps.get(1).map_or("", |m| m.as_str());
+            render_reflect_form(attributes, type_registry).unwrap_or_default()
+        })
+        .into_owned()
+}
+
+/// Returns an error diagnostic for every `<reflect-form>` in `source` that
+/// cannot be generated.
+pub fn reflect_form_diagnostics(
+    source: &str,
+    type_registry: &TypeRegistry,
+    source_path: &str,
+) -> Vec<TemplateDiagnostic> {
+    if !source.contains("<reflect-form") {
+        return Vec::new();
+    }
+
+    REFLECT_FORM_RE
+        .captures_iter(source)
+        .filter_map(|caps| {
+            let attributes = caps.get(1).map_or("", |m| m.as_str());
+            let message = resolve_reflect_form(attributes, type_registry).err()?;
+            let offset = caps.get(0).map_or(0, |m| m.start());
+            Some(TemplateDiagnostic::error(
+                source_path,
+                source,
+                offset,
+                message,
+            ))
+        })
+        .collect()
+}
+
+/// Reads a form attribute and decodes HTML entities.
+fn form_attribute(attributes: &str, name: &str) -> Option<String> {
+    ATTRIBUTE_RE
+        .captures_iter(attributes)
+        .find(|caps| &caps[1] == name)
+        .map(|caps| decode_attribute(caps[2].trim()))
+}
+
+/// Resolves the `bind` path and the struct info of a form's `type`.
+fn resolve_reflect_form<'a>(
+    attributes: &str,
+    type_registry: &'a TypeRegistry,
+) -> Result<(String, String, &'a StructInfo), String> {
+    let Some(type_name) = form_attribute(attributes, "type") else {
+        return Err("<reflect-form> requires a 'type' attribute".to_string());
+    };
+    let Some(bind) = form_attribute(attributes, "bind") else {
+        return Err(format!(
+            "<reflect-form type=\"{type_name}\"> requires a 'bind' attribute"
+        ));
+    };
+    if !is_bind_path(&bind) {
+        return Err(format!(
+            "<reflect-form type=\"{type_name}\"> has an invalid 'bind' path '{bind}'"
+        ));
+    }
+    let Some(TypeInfo::Struct(info)) = type_registry
+        .get_with_short_type_path(&type_name)
+        .or_else(|| type_registry.get_with_type_path(&type_name))
+        .map(|registration| registration.type_info())
+    else {
+        return Err(format!(
+            "<reflect-form> type '{type_name}' is not a registered struct"
+        ));
+    };
+    Ok((type_name, bind, info))
+}
+
+fn render_reflect_form(attributes: &str, type_registry: &TypeRegistry) -> Option<String> {
+    let (type_name, bind, info) = resolve_reflect_form(attributes, type_registry).ok()?;
+
+    let mut fields = String::new();
+    for field in info.iter() {
+        let path = format!("{bind}.{}", field.name());
+        match render_field(field, &path, type_registry) {
+            Some(control) => {
+                fields.push_str(&format!(
+                    "<div class=\"reflect-form-field\"><p>{}</p>{control}</div>",
+                    escape_attribute(&field_label(field.name()))
+                ));
+            }
+            None => debug!(
+                "<reflect-form> skipped field '{}' of '{type_name}' ({})",
+                field.name(),
+                field.type_path()
+            ),
+        }
+    }
+
+    let mut classes = String::from("reflect-form");
+    if let Some(extra) = form_attribute(attributes, "class") {
+        classes.push(' ');
+        classes.push_str(&extra);
+    }
+    let id = form_attribute(attributes, "id")
+        .map(|id| format!(" id=\"{}\"", escape_attribute(&id)))
+        .unwrap_or_default();
+
+    Some(format!(
+        "<div{id} class=\"{}\">{fields}</div>",
+        escape_attribute(&classes)
+    ))
+}
+
+fn render_field(field: &NamedField, path: &str, type_registry: &TypeRegistry) -> Option<String> {
+    let value = format!("{{{{ {path} }}}}");
+    let write = |event: &str| format!("onchange=\"$set({path}, $event.{event})\"");
+    let type_id = field.type_id();
+
+    if type_id == TypeId::of::<bool>() {
+        return Some(format!(
+            "<switch value=\"{value}\" {}></switch>",
+            write("checked")
+        ));
+    }
+    if type_id == TypeId::of::<String>() {
+        return Some(format!("<input value=\"{value}\" {}>", write("value")));
+    }
+    if type_id == TypeId::of::<Color>() {
+        return Some(format!(
+            "<colorpicker value=\"{value}\" {}></colorpicker>",
+            write("hex")
+        ));
+    }
+    if let Some((min, max, step)) = numeric_range(field) {
+        return Some(format!(
+            "<slider min=\"{min}\" max=\"{max}\" step=\"{step}\" value=\"{value}\" {}></slider>",
+            write("value")
+        ));
+    }
+    if is_number(type_id) {
+        return Some(format!(
+            "<input type=\"number\" value=\"{value}\" {}>",
+            write("value")
+        ));
+    }
+
+    let Some(TypeInfo::Enum(info)) = type_registry
+        .get(type_id)
+        .map(|registration| registration.type_info())
+    else {
+        return None;
+    };
+    if !info
+        .iter()
+        .all(|variant| matches!(variant, VariantInfo::Unit(_)))
+    {
+        return None;
+    }
+
+    let options = info
+        .variant_names()
+        .iter()
+        .map(|name| {
+            format!(
+                "<option value=\"{}\">{}</option>",
+                escape_attribute(name),
+                escape_attribute(&field_label(name))
+            )
+        })
+        .collect::<String>();
+    Some(format!(
+        "<select value=\"{value}\" {}>{options}</select>",
+        write("value")
+    ))
+}
+
+/// Reads a slider range from a `RangeInclusive` custom attribute.
+fn numeric_range(field: &NamedField) -> Option<(f32, f32, f32)> {
+    let attributes = field.custom_attributes();
+    if let Some(range) = attributes.get::<RangeInclusive<f32>>() {
+        let step = if is_integer(field.type_id()) {
+            1.0
+        } else {
+            (range.end() - range.start()) / 100.0
+        };
+        return Some((*range.start(), *range.end(), step));
+    }
+    if let Some(range) = attributes.get::<RangeInclusive<i32>>() {
+        return Some((*range.start() as f32, *range.end() as f32, 1.0));
+    }
+    None
+}
+
+fn is_integer(type_id: TypeId) -> bool {
+    [
+        TypeId::of::<i8>(),
+        TypeId::of::<i16>(),
+        TypeId::of::<i32>(),
+        TypeId::of::<i64>(),
+        TypeId::of::<isize>(),
+        TypeId::of::<u8>(),
+        TypeId::of::<u16>(),
+        TypeId::of::<u32>(),
+        TypeId::of::<u64>(),
+        TypeId::of::<usize>(),
+    ]
+    .contains(&type_id)
+}
+
+fn is_number(type_id: TypeId) -> bool {
+    is_integer(type_id) || type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>()
+}
+
+/// Accepts dotted `res:Type.field` and `store.key` paths only, so the path
+/// can be interpolated into `{{ }}` and `$set(...)` unchanged.
+fn is_bind_path(bind: &str) -> bool {
+    parse_path(bind).is_some()
+}
+
+fn decode_attribute(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Turns `master_volume` / `HighQuality` into `Master Volume` / `High Quality`.
+fn field_label(name: &str) -> String {
+    let mut label = String::new();
+    let mut previous_lower = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            label.push(' ');
+            previous_lower = false;
+            continue;
+        }
+        if ch.is_uppercase() && previous_lower {
+            label.push(' ');
+        }
+        if label.is_empty() || label.ends_with(' ') {
+            label.extend(ch.to_uppercase());
+        } else {
+            label.push(ch);
+        }
+        previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+    }
+    label
+}
@@ -0,0 +1,289 @@
+use bevy::prelude::*;
+
+use crate::html::inline_functions::{execute_html_inline_action, resolve_html_handler_args};
+use crate::html::{
+    HtmlClick, HtmlEvent, HtmlEventHandler, HtmlFunctionRegistry, HtmlKeyDown, HtmlStructureMap,
+    run_html_handler_with_args,
+};
+use crate::widgets::{Body, InputField, UIWidgetState};
+
+/// Key combination such as `Ctrl+Shift+S`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HtmlKeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub super_key: bool,
+}
+
+impl HtmlKeyChord {
+    /// Parses `+` separated chords like `Ctrl+S`, `Alt+1` or `F5`.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let (mut ctrl, mut shift, mut alt, mut super_key) = (false, false, false, false);
+        let mut key = None;
+        for part in raw.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => ctrl = true,
+                "shift" => shift = true,
+                "alt" | "option" => alt = true,
+                "meta" | "super" | "cmd" | "win" => super_key = true,
+                name => {
+                    if key.is_some() {
+                        return Err(format!("shortcut '{raw}' names more than one key"));
+                    }
+                    key = Some(
+                        parse_key_code(name)
+                            .ok_or_else(|| format!("unknown key '{part}' in shortcut '{raw}'"))?,
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            key: key.ok_or_else(|| format!("shortcut '{raw}' has no key"))?,
+            ctrl,
+            shift,
+            alt,
+            super_key,
+        })
+    }
+
+    /// Returns `true` when `key` was pressed with exactly these modifiers held.
+    pub fn matches(&self, key: KeyCode, keyboard: &ButtonInput<KeyCode>) -> bool {
+        let held = |left, right| keyboard.any_pressed([left, right]);
+        key == self.key
+            && held(KeyCode::ControlLeft, KeyCode::ControlRight) == self.ctrl
+            && held(KeyCode::ShiftLeft, KeyCode::ShiftRight) == self.shift
+            && held(KeyCode::AltLeft, KeyCode::AltRight) == self.alt
+            && held(KeyCode::SuperLeft, KeyCode::SuperRight) == self.super_key
+    }
+
+    /// Returns `true` for chords without `Ctrl`, `Alt` or `Meta`, which would
+    /// collide with typing.
+    fn is_plain(&self) -> bool {
+        !self.ctrl && !self.alt && !self.super_key
+    }
+}
+
+fn parse_key_code(name: &str) -> Option<KeyCode> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA,
+        KeyCode::KeyB,
+        KeyCode::KeyC,
+        KeyCode::KeyD,
+        KeyCode::KeyE,
+        KeyCode::KeyF,
+        KeyCode::KeyG,
+        KeyCode::KeyH,
+        KeyCode::KeyI,
+        KeyCode::KeyJ,
+        KeyCode::KeyK,
+        KeyCode::KeyL,
+        KeyCode::KeyM,
+        KeyCode::KeyN,
+        KeyCode::KeyO,
+        KeyCode::KeyP,
+        KeyCode::KeyQ,
+        KeyCode::KeyR,
+        KeyCode::KeyS,
+        KeyCode::KeyT,
+        KeyCode::KeyU,
+        KeyCode::KeyV,
+        KeyCode::KeyW,
+        KeyCode::KeyX,
+        KeyCode::KeyY,
+        KeyCode::KeyZ,
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Digit0,
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+    const FUNCTION_KEYS: [KeyCode; 12] = [
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+    ];
+
+    if let [byte] = name.as_bytes() {
+        match byte {
+            b'a'..=b'z' => return Some(LETTERS[(byte - b'a') as usize]),
+            b'0'..=b'9' => return Some(DIGITS[(byte - b'0') as usize]),
+            _ => {}
+        }
+    }
+    if let Some(index) = name
+        .strip_prefix('f')
+        .and_then(|index| index.parse::<usize>().ok())
+        .filter(|index| (1..=12).contains(index))
+    {
+        return Some(FUNCTION_KEYS[index - 1]);
+    }
+
+    Some(match name {
+        "esc" | "escape" => KeyCode::Escape,
+        "enter" | "return" => KeyCode::Enter,
+        "space" => KeyCode::Space,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" | "arrowup" => KeyCode::ArrowUp,
+        "down" | "arrowdown" => KeyCode::ArrowDown,
+        "left" | "arrowleft" => KeyCode::ArrowLeft,
+        "right" | "arrowright" => KeyCode::ArrowRight,
+        "-" | "minus" => KeyCode::Minus,
+        "=" | "equal" => KeyCode::Equal,
+        "," | "comma" => KeyCode::Comma,
+        "." | "period" => KeyCode::Period,
+        "/" | "slash" => KeyCode::Slash,
+        _ => return None,
+    })
+}
+
+/// Keyboard shortcut bound to a widget.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtmlShortcut {
+    pub chord: HtmlKeyChord,
+    /// Handler from `<shortcut action="...">`; `None` clicks the widget
+    /// (`hotkey`/`accesskey` attributes).
+    pub handler: Option<HtmlEventHandler>,
+}
+
+/// Component holding the shortcuts of a widget.
+///
+/// Shortcuts fire while the widget is visible and enabled, regardless of
+/// focus. When several active pages declare the same chord, only the page
+/// listed last in [`HtmlStructureMap::active`] runs it.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct HtmlShortcuts(pub Vec<HtmlShortcut>);
+
+/// Runs the shortcuts matching the keys pressed this frame.
+pub(crate) fn emit_html_shortcuts(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    structure_map: Option<Res<HtmlStructureMap>>,
+    q_shortcuts: Query<(
+        Entity,
+        &HtmlShortcuts,
+        Option<&UIWidgetState>,
+        Option<&InheritedVisibility>,
+    )>,
+    q_inputs: Query<&UIWidgetState, With<InputField>>,
+    q_parents: Query<&ChildOf>,
+    q_bodies: Query<&Body>,
+) {
+    let keys: Vec<KeyCode> = keyboard.get_just_pressed().copied().collect();
+    if keys.is_empty() {
+        return;
+    }
+    let typing = q_inputs.iter().any(|state| state.focused);
+    let active = structure_map
+        .as_ref()
+        .and_then(|map| map.active.as_deref())
+        .unwrap_or_default();
+    // Position of the widget's page in the active list; later pages are on top.
+    let page_rank = |entity: Entity| {
+        let key = std::iter::successors(Some(entity), |entity| {
+            q_parents.get(*entity).ok().map(ChildOf::parent)
+        })
+        .find_map(|entity| q_bodies.get(entity).ok()?.html_key.as_deref())?;
+        active.iter().position(|name| name == key)
+    };
+
+    let mut matches = Vec::new();
+    for (entity, shortcuts, state, visibility) in &q_shortcuts {
+        if state.is_some_and(|state| state.disabled)
+            || visibility.is_some_and(|visibility| !visibility.get())
+        {
+            continue;
+        }
+
+        for shortcut in &shortcuts.0 {
+            let Some(&key) = keys
+                .iter()
+                .find(|key| shortcut.chord.matches(**key, &keyboard))
+            else {
+                continue;
+            };
+            if typing && shortcut.chord.is_plain() {
+                continue;
+            }
+            matches.push((entity, key, shortcut, page_rank(entity)));
+        }
+    }
+
+    for &(entity, key, shortcut, rank) in &matches {
+        let shadowed = matches
+            .iter()
+            .any(|(_, _, other, other_rank)| other.chord == shortcut.chord && *other_rank > rank);
+        if shadowed {
+            continue;
+        }
+
+        match shortcut.handler.clone() {
+            None => commands.trigger(HtmlClick {
+                entity,
+                position: Vec2::ZERO,
+                inner_position: Vec2::ZERO,
+                propagation: default(),
+            }),
+            Some(handler) => commands.queue(move |world: &mut World| {
+                run_html_shortcut(world, entity, key, &handler);
+            }),
+        }
+    }
+}
+
+/// Runs a `<shortcut action="...">`; named handlers are looked up like
+/// `onkeydown` handlers.
+fn run_html_shortcut(world: &mut World, entity: Entity, key: KeyCode, handler: &HtmlEventHandler) {
+    let (name, args) = match handler {
+        HtmlEventHandler::Inline(action) => {
+            execute_html_inline_action(world, entity, action);
+            return;
+        }
+        HtmlEventHandler::Named { name, args } => (name, args),
+    };
+
+    let Some(registry) = world.get_resource::<HtmlFunctionRegistry>() else {
+        return;
+    };
+    let typed = registry.keydown_typed.get(name).copied();
+    let untyped = registry.keydown.get(name).copied();
+    let values = resolve_html_handler_args(world, entity, args);
+
+    if let Some(system) = typed {
+        let event = HtmlKeyDown {
+            entity,
+            key,
+            propagation: default(),
+        };
+        run_html_handler_with_args(world, system, event, values);
+    } else if let Some(system) = untyped {
+        run_html_handler_with_args(world, system, HtmlEvent { entity }, values);
+    } else {
+        warn!("shortcut action '{name}' not registered via #[html_fn(...)]");
+    }
+}
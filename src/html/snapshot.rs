@@ -0,0 +1,475 @@
+use bevy::prelude::*;
+use bevy::reflect::ReflectRef;
+use bevy::reflect::structs::Struct;
+use std::fmt::Write;
+
+use crate::html::HtmlEventBindings;
+use crate::html::builder::HtmlNodeKind;
+use crate::html::converter::{BUBBLING_EVENT_ATTRIBUTES, DIRECT_EVENT_ATTRIBUTES};
+use crate::styles::components::UiStyle;
+use crate::styles::{CssClass, CssID};
+use crate::widgets::{
+    Badge, Button, CheckBox, ChoiceBox, ChoiceOption, DatePicker, Form, Headline, HyperLink, Img,
+    InputField, ListBox, Paragraph, ProgressBar, RadioButton, Slider, SwitchButton, ToggleButton,
+    ToolTip, UIWidgetState,
+};
+
+type UiSnapshotWidgets<'a> = (
+    (
+        Option<&'a Form>,
+        Option<&'a Button>,
+        Option<&'a CheckBox>,
+        Option<&'a ChoiceBox>,
+        Option<&'a DatePicker>,
+        Option<&'a Headline>,
+        Option<&'a HyperLink>,
+        Option<&'a Img>,
+        Option<&'a InputField>,
+    ),
+    (
+        Option<&'a ListBox>,
+        Option<&'a Paragraph>,
+        Option<&'a ToolTip>,
+        Option<&'a Badge>,
+        Option<&'a ProgressBar>,
+        Option<&'a RadioButton>,
+        Option<&'a Slider>,
+        Option<&'a SwitchButton>,
+        Option<&'a ToggleButton>,
+    ),
+);
+
+/// Data of a spawned HTML widget read by `UiDom::serialize` and
+/// `UiDom::snapshot`.
+pub(crate) type UiSnapshotNode<'a> = (
+    &'a HtmlNodeKind,
+    Option<&'a CssID>,
+    Option<&'a CssClass>,
+    Option<&'a UIWidgetState>,
+    Option<&'a Visibility>,
+    Option<&'a HtmlEventBindings>,
+    Option<&'a UiStyle>,
+    UiSnapshotWidgets<'a>,
+);
+
+/// Named view of a [`UiSnapshotNode`] query item.
+struct UiSnapshotData<'a> {
+    kind: &'a HtmlNodeKind,
+    id: Option<&'a CssID>,
+    class: Option<&'a CssClass>,
+    state: Option<&'a UIWidgetState>,
+    visibility: Option<&'a Visibility>,
+    bindings: Option<&'a HtmlEventBindings>,
+    style: Option<&'a UiStyle>,
+    form: Option<&'a Form>,
+    button: Option<&'a Button>,
+    checkbox: Option<&'a CheckBox>,
+    choice_box: Option<&'a ChoiceBox>,
+    date_picker: Option<&'a DatePicker>,
+    headline: Option<&'a Headline>,
+    hyper_link: Option<&'a HyperLink>,
+    img: Option<&'a Img>,
+    input: Option<&'a InputField>,
+    list_box: Option<&'a ListBox>,
+    paragraph: Option<&'a Paragraph>,
+    tool_tip: Option<&'a ToolTip>,
+    badge: Option<&'a Badge>,
+    progress_bar: Option<&'a ProgressBar>,
+    radio: Option<&'a RadioButton>,
+    slider: Option<&'a Slider>,
+    switch: Option<&'a SwitchButton>,
+    toggle: Option<&'a ToggleButton>,
+}
+
+impl<'a> From<UiSnapshotNode<'a>> for UiSnapshotData<'a> {
+    fn from(node: UiSnapshotNode<'a>) -> Self {
+        let (kind, id, class, state, visibility, bindings, style, widgets) = node;
+        let (
+            (form, button, checkbox, choice_box, date_picker, headline, hyper_link, img, input),
+            (list_box, paragraph, tool_tip, badge, progress_bar, radio, slider, switch, toggle),
+        ) = widgets;
+        Self {
+            kind,
+            id,
+            class,
+            state,
+            visibility,
+            bindings,
+            style,
+            form,
+            button,
+            checkbox,
+            choice_box,
+            date_picker,
+            headline,
+            hyper_link,
+            img,
+            input,
+            list_box,
+            paragraph,
+            tool_tip,
+            badge,
+            progress_bar,
+            radio,
+            slider,
+            switch,
+            toggle,
+        }
+    }
+}
+
+/// HTML view of one widget.
+struct UiSnapshotElement {
+    tag: &'static str,
+    attributes: Vec<(String, Option<String>)>,
+    text: Option<String>,
+    options: Vec<(ChoiceOption, bool)>,
+}
+
+/// Tags without a closing tag.
+const VOID_TAGS: &[&str] = &["img", "input"];
+
+impl UiSnapshotData<'_> {
+    fn element(&self) -> UiSnapshotElement {
+        let state = self.state.cloned().unwrap_or_default();
+        let mut element = UiSnapshotElement {
+            tag: self.kind.tag_name(),
+            attributes: Vec::new(),
+            text: None,
+            options: Vec::new(),
+        };
+
+        if let Some(id) = self.id.filter(|id| !id.0.is_empty()) {
+            element.attr("id", &id.0);
+        }
+        if let Some(class) = self.class.filter(|class| !class.0.is_empty()) {
+            element.attr("class", class.0.join(" "));
+        }
+
+        if let Some(form) = self.form {
+            element.attr_opt("action", form.action.as_deref());
+        }
+        if let Some(button) = self.button {
+            element.text = Some(button.text.clone());
+        }
+        if let Some(checkbox) = self.checkbox {
+            element.flag("checked", checkbox.checked || state.checked);
+            element.text = Some(checkbox.label.clone());
+        }
+        if let Some(choice) = self.choice_box {
+            element.attr_opt("value", choice.value.value_as_str());
+            element.options = choice
+                .options
+                .iter()
+                .map(|option| (option.clone(), option.text == choice.value.text))
+                .collect();
+        }
+        if let Some(list) = self.list_box {
+            element.flag("multiple", list.multiselect);
+            element.options = list
+                .options
+                .iter()
+                .map(|option| {
+                    let selected = list.values.iter().any(|value| value.text == option.text);
+                    (option.clone(), selected)
+                })
+                .collect();
+        }
+        if let Some(date) = self.date_picker {
+            element.attr_opt("for", date.for_id.as_deref());
+            element.attr_non_empty("name", &date.name);
+            element.attr_non_empty("value", &date.value);
+            element.attr_non_empty("placeholder", &date.placeholder);
+            element.attr_opt("min", date.min.as_deref());
+            element.attr_opt("max", date.max.as_deref());
+            element.text = Some(date.label.clone()).filter(|label| !label.is_empty());
+        }
+        if let Some(headline) = self.headline {
+            element.tag = match headline.h_type.to_string().as_str() {
+                "h2" => "h2",
+                "h3" => "h3",
+                "h4" => "h4",
+                "h5" => "h5",
+                "h6" => "h6",
+                _ => "h1",
+            };
+            element.text = Some(headline.text.clone());
+        }
+        if let Some(link) = self.hyper_link {
+            element.attr("href", &link.href);
+            element.text = Some(link.text.clone());
+        }
+        if let Some(img) = self.img {
+            element.attr_opt("src", img.src.as_deref());
+            element.attr_non_empty("alt", &img.alt);
+        }
+        if let Some(input) = self.input {
+            element.attr("type", format!("{:?}", input.input_type).to_lowercase());
+            element.attr_non_empty("name", &input.name);
+            element.attr_non_empty("value", &input.text);
+            element.attr_non_empty("placeholder", &input.placeholder);
+        }
+        if let Some(paragraph) = self.paragraph {
+            element.text = Some(paragraph.text.clone());
+        }
+        if let Some(tool_tip) = self.tool_tip {
+            element.attr_opt("for", tool_tip.for_id.as_deref());
+            element.text = Some(tool_tip.text.clone());
+        }
+        if let Some(badge) = self.badge {
+            element.attr_opt("for", badge.for_id.as_deref());
+            element.attr("value", badge.value.to_string());
+            element.attr("max", badge.max.to_string());
+        }
+        if let Some(progress) = self.progress_bar {
+            element.attr("min", progress.min.to_string());
+            element.attr("max", progress.max.to_string());
+            element.attr("value", progress.value.to_string());
+        }
+        if let Some(radio) = self.radio {
+            element.attr_opt("value", radio.value_as_str());
+            element.flag("selected", radio.selected);
+            element.text = Some(radio.label.clone());
+        }
+        if let Some(slider) = self.slider {
+            element.attr("min", slider.min.to_string());
+            element.attr("max", slider.max.to_string());
+            element.attr("step", slider.step.to_string());
+            element.attr("value", slider.value.to_string());
+        }
+        if let Some(switch) = self.switch {
+            element.flag("checked", switch.selected);
+            element.text = Some(switch.label.clone());
+        }
+        if let Some(toggle) = self.toggle {
+            element.attr_opt("value", toggle.value.as_str());
+            element.flag("selected", toggle.selected);
+            element.text = Some(toggle.label.clone());
+        }
+
+        element.flag("disabled", state.disabled);
+        element.flag("readonly", state.readonly);
+        element.flag("hidden", self.visibility == Some(&Visibility::Hidden));
+
+        if let Some(bindings) = self.bindings {
+            for (field, _) in BUBBLING_EVENT_ATTRIBUTES
+                .iter()
+                .chain(DIRECT_EVENT_ATTRIBUTES)
+            {
+                // `onfoucs` is the field name; templates spell it `onfocus`.
+                let attribute = if *field == "onfoucs" {
+                    "onfocus"
+                } else {
+                    field
+                };
+                element.attr_opt(attribute, bindings.handler(field));
+            }
+        }
+
+        element
+    }
+
+    /// Returns the selector-like header used by the text snapshot.
+    fn header(&self) -> String {
+        let element = self.element();
+        let mut header = element.tag.to_string();
+        if let Some(id) = self.id.filter(|id| !id.0.is_empty()) {
+            let _ = write!(header, "#{}", id.0);
+        }
+        for class in self.class.iter().flat_map(|class| &class.0) {
+            let _ = write!(header, ".{class}");
+        }
+
+        let state = self.state.cloned().unwrap_or_default();
+        let flags = [
+            ("focused", state.focused),
+            ("hovered", state.hovered),
+            ("disabled", state.disabled),
+            ("readonly", state.readonly),
+            ("checked", state.checked),
+            ("open", state.open),
+            ("invalid", state.invalid),
+            ("drag-over", state.drag_over),
+            ("pressed", state.pressed),
+            ("focus-visible", state.focus_visible),
+            ("hidden", self.visibility == Some(&Visibility::Hidden)),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+        if !flags.is_empty() {
+            let _ = write!(header, " [{}]", flags.join(", "));
+        }
+        header
+    }
+}
+
+impl UiSnapshotElement {
+    fn attr(&mut self, name: &str, value: impl Into<String>) {
+        self.attributes.push((name.to_string(), Some(value.into())));
+    }
+
+    fn attr_opt(&mut self, name: &str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.attr(name, value);
+        }
+    }
+
+    fn attr_non_empty(&mut self, name: &str, value: &str) {
+        if !value.is_empty() {
+            self.attr(name, value);
+        }
+    }
+
+    fn flag(&mut self, name: &str, set: bool) {
+        if set {
+            self.attributes.push((name.to_string(), None));
+        }
+    }
+
+    fn open_tag(&self) -> String {
+        let mut tag = format!("<{}", self.tag);
+        for (name, value) in &self.attributes {
+            match value {
+                Some(value) => {
+                    let _ = write!(tag, " {name}=\"{}\"", escape_html(value, true));
+                }
+                None => {
+                    let _ = write!(tag, " {name}");
+                }
+            }
+        }
+        if VOID_TAGS.contains(&self.tag) {
+            tag.push_str(" />");
+        } else {
+            tag.push('>');
+        }
+        tag
+    }
+}
+
+/// Writes `entity` and its widget descendants as indented HTML.
+pub(crate) fn write_html(
+    out: &mut String,
+    entity: Entity,
+    depth: usize,
+    nodes: &Query<UiSnapshotNode<'static>>,
+    children: &Query<&Children>,
+) {
+    let Ok(node) = nodes.get(entity).map(UiSnapshotData::from) else {
+        for child in widget_children(entity, nodes, children) {
+            write_html(out, child, depth, nodes, children);
+        }
+        return;
+    };
+
+    let element = node.element();
+    let indent = "  ".repeat(depth);
+    let open = element.open_tag();
+    if VOID_TAGS.contains(&element.tag) {
+        let _ = writeln!(out, "{indent}{open}");
+        return;
+    }
+
+    let child_widgets = widget_children(entity, nodes, children);
+    if child_widgets.is_empty() && element.options.is_empty() {
+        let text = element
+            .text
+            .as_deref()
+            .map(|text| escape_html(text, false))
+            .unwrap_or_default();
+        let _ = writeln!(out, "{indent}{open}{text}</{}>", element.tag);
+        return;
+    }
+
+    let _ = writeln!(out, "{indent}{open}");
+    for (option, selected) in &element.options {
+        let value = option.value_as_str().unwrap_or(&option.text);
+        let _ = writeln!(
+            out,
+            "{indent}  <option value=\"{}\"{}>{}</option>",
+            escape_html(value, true),
+            if *selected { " selected" } else { "" },
+            escape_html(&option.text, false)
+        );
+    }
+    for child in child_widgets {
+        write_html(out, child, depth + 1, nodes, children);
+    }
+    let _ = writeln!(out, "{indent}</{}>", element.tag);
+}
+
+/// Writes `entity` and its widget descendants with their computed styles.
+pub(crate) fn write_snapshot(
+    out: &mut String,
+    entity: Entity,
+    depth: usize,
+    nodes: &Query<UiSnapshotNode<'static>>,
+    children: &Query<&Children>,
+) {
+    let Ok(node) = nodes.get(entity).map(UiSnapshotData::from) else {
+        for child in widget_children(entity, nodes, children) {
+            write_snapshot(out, child, depth, nodes, children);
+        }
+        return;
+    };
+
+    let indent = "  ".repeat(depth);
+    let _ = writeln!(out, "{indent}{}", node.header());
+    if let Some(text) = node.element().text {
+        let _ = writeln!(out, "{indent}  text: {text:?}");
+    }
+    if let Some(style) = node.style.and_then(|style| style.active_style.as_ref()) {
+        for index in 0..style.field_len() {
+            let (Some(name), Some(value)) = (style.name_at(index), style.field_at(index)) else {
+                continue;
+            };
+            let ReflectRef::Enum(value) = value.reflect_ref() else {
+                continue;
+            };
+            if let Some(value) = value.field_at(0).filter(|_| value.variant_name() == "Some") {
+                let _ = writeln!(out, "{indent}  {name}: {value:?}");
+            }
+        }
+    }
+    for child in widget_children(entity, nodes, children) {
+        write_snapshot(out, child, depth + 1, nodes, children);
+    }
+}
+
+/// Returns the nearest widget descendants of `entity`, skipping the
+/// internal nodes widgets spawn for themselves.
+fn widget_children(
+    entity: Entity,
+    nodes: &Query<UiSnapshotNode<'static>>,
+    children: &Query<&Children>,
+) -> Vec<Entity> {
+    let mut found = Vec::new();
+    let mut stack = children
+        .get(entity)
+        .map(|children| children.iter().rev().collect::<Vec<_>>())
+        .unwrap_or_default();
+    while let Some(child) = stack.pop() {
+        if nodes.contains(child) {
+            found.push(child);
+        } else if let Ok(grandchildren) = children.get(child) {
+            stack.extend(grandchildren.iter().rev());
+        }
+    }
+    found
+}
+
+fn escape_html(text: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,522 @@
+//! Typed Rust builder for HTML widget trees.
+//!
+//! ```ignore
+//! use bevy_extended_ui::html::ui;
+//!
+//! fn spawn_menu(mut dom: UiDom) {
+//!     dom.spawn(
+//!         ui::body().child(
+//!             ui::div()
+//!                 .class("row")
+//!                 .child(ui::button("Play").on_click(play))
+//!                 .child(ui::button("Quit").on("onclick", "quit_game")),
+//!         ),
+//!     );
+//! }
+//! ```
+
+use bevy::ecs::system::SystemId;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+#[cfg(feature = "extended-dialog")]
+use crate::dialog::DialogWidget;
+use crate::html::converter::HTML_EVENT_ATTRIBUTES;
+use crate::html::{
+    HTML_ID_COUNTER, HtmlChange, HtmlClick, HtmlContextMenu, HtmlDoubleClick, HtmlDrag,
+    HtmlDragEnter, HtmlDragLeave, HtmlDragStart, HtmlDragStop, HtmlDrop, HtmlEventBindings,
+    HtmlFocus, HtmlFunctionRegistry, HtmlID, HtmlInit, HtmlInnerContent, HtmlKeyDown, HtmlKeyUp,
+    HtmlLongPress, HtmlMeta, HtmlMouseDown, HtmlMouseOut, HtmlMouseOver, HtmlMouseUp, HtmlScroll,
+    HtmlStates, HtmlStyle, HtmlTouchEnd, HtmlTouchMove, HtmlTouchStart, HtmlWheel, HtmlWidgetNode,
+};
+use crate::io::CssAsset;
+use crate::styles::Style;
+use crate::styles::parser::apply_property_to_style;
+use crate::widgets::{
+    Badge, Body, Button, CheckBox, ChoiceBox, ColorPicker, DatePicker, Div, Divider, FieldSet,
+    Form, Headline, HeadlineType, HyperLink, Img, InputField, ListBox, Paragraph, ProgressBar,
+    RadioButton, Scrollbar, Slider, SwitchButton, ToggleButton, ToolTip, Widget,
+};
+
+type UiHandlerRegistration = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Widget node built in Rust, spawned like a parsed HTML element.
+pub struct UiNode {
+    node: HtmlWidgetNode,
+    children: Vec<UiNode>,
+    css: Vec<Handle<CssAsset>>,
+    handlers: Vec<UiHandlerRegistration>,
+}
+
+/// Widget components that can become a [`UiNode`], e.g. a configured `Slider`.
+pub trait UiWidget {
+    /// Wraps the component into a widget node without attributes.
+    fn into_html_node(self) -> HtmlWidgetNode;
+}
+
+macro_rules! ui_widgets {
+    (
+        leaf: $($leaf:ident => $leaf_variant:ident),* ;
+        container: $($container:ident => $container_variant:ident),* $(;)?
+    ) => {
+        $(
+            impl UiWidget for $leaf {
+                fn into_html_node(self) -> HtmlWidgetNode {
+                    HtmlWidgetNode::$leaf_variant(
+                        self,
+                        HtmlMeta::default(),
+                        HtmlStates::default(),
+                        HtmlEventBindings::default(),
+                        Widget::default(),
+                        HtmlID::default(),
+                    )
+                }
+            }
+        )*
+        $(
+            impl UiWidget for $container {
+                fn into_html_node(self) -> HtmlWidgetNode {
+                    HtmlWidgetNode::$container_variant(
+                        self,
+                        HtmlMeta::default(),
+                        HtmlStates::default(),
+                        Vec::new(),
+                        HtmlEventBindings::default(),
+                        Widget::default(),
+                        HtmlID::default(),
+                    )
+                }
+            }
+        )*
+
+        /// Returns the attribute parts and the children of `node`.
+        fn node_parts(
+            node: &mut HtmlWidgetNode,
+        ) -> (
+            &mut HtmlMeta,
+            &mut HtmlStates,
+            &mut HtmlEventBindings,
+            &mut Widget,
+            Option<&mut Vec<HtmlWidgetNode>>,
+        ) {
+            match node {
+                $(
+                    HtmlWidgetNode::$leaf_variant(_, meta, states, bindings, widget, _) => {
+                        (meta, states, bindings, widget, None)
+                    }
+                )*
+                $(
+                    HtmlWidgetNode::$container_variant(_, meta, states, children, bindings, widget, _) => {
+                        (meta, states, bindings, widget, Some(children))
+                    }
+                )*
+                #[cfg(feature = "extended-dialog")]
+                HtmlWidgetNode::Dialog(_, meta, states, children, bindings, widget, _) => {
+                    (meta, states, bindings, widget, Some(children))
+                }
+            }
+        }
+    };
+}
+
+ui_widgets! {
+    leaf: Badge => Badge, Button => Button, CheckBox => CheckBox, ChoiceBox => ChoiceBox,
+        ColorPicker => ColorPicker, DatePicker => DatePicker, Divider => Divider,
+        Headline => Headline, HyperLink => HyperLink, Img => Img, InputField => Input,
+        ListBox => ListBox, Paragraph => Paragraph, ProgressBar => ProgressBar,
+        RadioButton => RadioButton, Scrollbar => Scrollbar, Slider => Slider,
+        SwitchButton => SwitchButton, ToggleButton => ToggleButton, ToolTip => ToolTip;
+    container: Body => Body, Div => Div, FieldSet => FieldSet, Form => Form;
+}
+
+#[cfg(feature = "extended-dialog")]
+impl UiWidget for DialogWidget {
+    fn into_html_node(self) -> HtmlWidgetNode {
+        HtmlWidgetNode::Dialog(
+            self,
+            HtmlMeta::default(),
+            HtmlStates::default(),
+            Vec::new(),
+            HtmlEventBindings::default(),
+            Widget::default(),
+            HtmlID::default(),
+        )
+    }
+}
+
+macro_rules! ui_event_methods {
+    ($($method:ident => $attribute:literal, $registry:ident, $event:ty;)*) => {$(
+        #[doc = concat!("Runs the `", stringify!($event), "` system `handler` for `", $attribute, "`.")]
+        pub fn $method<M>(self, handler: impl IntoSystem<In<$event>, (), M> + 'static) -> Self {
+            self.on_system($attribute, handler, |registry| &mut registry.$registry)
+        }
+    )*};
+}
+
+impl UiNode {
+    /// Creates a node from any widget component.
+    pub fn new(widget: impl UiWidget) -> Self {
+        Self {
+            node: widget.into_html_node(),
+            children: Vec::new(),
+            css: Vec::new(),
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Sets the `id` attribute.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.meta().id = Some(id.into());
+        self
+    }
+
+    /// Adds one or more space separated classes.
+    pub fn class(mut self, class: &str) -> Self {
+        let classes = self.meta().class.get_or_insert_with(Vec::new);
+        for class in class.split_whitespace() {
+            if !classes.iter().any(|existing| existing == class) {
+                classes.push(class.to_string());
+            }
+        }
+        self
+    }
+
+    /// Adds inline declarations like `style="width: 50%; color: red"`.
+    pub fn style(mut self, declarations: &str) -> Self {
+        let style = self
+            .meta()
+            .style
+            .get_or_insert_with(|| HtmlStyle(Style::default()));
+        for part in declarations.split(';') {
+            if let Some((name, value)) = part.split_once(':') {
+                apply_property_to_style(&mut style.0, name.trim(), value.trim());
+            }
+        }
+        self
+    }
+
+    /// Adds a stylesheet; children inherit it.
+    pub fn css(mut self, css: Handle<CssAsset>) -> Self {
+        self.css.push(css);
+        self
+    }
+
+    /// Sets the controller used to resolve named handlers; children inherit it.
+    pub fn controller(mut self, controller: impl Into<String>) -> Self {
+        *node_parts(&mut self.node).3 = Widget(Some(controller.into()));
+        self
+    }
+
+    /// Marks the widget as `disabled`.
+    pub fn disabled(mut self) -> Self {
+        node_parts(&mut self.node).1.disabled = true;
+        self
+    }
+
+    /// Marks the widget as `readonly`.
+    pub fn readonly(mut self) -> Self {
+        node_parts(&mut self.node).1.readonly = true;
+        self
+    }
+
+    /// Marks the widget as `hidden`.
+    pub fn hidden(mut self) -> Self {
+        node_parts(&mut self.node).1.hidden = true;
+        self
+    }
+
+    /// Appends a child; ignored for widgets without children.
+    pub fn child(mut self, child: UiNode) -> Self {
+        if node_parts(&mut self.node).4.is_none() {
+            warn!("ui: widget cannot have children, child ignored");
+            return self;
+        }
+        self.children.push(child);
+        self
+    }
+
+    /// Appends several children.
+    pub fn children(self, children: impl IntoIterator<Item = UiNode>) -> Self {
+        children.into_iter().fold(self, Self::child)
+    }
+
+    /// Binds a `#[html_fn]` handler by name, like `onclick="name"`.
+    pub fn on(mut self, attribute: &str, handler: impl Into<String>) -> Self {
+        if !node_parts(&mut self.node).2.set_handler(attribute, handler) {
+            warn!("ui: unknown event attribute '{attribute}'");
+        }
+        self
+    }
+
+    ui_event_methods! {
+        on_click => "onclick", click_typed, HtmlClick;
+        on_mouse_down => "onmousedown", mousedown_typed, HtmlMouseDown;
+        on_mouse_up => "onmouseup", mouseup_typed, HtmlMouseUp;
+        on_mouse_over => "onmouseover", over_typed, HtmlMouseOver;
+        on_mouse_out => "onmouseout", out_typed, HtmlMouseOut;
+        on_change => "onchange", change_typed, HtmlChange;
+        on_init => "oninit", init_typed, HtmlInit;
+        on_focus => "onfoucs", focus_typed, HtmlFocus;
+        on_scroll => "onscroll", scroll_typed, HtmlScroll;
+        on_wheel => "onwheel", wheel_typed, HtmlWheel;
+        on_key_down => "onkeydown", keydown_typed, HtmlKeyDown;
+        on_key_up => "onkeyup", keyup_typed, HtmlKeyUp;
+        on_drag_start => "ondragstart", dragstart_typed, HtmlDragStart;
+        on_drag => "ondrag", drag_typed, HtmlDrag;
+        on_drag_stop => "ondragstop", dragstop_typed, HtmlDragStop;
+        on_touch_start => "ontouchstart", touchstart_typed, HtmlTouchStart;
+        on_touch_move => "ontouchmove", touchmove_typed, HtmlTouchMove;
+        on_touch_end => "ontouchend", touchend_typed, HtmlTouchEnd;
+        on_double_click => "ondblclick", dblclick_typed, HtmlDoubleClick;
+        on_context_menu => "oncontextmenu", contextmenu_typed, HtmlContextMenu;
+        on_long_press => "onlongpress", longpress_typed, HtmlLongPress;
+        on_drag_enter => "ondragenter", dragenter_typed, HtmlDragEnter;
+        on_drag_leave => "ondragleave", dragleave_typed, HtmlDragLeave;
+        on_drop => "ondrop", drop_typed, HtmlDrop;
+    }
+
+    /// Builds the `HtmlWidgetNode` tree and queues the registration of its
+    /// handler systems.
+    pub fn into_widget_node(self, commands: &mut Commands) -> HtmlWidgetNode {
+        self.build(commands, &[], &Widget::default())
+    }
+
+    pub(crate) fn build(
+        mut self,
+        commands: &mut Commands,
+        inherited_css: &[Handle<CssAsset>],
+        inherited_widget: &Widget,
+    ) -> HtmlWidgetNode {
+        for register in self.handlers.drain(..) {
+            commands.queue(register);
+        }
+
+        let (meta, _, _, widget, children) = node_parts(&mut self.node);
+        meta.css = inherited_css.to_vec();
+        for css in self.css {
+            if !meta.css.contains(&css) {
+                meta.css.push(css);
+            }
+        }
+        if widget.0.is_none() {
+            *widget = inherited_widget.clone();
+        }
+        let (css, widget) = (meta.css.clone(), widget.clone());
+        if let Some(children) = children {
+            children.extend(
+                self.children
+                    .into_iter()
+                    .map(|child| child.build(commands, &css, &widget)),
+            );
+        }
+        self.node
+    }
+
+    fn meta(&mut self) -> &mut HtmlMeta {
+        node_parts(&mut self.node).0
+    }
+
+    fn with_text(mut self, text: &str) -> Self {
+        self.meta().inner_content = HtmlInnerContent::new(text, text, Vec::new());
+        self
+    }
+
+    fn on_system<E: 'static, M>(
+        mut self,
+        attribute: &'static str,
+        handler: impl IntoSystem<In<E>, (), M> + 'static,
+        registry: fn(&mut HtmlFunctionRegistry) -> &mut HashMap<String, SystemId<In<E>>>,
+    ) -> Self
+    where
+        In<E>: SystemInput,
+    {
+        let name = format!(
+            "__ui_{attribute}_{}",
+            HTML_ID_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        node_parts(&mut self.node)
+            .2
+            .set_handler(attribute, name.clone());
+
+        let system = IntoSystem::into_system(handler);
+        self.handlers.push(Box::new(move |world: &mut World| {
+            let id = world.register_system(system);
+            let mut functions = world.get_resource_or_init::<HtmlFunctionRegistry>();
+            registry(&mut functions).insert(name.clone(), id);
+
+            let unregister = {
+                let name = name.clone();
+                move |world: &mut World| {
+                    if let Some(mut functions) = world.get_resource_mut::<HtmlFunctionRegistry>() {
+                        registry(&mut functions).remove(&name);
+                    }
+                    let _ = world.unregister_system(id);
+                }
+            };
+            world
+                .get_resource_or_init::<UiHandlerSystems>()
+                .0
+                .insert(name, Box::new(unregister));
+        }));
+        self
+    }
+}
+
+/// Unregisters the `on_*` handler systems of [`UiNode`] widgets, keyed by
+/// their generated `__ui_*` handler name.
+#[derive(Resource, Default)]
+pub(crate) struct UiHandlerSystems(HashMap<String, UiHandlerRegistration>);
+
+/// Unregisters the handler systems of a despawned builder widget once no
+/// other widget is bound to them.
+pub(crate) fn unregister_ui_handlers(
+    ev: On<Remove, HtmlEventBindings>,
+    q_bindings: Query<(Entity, &HtmlEventBindings)>,
+    mut commands: Commands,
+) {
+    let Ok((_, bindings)) = q_bindings.get(ev.entity) else {
+        return;
+    };
+    let names = HTML_EVENT_ATTRIBUTES
+        .iter()
+        .filter_map(|attribute| bindings.handler(attribute))
+        .filter(|name| name.starts_with("__ui_"))
+        .filter(|name| {
+            !q_bindings.iter().any(|(entity, other)| {
+                entity != ev.entity
+                    && HTML_EVENT_ATTRIBUTES
+                        .iter()
+                        .any(|attribute| other.handler(attribute) == Some(*name))
+            })
+        })
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return;
+    }
+
+    commands.queue(move |world: &mut World| {
+        for name in names {
+            let unregister = world
+                .get_resource_mut::<UiHandlerSystems>()
+                .and_then(|mut systems| systems.0.remove(&name));
+            if let Some(unregister) = unregister {
+                unregister(world);
+            }
+        }
+    });
+}
+
+/// `<body>`.
+pub fn body() -> UiNode {
+    UiNode::new(Body::default())
+}
+
+/// `<div>`.
+pub fn div() -> UiNode {
+    UiNode::new(Div::default())
+}
+
+/// `<form>`.
+pub fn form() -> UiNode {
+    UiNode::new(Form::default())
+}
+
+/// `<fieldset>`.
+pub fn fieldset() -> UiNode {
+    UiNode::new(FieldSet::default())
+}
+
+/// `<button>text</button>`.
+pub fn button(text: &str) -> UiNode {
+    UiNode::new(Button {
+        text: text.to_string(),
+        ..default()
+    })
+    .with_text(text)
+}
+
+/// `<p>text</p>`.
+pub fn p(text: &str) -> UiNode {
+    UiNode::new(Paragraph {
+        text: text.to_string(),
+        ..default()
+    })
+    .with_text(text)
+}
+
+/// `<h1>` to `<h6>`.
+pub fn headline(h_type: HeadlineType, text: &str) -> UiNode {
+    UiNode::new(Headline {
+        text: text.to_string(),
+        h_type,
+        ..default()
+    })
+    .with_text(text)
+}
+
+/// `<a href="...">text</a>`.
+pub fn a(text: &str, href: &str) -> UiNode {
+    UiNode::new(HyperLink {
+        text: text.to_string(),
+        href: href.to_string(),
+        ..default()
+    })
+    .with_text(text)
+}
+
+/// `<img src="...">`.
+pub fn img(src: &str) -> UiNode {
+    UiNode::new(Img {
+        src: Some(src.to_string()),
+        ..default()
+    })
+}
+
+/// `<input name="..." placeholder="...">`.
+pub fn input(name: &str, placeholder: &str) -> UiNode {
+    UiNode::new(InputField {
+        name: name.to_string(),
+        placeholder: placeholder.to_string(),
+        ..default()
+    })
+}
+
+/// `<checkbox>label</checkbox>`.
+pub fn checkbox(label: &str) -> UiNode {
+    UiNode::new(CheckBox {
+        label: label.to_string(),
+        ..default()
+    })
+    .with_text(label)
+}
+
+/// `<slider min="..." max="...">`.
+pub fn slider(min: f32, max: f32) -> UiNode {
+    UiNode::new(Slider {
+        min,
+        max,
+        value: min,
+        ..default()
+    })
+}
+
+/// `<switch>label</switch>`.
+pub fn switch(label: &str) -> UiNode {
+    UiNode::new(SwitchButton {
+        label: label.to_string(),
+        ..default()
+    })
+    .with_text(label)
+}
+
+/// `<divider>`.
+pub fn divider() -> UiNode {
+    UiNode::new(Divider::default())
+}
+
+/// Any other widget, e.g. `ui::widget(ProgressBar { value: 40.0, ..default() })`.
+pub fn widget(widget: impl UiWidget) -> UiNode {
+    UiNode::new(widget)
+}
@@ -39,7 +39,7 @@ pub mod styles;
 pub mod utils;
 pub mod widgets;
 
-pub use bevy_extended_ui_macros::BeuStore;
+pub use bevy_extended_ui_macros::{BeuStore, beu_computed};
 pub use lang::{UILang, UiLangVariables, UiSharedValues};
 
 /// A cache mapping image paths to their loaded handles,
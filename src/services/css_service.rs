@@ -6,12 +6,16 @@ use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::sync::RwLock;
 
+use crate::html::HtmlAttributes;
+use crate::html::inline_functions::split_top_level;
 use crate::io::CssAsset;
+use crate::services::style_service::pseudo_class_matches_state;
 use crate::styles::components::UiStyle;
 use crate::styles::parser::{collect_root_css_vars, load_css, load_css_with_root_vars};
 use crate::styles::{
     AnimationKeyframe, CssClass, CssID, CssSource, ExistingCssIDs, ParsedCss, StylePair, TagName,
 };
+use crate::widgets::UIWidgetState;
 
 // Marks entities as needing CSS re-apply on hot reload
 use crate::html::reload::CssDirty;
@@ -22,6 +26,15 @@ static ROOT_CSS_VARS_CACHE: Lazy<RwLock<HashMap<AssetId<CssAsset>, HashMap<Strin
     Lazy::new(|| RwLock::new(HashMap::new()));
 static PARSED_CSS_WITH_VARS_CACHE: Lazy<RwLock<HashMap<ParsedCssWithVarsKey, ParsedCss>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
+static SELECTOR_FEATURES_CACHE: Lazy<RwLock<HashMap<AssetId<CssAsset>, u8>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Pseudo-classes left of a `+` or `~` combinator, like `input:checked ~ .panel`.
+const SELECTORS_USE_SIBLING_STATE: u8 = 1 << 0;
+/// Structural pseudo-classes or sibling combinators, like `li:nth-child(odd)`.
+const SELECTORS_USE_STRUCTURE: u8 = 1 << 1;
+/// `:has(...)`, whose match depends on descendants.
+const SELECTORS_USE_HAS: u8 = 1 << 2;
 
 /// Represents the `ParsedCssWithVarsKey` data structure used by the extended UI system.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -51,23 +64,25 @@ type CssApplyTrigger = Or<(
     Changed<CssClass>,
     Changed<CssID>,
     Changed<TagName>,
+    Changed<HtmlAttributes>,
     Changed<ChildOf>,
 )>;
 
-type CssSourceEntry<'a> = (
-    Entity,
-    &'a CssSource,
-    Option<&'a CssID>,
-    Option<&'a CssClass>,
-    Option<&'a TagName>,
-    Option<&'a ChildOf>,
-    Option<&'a CssDirty>,
-);
+type HasDependencyTrigger = Or<(
+    Changed<UIWidgetState>,
+    Changed<CssClass>,
+    Changed<HtmlAttributes>,
+)>;
 
-type CssParentSelectorEntry<'a> = (
+type CssSourceEntry<'a> = (Entity, &'a CssSource, Option<&'a CssDirty>);
+
+pub(crate) type CssParentSelectorEntry<'a> = (
     Option<&'a CssID>,
     Option<&'a CssClass>,
     Option<&'a TagName>,
+    Option<&'a HtmlAttributes>,
+    Option<&'a UIWidgetState>,
+    Option<&'a Children>,
     Option<&'a ChildOf>,
 );
 
@@ -97,6 +112,9 @@ impl Plugin for CssService {
             (
                 invalidate_css_cache_on_asset_change,
                 update_css_users_index,
+                mark_sibling_state_dependents_dirty,
+                mark_structural_dependents_dirty,
+                mark_has_dependents_dirty,
                 apply_css_to_entities_legacy,
             )
                 .chain(),
@@ -108,6 +126,9 @@ impl Plugin for CssService {
                 invalidate_css_cache_on_asset_change,
                 update_css_users_index,
                 mark_css_users_dirty_on_viewport_change,
+                mark_sibling_state_dependents_dirty,
+                mark_structural_dependents_dirty,
+                mark_has_dependents_dirty,
                 apply_css_to_entities,
             )
                 .chain(),
@@ -215,6 +236,9 @@ fn invalidate_css_cache_on_asset_change(mut ev: MessageReader<AssetEvent<CssAsse
         if let Ok(mut cache) = PARSED_CSS_WITH_VARS_CACHE.write() {
             cache.retain(|key, _| key.asset_id != asset_id);
         }
+        if let Ok(mut cache) = SELECTOR_FEATURES_CACHE.write() {
+            cache.remove(&asset_id);
+        }
     }
 }
 
@@ -426,6 +450,204 @@ fn mark_css_users_dirty_on_viewport_change(
     }
 }
 
+/// Marks later siblings dirty when a widget state used by `+`/`~` selectors changes.
+///
+/// Covers patterns like `input:checked ~ .panel`, whose match depends on the
+/// state of another element.
+fn mark_sibling_state_dependents_dirty(
+    mut commands: Commands,
+    css_assets: Res<Assets<CssAsset>>,
+    changed_states: Query<(Entity, &ChildOf), Changed<UIWidgetState>>,
+    children_query: Query<&Children>,
+    source_query: Query<&CssSource>,
+) {
+    let mut dirty = HashSet::new();
+
+    for (entity, parent) in changed_states.iter() {
+        let Ok(siblings) = children_query.get(parent.parent()) else {
+            continue;
+        };
+        let Some(position) = siblings.iter().position(|sibling| sibling == entity) else {
+            continue;
+        };
+
+        for sibling in siblings.iter().skip(position + 1) {
+            if !uses_selector_features(
+                sibling,
+                SELECTORS_USE_SIBLING_STATE,
+                &source_query,
+                &css_assets,
+            ) {
+                continue;
+            }
+
+            dirty.insert(sibling);
+            dirty.extend(children_query.iter_descendants(sibling));
+        }
+    }
+
+    mark_entities_css_dirty(&mut commands, dirty, &source_query);
+}
+
+/// Marks elements dirty when children are added, removed or reordered.
+///
+/// Structural selectors like `:nth-child`, `:empty`, `+` and `:has()` depend
+/// on the surrounding tree and must be re-evaluated then.
+fn mark_structural_dependents_dirty(
+    mut commands: Commands,
+    css_assets: Res<Assets<CssAsset>>,
+    changed_children: Query<Entity, Changed<Children>>,
+    mut removed_children: RemovedComponents<Children>,
+    children_query: Query<&Children>,
+    parent_query: Query<&ChildOf>,
+    source_query: Query<&CssSource>,
+) {
+    let mut parents: Vec<Entity> = changed_children.iter().collect();
+    parents.extend(removed_children.read());
+    if parents.is_empty() {
+        return;
+    }
+
+    let uses = |entity: Entity, feature: u8| {
+        uses_selector_features(entity, feature, &source_query, &css_assets)
+    };
+    let mut dirty = HashSet::new();
+
+    for parent in parents {
+        if uses(parent, SELECTORS_USE_STRUCTURE) {
+            dirty.insert(parent);
+        }
+
+        if let Ok(children) = children_query.get(parent) {
+            for child in children.iter() {
+                if uses(child, SELECTORS_USE_STRUCTURE) {
+                    dirty.insert(child);
+                    dirty.extend(children_query.iter_descendants(child));
+                }
+            }
+        }
+
+        for ancestor in std::iter::once(parent).chain(parent_query.iter_ancestors(parent)) {
+            if uses(ancestor, SELECTORS_USE_HAS) {
+                dirty.insert(ancestor);
+            }
+        }
+    }
+
+    mark_entities_css_dirty(&mut commands, dirty, &source_query);
+}
+
+/// Marks `:has()` subjects dirty when the state, classes or attributes of an
+/// element they look at change, like `.card:has(input:checked)`.
+fn mark_has_dependents_dirty(
+    mut commands: Commands,
+    css_assets: Res<Assets<CssAsset>>,
+    changed_elements: Query<Entity, HasDependencyTrigger>,
+    children_query: Query<&Children>,
+    parent_query: Query<&ChildOf>,
+    source_query: Query<&CssSource>,
+) {
+    let mut dirty = HashSet::new();
+
+    for entity in changed_elements.iter() {
+        let Ok(parent) = parent_query.get(entity) else {
+            continue;
+        };
+        let siblings = children_query
+            .get(parent.parent())
+            .into_iter()
+            .flatten()
+            .copied();
+        for relative in siblings.chain(parent_query.iter_ancestors(entity)) {
+            if relative != entity
+                && uses_selector_features(relative, SELECTORS_USE_HAS, &source_query, &css_assets)
+            {
+                dirty.insert(relative);
+            }
+        }
+    }
+
+    mark_entities_css_dirty(&mut commands, dirty, &source_query);
+}
+
+/// Inserts `CssDirty` on every styled entity in `entities`.
+fn mark_entities_css_dirty(
+    commands: &mut Commands,
+    entities: HashSet<Entity>,
+    source_query: &Query<&CssSource>,
+) {
+    for entity in entities {
+        if source_query.contains(entity)
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.insert(CssDirty);
+        }
+    }
+}
+
+/// Returns `true` when a stylesheet of `entity` uses any selector `feature`.
+fn uses_selector_features(
+    entity: Entity,
+    feature: u8,
+    source_query: &Query<&CssSource>,
+    css_assets: &Assets<CssAsset>,
+) -> bool {
+    source_query.get(entity).is_ok_and(|source| {
+        source
+            .0
+            .iter()
+            .any(|handle| selector_features(handle.id(), css_assets) & feature != 0)
+    })
+}
+
+/// Returns the `SELECTORS_USE_*` features of a stylesheet.
+fn selector_features(asset_id: AssetId<CssAsset>, css_assets: &Assets<CssAsset>) -> u8 {
+    if let Some(cached) = read_cached(&SELECTOR_FEATURES_CACHE, &asset_id) {
+        return cached;
+    }
+
+    let Some(parsed) = get_or_parse_css_by_id(asset_id, css_assets) else {
+        return 0;
+    };
+    let features = parsed.styles.iter().fold(0, |features, (key, style)| {
+        let selector = if style.selector.is_empty() {
+            key.as_str()
+        } else {
+            style.selector.as_str()
+        };
+        features | selector_features_of(selector)
+    });
+
+    if let Ok(mut cache) = SELECTOR_FEATURES_CACHE.write() {
+        cache.insert(asset_id, features);
+    }
+    features
+}
+
+/// Returns the `SELECTORS_USE_*` features of one selector.
+fn selector_features_of(selector: &str) -> u8 {
+    const STRUCTURAL_PSEUDO_CLASSES: [&str; 5] = [":first-", ":last-", ":only-", ":nth-", ":empty"];
+
+    let mut features = 0;
+    let tokens = split_selector_tokens(selector);
+    if let Some(last) = tokens.iter().rposition(|token| matches!(*token, "+" | "~")) {
+        features |= SELECTORS_USE_STRUCTURE;
+        if tokens[..last].iter().any(|token| token.contains(':')) {
+            features |= SELECTORS_USE_SIBLING_STATE;
+        }
+    }
+    if STRUCTURAL_PSEUDO_CLASSES
+        .iter()
+        .any(|pseudo| selector.contains(pseudo))
+    {
+        features |= SELECTORS_USE_STRUCTURE;
+    }
+    if selector.contains(":has(") {
+        features |= SELECTORS_USE_HAS;
+    }
+    features
+}
+
 /// Returns the viewport used for media-query breakpoints.
 ///
 /// Feature behavior:
@@ -526,19 +748,14 @@ fn apply_css_to_entities(
     let viewport = resolve_breakpoint_viewport(&window_query).unwrap_or(Vec2::ZERO);
 
     for entity in dirty {
-        let Ok((_, css_source, id, class, tag, parent, dirty_marker)) =
-            query_all_source.get(entity)
-        else {
+        let Ok((_, css_source, dirty_marker)) = query_all_source.get(entity) else {
             continue;
         };
 
         let merged_css = load_and_merge_styles_from_assets(
+            entity,
             &css_source.0,
             &css_assets,
-            id,
-            class,
-            tag,
-            parent,
             &parent_query,
             viewport,
         );
@@ -583,19 +800,14 @@ fn apply_css_to_entities_legacy(
     }
 
     for entity in dirty {
-        let Ok((_, css_source, id, class, tag, parent, dirty_marker)) =
-            query_all_source.get(entity)
-        else {
+        let Ok((_, css_source, dirty_marker)) = query_all_source.get(entity) else {
             continue;
         };
 
         let merged_css = load_and_merge_styles_from_assets_legacy(
+            entity,
             &css_source.0,
             &css_assets,
-            id,
-            class,
-            tag,
-            parent,
             &parent_query,
         );
 
@@ -640,18 +852,10 @@ fn merge_style_for_selector(
 
 /// Handles `load_and_merge_styles_from_assets_common` in the extended UI workflow.
 fn load_and_merge_styles_from_assets_common(
+    entity: Entity,
     sources: &[Handle<CssAsset>],
     css_assets: &Assets<CssAsset>,
-    id: Option<&CssID>,
-    class: Option<&CssClass>,
-    tag: Option<&TagName>,
-    parent: Option<&ChildOf>,
-    parent_query: &Query<(
-        Option<&CssID>,
-        Option<&CssClass>,
-        Option<&TagName>,
-        Option<&ChildOf>,
-    )>,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
     viewport: Option<Vec2>,
 ) -> ParsedCss {
     let mut merged_styles: HashMap<String, StylePair> = HashMap::new();
@@ -681,7 +885,7 @@ fn load_and_merge_styles_from_assets_common(
             };
             let selector_parts = parse_selector_steps(selector);
 
-            if matches_selector_chain(&selector_parts, id, class, tag, parent, parent_query) {
+            if matches_selector_chain(&selector_parts, entity, parent_query) {
                 merge_style_for_selector(&mut merged_styles, selector_key, new_style, index);
             }
         }
@@ -699,22 +903,16 @@ fn load_and_merge_styles_from_assets_common(
 
 /// Loads and merges CSS styles from multiple sources with selector matching.
 fn load_and_merge_styles_from_assets(
+    entity: Entity,
     sources: &[Handle<CssAsset>],
     css_assets: &Assets<CssAsset>,
-    id: Option<&CssID>,
-    class: Option<&CssClass>,
-    tag: Option<&TagName>,
-    parent: Option<&ChildOf>,
     parent_query: &Query<CssParentSelectorEntry<'_>>,
     viewport: Vec2,
 ) -> ParsedCss {
     load_and_merge_styles_from_assets_common(
+        entity,
         sources,
         css_assets,
-        id,
-        class,
-        tag,
-        parent,
         parent_query,
         Some(viewport),
     )
@@ -723,110 +921,422 @@ fn load_and_merge_styles_from_assets(
 /// Handles `load_and_merge_styles_from_assets_legacy` in the extended UI workflow.
 #[cfg(all(feature = "wasm-default", target_arch = "wasm32"))]
 fn load_and_merge_styles_from_assets_legacy(
+    entity: Entity,
     sources: &[Handle<CssAsset>],
     css_assets: &Assets<CssAsset>,
-    id: Option<&CssID>,
-    class: Option<&CssClass>,
-    tag: Option<&TagName>,
-    parent: Option<&ChildOf>,
     parent_query: &Query<CssParentSelectorEntry<'_>>,
 ) -> ParsedCss {
-    load_and_merge_styles_from_assets_common(
-        sources,
-        css_assets,
-        id,
-        class,
-        tag,
-        parent,
-        parent_query,
-        None,
-    )
+    load_and_merge_styles_from_assets_common(entity, sources, css_assets, parent_query, None)
 }
 
-/// Recursively matches a selector chain against an element and its parents.
+/// One element as seen by the selector engine.
+struct SelectorElement<'a> {
+    entity: Entity,
+    id: Option<&'a CssID>,
+    class: Option<&'a CssClass>,
+    tag: Option<&'a TagName>,
+    attributes: Option<&'a HtmlAttributes>,
+    state: Option<&'a UIWidgetState>,
+    children: Option<&'a Children>,
+    parent: Option<Entity>,
+}
+
+impl<'a> SelectorElement<'a> {
+    /// Reads the selector-relevant components of `entity`.
+    fn get(entity: Entity, parent_query: &'a Query<CssParentSelectorEntry<'_>>) -> Option<Self> {
+        let (id, class, tag, attributes, state, children, parent) =
+            parent_query.get(entity).ok()?;
+        Some(Self {
+            entity,
+            id,
+            class,
+            tag,
+            attributes,
+            state,
+            children,
+            parent: parent.map(ChildOf::parent),
+        })
+    }
+}
+
+/// Recursively matches a selector chain against an element, its parents, and its siblings.
 fn matches_selector_chain(
     selectors: &[SelectorStep],
-    id_opt: Option<&CssID>,
-    class_opt: Option<&CssClass>,
-    tag_opt: Option<&TagName>,
-    parent_opt: Option<&ChildOf>,
-    parent_query: &Query<(
-        Option<&CssID>,
-        Option<&CssClass>,
-        Option<&TagName>,
-        Option<&ChildOf>,
-    )>,
+    entity: Entity,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
 ) -> bool {
     if selectors.is_empty() {
         return true;
     }
 
-    let mut current_parent = parent_opt;
+    matches_selector_step(selectors, selectors.len() - 1, entity, false, parent_query)
+}
 
-    let current_sel = &selectors[selectors.len() - 1].selector;
-    if !matches_selector(current_sel, id_opt, class_opt, tag_opt) {
+/// Matches the steps left of `index` against the relatives of `element`.
+///
+/// Pseudo states of steps left of a `+` or `~` combinator are checked against
+/// the sibling they match, since the subject's own state says nothing about them.
+fn matches_selector_relatives(
+    selectors: &[SelectorStep],
+    index: usize,
+    element: &SelectorElement,
+    check_state: bool,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    if index == 0 {
+        return true;
+    }
+
+    let target = index - 1;
+    match selectors[index].combinator {
+        SelectorCombinator::Child => element.parent.is_some_and(|parent| {
+            matches_selector_step(selectors, target, parent, check_state, parent_query)
+        }),
+        SelectorCombinator::Descendant => std::iter::successors(element.parent, |ancestor| {
+            SelectorElement::get(*ancestor, parent_query).and_then(|ancestor| ancestor.parent)
+        })
+        .any(|ancestor| {
+            matches_selector_step(selectors, target, ancestor, check_state, parent_query)
+        }),
+        SelectorCombinator::Adjacent => {
+            let siblings = element_siblings(element, parent_query);
+            sibling_position(&siblings, element.entity)
+                .and_then(|position| position.checked_sub(1))
+                .is_some_and(|previous| {
+                    matches_selector_step(selectors, target, siblings[previous], true, parent_query)
+                })
+        }
+        SelectorCombinator::General => {
+            let siblings = element_siblings(element, parent_query);
+            let position = sibling_position(&siblings, element.entity).unwrap_or(0);
+            siblings[..position].iter().any(|sibling| {
+                matches_selector_step(selectors, target, *sibling, true, parent_query)
+            })
+        }
+        SelectorCombinator::Root => false,
+    }
+}
+
+/// Matches step `index` against `entity`, then continues with its relatives.
+fn matches_selector_step(
+    selectors: &[SelectorStep],
+    index: usize,
+    entity: Entity,
+    check_state: bool,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    let Some(element) = SelectorElement::get(entity, parent_query) else {
+        return false;
+    };
+
+    matches_compound(
+        &selectors[index].selector,
+        &element,
+        check_state,
+        parent_query,
+    ) && matches_selector_relatives(selectors, index, &element, check_state, parent_query)
+}
+
+/// Matches a complex selector like `.row > .cell:first-child` with `element` as subject.
+fn matches_complex_selector(
+    selector: &str,
+    element: &SelectorElement,
+    check_state: bool,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    let steps = parse_selector_steps(selector);
+    let Some(subject) = steps.last() else {
+        return false;
+    };
+
+    matches_compound(&subject.selector, element, check_state, parent_query)
+        && matches_selector_relatives(&steps, steps.len() - 1, element, check_state, parent_query)
+}
+
+/// Matches one compound selector, including its pseudo-classes, against `element`.
+///
+/// State pseudo-classes are only checked with `check_state`; otherwise the
+/// style state of the subject resolves them later.
+fn matches_compound(
+    selector: &str,
+    element: &SelectorElement,
+    check_state: bool,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    if !matches_selector(
+        selector,
+        element.id,
+        element.class,
+        element.tag,
+        element.attributes,
+    ) {
         return false;
     }
 
-    if selectors.len() == 1 {
-        return true;
+    split_pseudo_classes(selector)
+        .1
+        .iter()
+        .all(|pseudo| matches_pseudo_class(pseudo, element, check_state, parent_query))
+}
+
+/// Matches one pseudo-class against `element`.
+fn matches_pseudo_class(
+    pseudo: &PseudoClass,
+    element: &SelectorElement,
+    check_state: bool,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    let argument = pseudo.argument.unwrap_or_default();
+    let any_argument = |check_state: bool| {
+        split_top_level(argument, ',').iter().any(|selector| {
+            matches_complex_selector(selector.trim(), element, check_state, parent_query)
+        })
+    };
+
+    let name = pseudo.name.to_ascii_lowercase();
+    match name.as_str() {
+        "first-child" => nth_position(element, false, false, parent_query) == Some(1),
+        "last-child" => nth_position(element, false, true, parent_query) == Some(1),
+        "only-child" => element_siblings(element, parent_query).len() == 1,
+        "first-of-type" => nth_position(element, true, false, parent_query) == Some(1),
+        "last-of-type" => nth_position(element, true, true, parent_query) == Some(1),
+        "only-of-type" => {
+            nth_position(element, true, false, parent_query) == Some(1)
+                && nth_position(element, true, true, parent_query) == Some(1)
+        }
+        "nth-child" => matches_nth(argument, nth_position(element, false, false, parent_query)),
+        "nth-last-child" => matches_nth(argument, nth_position(element, false, true, parent_query)),
+        "nth-of-type" => matches_nth(argument, nth_position(element, true, false, parent_query)),
+        "nth-last-of-type" => {
+            matches_nth(argument, nth_position(element, true, true, parent_query))
+        }
+        "empty" => element_children(element, parent_query).is_empty(),
+        "root" => !std::iter::successors(element.parent, |ancestor| {
+            SelectorElement::get(*ancestor, parent_query).and_then(|ancestor| ancestor.parent)
+        })
+        .any(|ancestor| is_selector_element(ancestor, parent_query)),
+        // Arguments with state pseudo-classes are resolved by the style state,
+        // see `matches_subject_state_arguments`.
+        "not" if !check_state => !split_top_level(argument, ',').iter().any(|selector| {
+            !has_state_pseudo_class(selector)
+                && matches_complex_selector(selector.trim(), element, false, parent_query)
+        }),
+        "not" => !any_argument(true),
+        "is" | "where" => any_argument(check_state),
+        "has" => matches_has(argument, element, parent_query),
+        "enabled" | "valid" if !is_form_control(element, name == "valid") => false,
+        name if check_state => {
+            let state = element.state.cloned().unwrap_or_default();
+            pseudo_class_matches_state(name, &state).unwrap_or(true)
+        }
+        _ => true,
     }
+}
 
-    let mut index = selectors.len() - 1;
-    while index > 0 {
-        let relation = selectors[index].combinator;
-        let target = &selectors[index - 1].selector;
-
-        match relation {
-            SelectorCombinator::Child => {
-                let Some(parent) = current_parent else {
-                    return false;
-                };
-                let Ok((pid, p_class, p_tag, p_parent)) = parent_query.get(parent.parent()) else {
-                    return false;
-                };
-                if !matches_selector(target, pid, p_class, p_tag) {
-                    return false;
-                }
-                current_parent = p_parent;
-            }
-            SelectorCombinator::Descendant => {
-                let mut parent = current_parent;
-                let mut found = false;
-                while let Some(parent_entity) = parent {
-                    let Ok((pid, p_class, p_tag, p_parent)) =
-                        parent_query.get(parent_entity.parent())
-                    else {
-                        return false;
-                    };
-                    if matches_selector(target, pid, p_class, p_tag) {
-                        current_parent = p_parent;
-                        found = true;
-                        break;
-                    }
-                    parent = p_parent;
-                }
-                if !found {
-                    return false;
+/// Returns `true` for widgets `:enabled` applies to, plus `<form>` for `:valid`.
+fn is_form_control(element: &SelectorElement, include_form: bool) -> bool {
+    const FORM_CONTROL_TAGS: [&str; 12] = [
+        "button",
+        "input",
+        "checkbox",
+        "radio",
+        "switch",
+        "slider",
+        "toggle",
+        "select",
+        "listbox",
+        "colorpicker",
+        "date-picker",
+        "fieldset",
+    ];
+
+    element.tag.is_some_and(|tag| {
+        FORM_CONTROL_TAGS
+            .iter()
+            .any(|control| tag.0.eq_ignore_ascii_case(control))
+            || (include_form && tag.0.eq_ignore_ascii_case("form"))
+    })
+}
+
+/// Matches the relative selectors of `:has(...)` like `> .icon` or `+ .hint`.
+fn matches_has(
+    argument: &str,
+    element: &SelectorElement,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    split_top_level(argument, ',').iter().any(|relative| {
+        let relative = relative.trim();
+        let (combinator, selector) = match relative.chars().next() {
+            Some(combinator @ ('>' | '+' | '~')) => (Some(combinator), relative[1..].trim()),
+            _ => (None, relative),
+        };
+
+        let candidates = match combinator {
+            Some('>') => element_children(element, parent_query),
+            Some(combinator) => {
+                let siblings = element_siblings(element, parent_query);
+                let next = sibling_position(&siblings, element.entity)
+                    .map_or(siblings.len(), |position| position + 1);
+                let following = siblings.into_iter().skip(next);
+                if combinator == '+' {
+                    following.take(1).collect()
+                } else {
+                    following.collect()
                 }
             }
-            SelectorCombinator::Root => {
-                return false;
-            }
+            None => element_descendants(element, parent_query),
+        };
+
+        candidates.into_iter().any(|candidate| {
+            SelectorElement::get(candidate, parent_query).is_some_and(|candidate| {
+                matches_complex_selector(selector, &candidate, true, parent_query)
+            })
+        })
+    })
+}
+
+/// Returns `true` when an entity takes part in selector matching as an element.
+fn is_selector_element(entity: Entity, parent_query: &Query<CssParentSelectorEntry<'_>>) -> bool {
+    parent_query
+        .get(entity)
+        .is_ok_and(|(_, _, tag, ..)| tag.is_some())
+}
+
+/// Returns the child elements of `element`.
+fn element_children(
+    element: &SelectorElement,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> Vec<Entity> {
+    element
+        .children
+        .map(|children| {
+            children
+                .iter()
+                .filter(|child| is_selector_element(*child, parent_query))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns all descendant elements of `element` in document order.
+fn element_descendants(
+    element: &SelectorElement,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> Vec<Entity> {
+    let mut descendants = Vec::new();
+    for child in element_children(element, parent_query) {
+        descendants.push(child);
+        if let Some(child) = SelectorElement::get(child, parent_query) {
+            descendants.extend(element_descendants(&child, parent_query));
         }
+    }
+    descendants
+}
+
+/// Returns `element` and its sibling elements in document order.
+fn element_siblings(
+    element: &SelectorElement,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> Vec<Entity> {
+    element
+        .parent
+        .and_then(|parent| SelectorElement::get(parent, parent_query))
+        .map(|parent| element_children(&parent, parent_query))
+        .unwrap_or_else(|| vec![element.entity])
+}
+
+/// Returns the index of `entity` in `siblings`.
+fn sibling_position(siblings: &[Entity], entity: Entity) -> Option<usize> {
+    siblings.iter().position(|sibling| *sibling == entity)
+}
 
-        index -= 1;
+/// Returns the 1-based position of `element` among its siblings, optionally
+/// counting only siblings of the same tag or counting from the end.
+fn nth_position(
+    element: &SelectorElement,
+    of_type: bool,
+    from_end: bool,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> Option<usize> {
+    let mut siblings = element_siblings(element, parent_query);
+    if of_type {
+        let tag = element.tag.map(|tag| tag.0.as_str()).unwrap_or_default();
+        siblings.retain(|sibling| {
+            parent_query
+                .get(*sibling)
+                .is_ok_and(|(_, _, sibling_tag, ..)| {
+                    sibling_tag.is_some_and(|sibling_tag| sibling_tag.0.eq_ignore_ascii_case(tag))
+                })
+        });
     }
+    if from_end {
+        siblings.reverse();
+    }
+    sibling_position(&siblings, element.entity).map(|position| position + 1)
+}
 
-    true
+/// Checks a 1-based position against an `An+B` expression like `2n+1`, `odd` or `3`.
+fn matches_nth(expression: &str, position: Option<usize>) -> bool {
+    let (Some((a, b)), Some(position)) = (parse_nth(expression), position) else {
+        return false;
+    };
+    let offset = position as i32 - b;
+    if a == 0 {
+        offset == 0
+    } else {
+        offset % a == 0 && offset / a >= 0
+    }
 }
 
-/// Matches a single selector against an element's id, class, and tag.
+/// Parses an `An+B` expression into `(A, B)`.
+fn parse_nth(expression: &str) -> Option<(i32, i32)> {
+    let expression: String = expression
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    match expression.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => {}
+    }
+
+    let Some((a, b)) = expression.split_once('n') else {
+        return expression.parse().ok().map(|b| (0, b));
+    };
+    let a = match a {
+        "" | "+" => 1,
+        "-" => -1,
+        a => a.parse().ok()?,
+    };
+    let b = match b {
+        "" => 0,
+        b => b.strip_prefix('+').unwrap_or(b).parse().ok()?,
+    };
+    Some((a, b))
+}
+
+/// Returns `true` when a selector list uses state pseudo-classes like `:hover`.
+fn has_state_pseudo_class(selector: &str) -> bool {
+    split_top_level(selector, ',')
+        .iter()
+        .flat_map(|complex| split_selector_tokens(complex))
+        .any(|compound| {
+            split_pseudo_classes(compound).1.iter().any(|pseudo| {
+                pseudo_class_matches_state(pseudo.name, &UIWidgetState::default()).is_some()
+                    || pseudo.argument.is_some_and(has_state_pseudo_class)
+            })
+        })
+}
+
+/// Matches a single selector against an element's id, class, tag, and attributes.
 fn matches_selector(
     selector: &str,
     id_opt: Option<&CssID>,
     class_opt: Option<&CssClass>,
     tag_opt: Option<&TagName>,
+    attributes_opt: Option<&HtmlAttributes>,
 ) -> bool {
     let Some(requirements) = parse_simple_selector(selector) else {
         return false;
@@ -862,7 +1372,56 @@ fn matches_selector(
         }
     }
 
-    true
+    requirements
+        .attributes
+        .iter()
+        .all(|attribute| attribute.matches(attributes_opt))
+}
+
+/// Matches a selector list such as `#inventory .slot, .hotbar > .slot`
+/// against an element, its ancestors, and its siblings.
+pub(crate) fn matches_css_selector(
+    selector: &str,
+    entity: Entity,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    split_top_level(selector, ',')
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .any(|part| matches_selector_chain(&parse_selector_steps(part), entity, parent_query))
+}
+
+/// Checks the `:not()`, `:is()` and `:where()` arguments of the subject of
+/// `selector` that use state pseudo-classes against the current state of `entity`.
+///
+/// Selector matching leaves those to the style state, as in `.btn:is(:hover, :focus)`
+/// or `.tab:not(.primary:hover)`.
+pub(crate) fn matches_subject_state_arguments(
+    selector: &str,
+    entity: Entity,
+    parent_query: &Query<CssParentSelectorEntry<'_>>,
+) -> bool {
+    let Some(element) = SelectorElement::get(entity, parent_query) else {
+        return true;
+    };
+    let Some(subject) = split_selector_tokens(selector).pop() else {
+        return true;
+    };
+
+    split_pseudo_classes(subject)
+        .1
+        .iter()
+        .filter(|pseudo| is_state_argument_pseudo_class(pseudo))
+        .all(|pseudo| matches_pseudo_class(pseudo, &element, true, parent_query))
+}
+
+/// Returns `true` for `:not()`, `:is()` and `:where()` with state pseudo-class arguments.
+pub(crate) fn is_state_argument_pseudo_class(pseudo: &PseudoClass) -> bool {
+    matches!(
+        pseudo.name.to_ascii_lowercase().as_str(),
+        "not" | "is" | "where"
+    ) && pseudo.argument.is_some_and(has_state_pseudo_class)
 }
 
 /// Exposes simple selector matching for integration tests and external validation.
@@ -873,77 +1432,270 @@ pub fn matches_css_selector_token(
     class_opt: Option<&CssClass>,
     tag_opt: Option<&TagName>,
 ) -> bool {
-    matches_selector(selector, id_opt, class_opt, tag_opt)
+    matches_selector(selector, id_opt, class_opt, tag_opt, None)
+}
+
+/// Like [`matches_css_selector_token`], but also checks attribute selectors.
+#[doc(hidden)]
+pub fn matches_css_selector_token_with_attributes(
+    selector: &str,
+    id_opt: Option<&CssID>,
+    class_opt: Option<&CssClass>,
+    tag_opt: Option<&TagName>,
+    attributes_opt: Option<&HtmlAttributes>,
+) -> bool {
+    matches_selector(selector, id_opt, class_opt, tag_opt, attributes_opt)
 }
 
-/// Parsed requirements for a simple selector token like `div.card#main`.
+/// Parsed requirements for a simple selector token like `div.card#main[type=text]`.
 #[derive(Default)]
 struct SimpleSelectorRequirements<'a> {
     tag: Option<&'a str>,
     id: Option<&'a str>,
     classes: Vec<&'a str>,
+    attributes: Vec<AttributeSelector<'a>>,
 }
 
-/// Strips pseudo and attribute suffixes from one selector token.
-fn strip_selector_suffixes(selector: &str) -> &str {
-    let mut end = selector.len();
-    for (idx, ch) in selector.char_indices() {
-        if ch == ':' || ch == '[' {
-            end = idx;
-            break;
-        }
-    }
-    selector[..end].trim()
+/// Defines the available `AttributeOperator` variants for attribute selectors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AttributeOperator {
+    /// `[attr]`
+    Exists,
+    /// `[attr=value]`
+    Equals,
+    /// `[attr~=value]`
+    Includes,
+    /// `[attr|=value]`
+    DashMatch,
+    /// `[attr^=value]`
+    Prefix,
+    /// `[attr$=value]`
+    Suffix,
+    /// `[attr*=value]`
+    Substring,
 }
 
-/// Parses a simple selector token into tag/id/class requirements.
-fn parse_simple_selector(selector: &str) -> Option<SimpleSelectorRequirements<'_>> {
-    let base = strip_selector_suffixes(selector);
-    if base.is_empty() || base == "*" {
-        return Some(SimpleSelectorRequirements::default());
+/// One attribute selector like `[type^="pass" i]`.
+struct AttributeSelector<'a> {
+    name: &'a str,
+    operator: AttributeOperator,
+    value: &'a str,
+    ignore_case: bool,
+}
+
+impl AttributeSelector<'_> {
+    /// Checks the selector against the element's HTML attributes.
+    fn matches(&self, attributes: Option<&HtmlAttributes>) -> bool {
+        let Some(actual) = attributes.and_then(|attributes| attributes.get(self.name)) else {
+            return false;
+        };
+        if self.operator == AttributeOperator::Exists {
+            return true;
+        }
+
+        let (actual, expected) = if self.ignore_case {
+            (actual.to_ascii_lowercase(), self.value.to_ascii_lowercase())
+        } else {
+            (actual.to_string(), self.value.to_string())
+        };
+
+        match self.operator {
+            AttributeOperator::Exists => true,
+            AttributeOperator::Equals => actual == expected,
+            AttributeOperator::Includes => {
+                !expected.is_empty()
+                    && !expected.contains(char::is_whitespace)
+                    && actual.split_whitespace().any(|word| word == expected)
+            }
+            AttributeOperator::DashMatch => {
+                actual == expected
+                    || actual
+                        .strip_prefix(expected.as_str())
+                        .is_some_and(|rest| rest.starts_with('-'))
+            }
+            AttributeOperator::Prefix => !expected.is_empty() && actual.starts_with(&expected),
+            AttributeOperator::Suffix => !expected.is_empty() && actual.ends_with(&expected),
+            AttributeOperator::Substring => !expected.is_empty() && actual.contains(&expected),
+        }
     }
+}
 
-    let mut requirements = SimpleSelectorRequirements::default();
-    let bytes = base.as_bytes();
-    let len = bytes.len();
-    let mut i = 0usize;
+/// Returns the end of a tag, class, or id name starting at `start`.
+fn selector_name_end(selector: &str, start: usize) -> usize {
+    selector[start..]
+        .find(['.', '#', '[', ':'])
+        .map_or(selector.len(), |offset| start + offset)
+}
 
-    if bytes[0] != b'.' && bytes[0] != b'#' {
-        let mut end = 0usize;
-        while end < len && bytes[end] != b'.' && bytes[end] != b'#' {
-            end += 1;
+/// Returns the index of the `]` closing the attribute selector opened at `start`.
+fn attribute_selector_end(selector: &str, start: usize) -> Option<usize> {
+    let mut quote = None;
+    for (idx, ch) in selector[start + 1..].char_indices() {
+        match (quote, ch) {
+            (Some(active), _) if ch == active => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, ']') => return Some(start + 1 + idx),
+            _ => {}
         }
-        if end == 0 {
+    }
+    None
+}
+
+/// Parses the inside of `[...]`, e.g. `data-kind="main" i`.
+fn parse_attribute_selector(raw: &str) -> Option<AttributeSelector<'_>> {
+    let raw = raw.trim();
+    let Some(op_start) = raw.find(['=', '~', '|', '^', '$', '*']) else {
+        if raw.is_empty() || raw.contains(char::is_whitespace) {
             return None;
         }
-        requirements.tag = Some(&base[..end]);
-        i = end;
+        return Some(AttributeSelector {
+            name: raw,
+            operator: AttributeOperator::Exists,
+            value: "",
+            ignore_case: false,
+        });
+    };
+
+    let name = raw[..op_start].trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
     }
 
-    while i < len {
-        let prefix = bytes[i];
-        if prefix != b'.' && prefix != b'#' {
-            return None;
+    let rest = &raw[op_start..];
+    let (operator, rest) = match rest.as_bytes() {
+        [b'=', ..] => (AttributeOperator::Equals, &rest[1..]),
+        [b'~', b'=', ..] => (AttributeOperator::Includes, &rest[2..]),
+        [b'|', b'=', ..] => (AttributeOperator::DashMatch, &rest[2..]),
+        [b'^', b'=', ..] => (AttributeOperator::Prefix, &rest[2..]),
+        [b'$', b'=', ..] => (AttributeOperator::Suffix, &rest[2..]),
+        [b'*', b'=', ..] => (AttributeOperator::Substring, &rest[2..]),
+        _ => return None,
+    };
+
+    let rest = rest.trim_start();
+    let (value, flags) = match rest.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let end = rest[1..].find(quote)? + 1;
+            (&rest[1..end], &rest[end + 1..])
+        }
+        _ => {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
         }
-        i += 1;
+    };
+
+    let ignore_case = match flags.trim() {
+        "" | "s" | "S" => false,
+        "i" | "I" => true,
+        _ => return None,
+    };
+
+    Some(AttributeSelector {
+        name,
+        operator,
+        value,
+        ignore_case,
+    })
+}
+
+/// One pseudo-class of a compound selector, like `hover` or `nth-child(2n+1)`.
+pub(crate) struct PseudoClass<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) argument: Option<&'a str>,
+}
 
-        let start = i;
-        while i < len && bytes[i] != b'.' && bytes[i] != b'#' {
-            i += 1;
+/// Splits a compound selector into its base (`input.field[type=text]`) and
+/// its pseudo-classes, keeping arguments like `:not(.a, .b)` intact.
+pub(crate) fn split_pseudo_classes(compound: &str) -> (&str, Vec<PseudoClass<'_>>) {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut starts = Vec::new();
+
+    for (idx, ch) in compound.char_indices() {
+        if let Some(active) = quote {
+            if ch == active {
+                quote = None;
+            }
+            continue;
         }
-        if start == i {
-            return None;
+        match ch {
+            '"' | '\'' if depth > 0 => quote = Some(ch),
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => starts.push(idx),
+            _ => {}
         }
+    }
 
-        let token = &base[start..i];
-        if prefix == b'.' {
-            requirements.classes.push(token);
-        } else if let Some(existing) = requirements.id {
-            if existing != token {
+    let base = &compound[..starts.first().copied().unwrap_or(compound.len())];
+    let pseudos = starts
+        .iter()
+        .enumerate()
+        .filter_map(|(index, start)| {
+            let end = starts.get(index + 1).copied().unwrap_or(compound.len());
+            let raw = &compound[start + 1..end];
+            if raw.is_empty() {
                 return None;
             }
-        } else {
-            requirements.id = Some(token);
+            Some(match raw.split_once('(') {
+                Some((name, argument)) => PseudoClass {
+                    name,
+                    argument: Some(argument.strip_suffix(')').unwrap_or(argument).trim()),
+                },
+                None => PseudoClass {
+                    name: raw,
+                    argument: None,
+                },
+            })
+        })
+        .collect();
+
+    (base, pseudos)
+}
+
+/// Parses a simple selector token into tag/id/class/attribute requirements.
+///
+/// Pseudo-classes (everything after a top-level `:`) are matched separately.
+fn parse_simple_selector(selector: &str) -> Option<SimpleSelectorRequirements<'_>> {
+    let selector = selector.trim();
+    let mut requirements = SimpleSelectorRequirements::default();
+    let bytes = selector.as_bytes();
+    let mut i = selector_name_end(selector, 0);
+
+    if i > 0 && &selector[..i] != "*" {
+        requirements.tag = Some(&selector[..i]);
+    }
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b':' => break,
+            b'[' => {
+                let end = attribute_selector_end(selector, i)?;
+                requirements
+                    .attributes
+                    .push(parse_attribute_selector(&selector[i + 1..end])?);
+                i = end + 1;
+            }
+            prefix => {
+                let start = i + 1;
+                let end = selector_name_end(selector, start);
+                if start == end {
+                    return None;
+                }
+
+                let token = &selector[start..end];
+                if prefix == b'.' {
+                    requirements.classes.push(token);
+                } else if let Some(existing) = requirements.id {
+                    if existing != token {
+                        return None;
+                    }
+                } else {
+                    requirements.id = Some(token);
+                }
+                i = end;
+            }
         }
     }
 
@@ -959,6 +1711,10 @@ enum SelectorCombinator {
     Descendant,
     /// Variant `Child`.
     Child,
+    /// Variant `Adjacent` (`+`).
+    Adjacent,
+    /// Variant `General` (`~`).
+    General,
 }
 
 /// Represents the `SelectorStep` data structure used by the extended UI system.
@@ -973,9 +1729,15 @@ fn parse_selector_steps(selector: &str) -> Vec<SelectorStep> {
     let mut steps = Vec::new();
     let mut next_relation = SelectorCombinator::Descendant;
 
-    for part in selector.replace('>', " > ").split_whitespace() {
-        if part == ">" {
-            next_relation = SelectorCombinator::Child;
+    for part in split_selector_tokens(selector) {
+        let combinator = match part {
+            ">" => Some(SelectorCombinator::Child),
+            "+" => Some(SelectorCombinator::Adjacent),
+            "~" => Some(SelectorCombinator::General),
+            _ => None,
+        };
+        if let Some(combinator) = combinator {
+            next_relation = combinator;
             continue;
         }
 
@@ -995,3 +1757,50 @@ fn parse_selector_steps(selector: &str) -> Vec<SelectorStep> {
 
     steps
 }
+
+/// Splits a selector into compound tokens and `>`, `+`, `~` combinators.
+///
+/// Whitespace and combinators inside `[...]` or `(...)` do not split.
+pub(crate) fn split_selector_tokens(selector: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut depth = 0usize;
+    let mut quote = None;
+
+    for (idx, ch) in selector.char_indices() {
+        if let Some(active) = quote {
+            if ch == active {
+                quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' if depth > 0 => quote = Some(ch),
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            '>' | '+' | '~' if depth == 0 => {
+                if let Some(token_start) = start.take() {
+                    tokens.push(&selector[token_start..idx]);
+                }
+                tokens.push(&selector[idx..idx + 1]);
+                continue;
+            }
+            ch if depth == 0 && ch.is_whitespace() => {
+                if let Some(token_start) = start.take() {
+                    tokens.push(&selector[token_start..idx]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        start.get_or_insert(idx);
+    }
+
+    if let Some(token_start) = start {
+        tokens.push(&selector[token_start..]);
+    }
+
+    tokens
+}
@@ -1,7 +1,11 @@
 use crate::CurrentWidgetState;
-use crate::widgets::{BindToID, IgnoreParentState, UIGenID, UIWidgetState};
+use crate::widgets::{
+    BindToID, IgnoreParentState, InputField, UIGenID, UIWidgetState, ValidationRules,
+};
+use bevy::input::touch::Touches;
+use bevy::picking::pointer::PointerButton;
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Represents the `BoundStateIndex` data structure used by the extended UI system.
 #[derive(Resource, Default)]
@@ -18,6 +22,7 @@ impl Plugin for StateService {
     fn build(&self, app: &mut App) {
         app.init_resource::<BoundStateIndex>();
         app.register_type::<Pickable>();
+        app.add_observer(mark_pressed_widgets);
         app.add_systems(
             PostUpdate,
             (refresh_bound_state_index, update_widget_states).chain(),
@@ -28,8 +33,17 @@ impl Plugin for StateService {
                 internal_state_check.run_if(resource_changed::<CurrentWidgetState>),
                 handle_tab_focus,
                 unfocus_disabled,
+                release_pressed_widgets,
+                sync_form_states,
             ),
         );
+        app.add_systems(
+            Update,
+            sync_focus_states
+                .after(internal_state_check)
+                .after(handle_tab_focus)
+                .after(unfocus_disabled),
+        );
     }
 }
 
@@ -127,6 +141,9 @@ fn copy_widget_state(target: &mut UIWidgetState, source: &UIWidgetState) {
     target.readonly = source.readonly;
     target.disabled = source.disabled;
     target.checked = source.checked;
+    target.drag_over = source.drag_over;
+    target.pressed = source.pressed;
+    target.focus_visible = source.focus_visible;
 }
 
 /// Clears the `focused` state from all widgets except the currently focused one.
@@ -165,7 +182,7 @@ fn internal_state_check(
 ///
 /// # Behavior
 /// - Widgets are sorted by `UIGenID.0`.
-/// - Pressing `Tab` sets focus to the next widget in order.
+/// - Pressing `Tab` sets focus to the next widget in order and marks it `focus_visible`.
 /// - Pressing `Shift+Tab` sets focus to the previous widget in order.
 /// - The focus wraps around if reaching the end or beginning.
 ///
@@ -207,17 +224,20 @@ fn handle_tab_focus(
     match focused_index {
         Some(current_index) => {
             focusable_widgets[current_index].1.focused = false;
+            focusable_widgets[current_index].1.focus_visible = false;
             let next_index = if reverse {
                 (current_index + focusable_count - 1) % focusable_count
             } else {
                 (current_index + 1) % focusable_count
             };
             focusable_widgets[next_index].1.focused = true;
+            focusable_widgets[next_index].1.focus_visible = true;
             current_state.widget_id = focusable_widgets[next_index].2.get();
         }
         None => {
             let next_index = if reverse { focusable_count - 1 } else { 0 };
             focusable_widgets[next_index].1.focused = true;
+            focusable_widgets[next_index].1.focus_visible = true;
             current_state.widget_id = focusable_widgets[next_index].2.get();
         }
     }
@@ -231,3 +251,112 @@ fn unfocus_disabled(mut state_query: Query<&mut UIWidgetState, Changed<UIWidgetS
         }
     }
 }
+
+/// Marks widgets under a pressed primary button or touch as `pressed` for `:active`.
+///
+/// Pointer events bubble, so ancestors of the pressed widget are marked as well.
+fn mark_pressed_widgets(ev: On<Pointer<Press>>, mut state_query: Query<&mut UIWidgetState>) {
+    let Ok(mut widget_state) = state_query.get_mut(ev.event().entity) else {
+        return;
+    };
+    if widget_state.disabled || ev.button != PointerButton::Primary {
+        return;
+    }
+
+    widget_state.pressed = true;
+    if widget_state.focus_visible {
+        widget_state.focus_visible = false;
+    }
+}
+
+/// Clears `pressed` once neither the primary mouse button nor a touch is held anymore.
+fn release_pressed_widgets(
+    mouse: Option<Res<ButtonInput<MouseButton>>>,
+    touches: Option<Res<Touches>>,
+    mut state_query: Query<&mut UIWidgetState>,
+) {
+    let mouse_held = mouse.is_some_and(|mouse| mouse.pressed(MouseButton::Left));
+    let touch_held = touches.is_some_and(|touches| touches.iter().next().is_some());
+    if mouse_held || touch_held {
+        return;
+    }
+
+    for mut widget_state in &mut state_query {
+        if widget_state.pressed {
+            widget_state.pressed = false;
+        }
+    }
+}
+
+/// Keeps `focus_within` and `focus_visible` in line with the focused widgets.
+fn sync_focus_states(
+    parent_query: Query<&ChildOf>,
+    mut state_query: Query<(Entity, &mut UIWidgetState)>,
+) {
+    if !state_query
+        .iter_mut()
+        .any(|(_, widget_state)| widget_state.is_changed())
+    {
+        return;
+    }
+
+    let mut within = HashSet::new();
+    for (entity, widget_state) in &state_query {
+        if widget_state.focused {
+            within.insert(entity);
+            within.extend(parent_query.iter_ancestors(entity));
+        }
+    }
+
+    for (entity, mut widget_state) in &mut state_query {
+        let focus_within = within.contains(&entity);
+        if widget_state.focus_within != focus_within {
+            widget_state.focus_within = focus_within;
+        }
+        if widget_state.focus_visible && !widget_state.focused {
+            widget_state.focus_visible = false;
+        }
+    }
+}
+
+/// Mirrors input placeholders and `required` rules into the widget state.
+fn sync_form_states(
+    mut removed_rules: RemovedComponents<ValidationRules>,
+    mut queries: ParamSet<(
+        Query<
+            (
+                &mut UIWidgetState,
+                Option<&InputField>,
+                Option<&ValidationRules>,
+            ),
+            Or<(
+                Added<UIWidgetState>,
+                Changed<InputField>,
+                Changed<ValidationRules>,
+            )>,
+        >,
+        Query<&mut UIWidgetState, Without<ValidationRules>>,
+    )>,
+) {
+    for (mut widget_state, field, rules) in &mut queries.p0() {
+        let placeholder_shown =
+            field.is_some_and(|field| field.text.is_empty() && !field.placeholder.is_empty());
+        let required = rules.is_some_and(|rules| rules.required);
+
+        if widget_state.placeholder_shown != placeholder_shown {
+            widget_state.placeholder_shown = placeholder_shown;
+        }
+        if widget_state.required != required {
+            widget_state.required = required;
+        }
+    }
+
+    let mut unrequired = queries.p1();
+    for entity in removed_rules.read() {
+        if let Ok(mut widget_state) = unrequired.get_mut(entity)
+            && widget_state.required
+        {
+            widget_state.required = false;
+        }
+    }
+}
@@ -1,16 +1,24 @@
 use crate::ExtendedUiConfiguration;
 use crate::ImageCache;
 use crate::html::HtmlStyle;
+use crate::html::inline_functions::split_top_level;
+use crate::services::css_service::{
+    CssParentSelectorEntry, is_state_argument_pseudo_class, matches_subject_state_arguments,
+    split_pseudo_classes, split_selector_tokens,
+};
 use crate::services::image_service::get_or_load_image;
 use crate::services::state_service::update_widget_states;
 use crate::styles::components::UiStyle;
+use crate::styles::parser::{resolve_style_vars, resolve_var};
 use crate::styles::{
     BackdropFilter, BackgroundAttachment, BackgroundPosition, BackgroundPositionValue,
-    BackgroundSize, BackgroundSizeValue, CalcContext, CalcExpr, FontWeight, GradientStopPosition,
-    LinearGradient, Style, TextTransform,
+    BackgroundSize, BackgroundSizeValue, CalcContext, CalcExpr, ComputedCssVariables, CssVariables,
+    FontWeight, GradientStopPosition, LinearGradient, Style, TextTransform,
 };
 use crate::widgets::UIWidgetState;
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::Arc;
 
 use bevy::asset::RenderAssetUsages;
 use bevy::asset::{load_internal_asset, uuid_handle};
@@ -62,12 +70,21 @@ const BACKDROP_BLUR_SHADER_HANDLE: Handle<Shader> =
     uuid_handle!("9d04a8bb-b6cf-4758-bca8-30706480973f");
 const MAX_BACKDROP_BLUR_PX: f32 = 80.0;
 
-const SELECTOR_READ_ONLY: u8 = 1 << 0;
-const SELECTOR_DISABLED: u8 = 1 << 1;
-const SELECTOR_CHECKED: u8 = 1 << 2;
-const SELECTOR_FOCUS: u8 = 1 << 3;
-const SELECTOR_HOVER: u8 = 1 << 4;
-const SELECTOR_INVALID: u8 = 1 << 5;
+const SELECTOR_READ_ONLY: u16 = 1 << 0;
+const SELECTOR_DISABLED: u16 = 1 << 1;
+const SELECTOR_CHECKED: u16 = 1 << 2;
+const SELECTOR_FOCUS: u16 = 1 << 3;
+const SELECTOR_HOVER: u16 = 1 << 4;
+const SELECTOR_INVALID: u16 = 1 << 5;
+const SELECTOR_DRAG_OVER: u16 = 1 << 6;
+const SELECTOR_ACTIVE: u16 = 1 << 7;
+const SELECTOR_FOCUS_VISIBLE: u16 = 1 << 8;
+const SELECTOR_FOCUS_WITHIN: u16 = 1 << 9;
+const SELECTOR_ENABLED: u16 = 1 << 10;
+const SELECTOR_VALID: u16 = 1 << 11;
+const SELECTOR_PLACEHOLDER_SHOWN: u16 = 1 << 12;
+const SELECTOR_OPEN: u16 = 1 << 13;
+const SELECTOR_REQUIRED: u16 = 1 << 14;
 
 static SELECTOR_METADATA_CACHE: Lazy<RwLock<HashMap<String, SelectorMetadata>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
@@ -76,7 +93,9 @@ static SELECTOR_METADATA_CACHE: Lazy<RwLock<HashMap<String, SelectorMetadata>>>
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 struct SelectorMetadata {
     specificity: u32,
-    pseudo_flags: u8,
+    pseudo_flags: u16,
+    /// `:not()`, `:is()` or `:where()` with state arguments on the subject.
+    state_arguments: bool,
     has_pseudo: bool,
     skip: bool,
 }
@@ -244,38 +263,64 @@ fn mark_new_nodes_for_style_refresh(
 /// Computes active styles for widgets and applies them to UI components.
 pub fn update_widget_styles_system(
     mut commands: Commands,
-    mut query: Query<
-        (
-            Entity,
-            Option<Ref<UIWidgetState>>,
-            Option<Ref<HtmlStyle>>,
-            Option<&StyleRefreshOnNodeAdded>,
-            &mut UiStyle,
-        ),
-        Or<(
-            Changed<UiStyle>,
-            Changed<HtmlStyle>,
-            Changed<UIWidgetState>,
-            Added<StyleRefreshOnNodeAdded>,
-        )>,
-    >,
+    mut query: Query<(
+        Entity,
+        Option<Ref<UIWidgetState>>,
+        Option<Ref<HtmlStyle>>,
+        Option<Ref<CssVariables>>,
+        Option<Ref<StyleRefreshOnNodeAdded>>,
+        &mut UiStyle,
+    )>,
     mut transition_query: Query<Option<&mut StyleTransition>>,
     mut animation_query: Query<Option<&mut StyleAnimation>>,
     mut qs: ParamSet<(Query<UiStyleComponents>,)>,
+    mut computed_vars_query: Query<&mut ComputedCssVariables>,
+    parent_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    styled_query: Query<(), With<UiStyle>>,
+    selector_query: Query<CssParentSelectorEntry>,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
     mut image_cache: ResMut<ImageCache>,
     mut images: ResMut<Assets<Image>>,
 ) {
-    for (entity, state_opt, html_style_opt, refresh_on_node_added, mut ui_style) in query.iter_mut()
-    {
+    // Parents first, so children inherit the custom properties computed this frame.
+    let mut pending: BinaryHeap<Reverse<(usize, Entity)>> = query
+        .iter_mut()
+        .filter(|(_, state, html_style, css_vars, refresh, ui_style)| {
+            ui_style.is_changed()
+                || state.as_ref().is_some_and(|state| state.is_changed())
+                || html_style
+                    .as_ref()
+                    .is_some_and(|html_style| html_style.is_changed())
+                || css_vars
+                    .as_ref()
+                    .is_some_and(|css_vars| css_vars.is_changed())
+                || refresh.as_ref().is_some_and(|refresh| refresh.is_added())
+        })
+        .map(|(entity, ..)| Reverse((parent_query.iter_ancestors(entity).count(), entity)))
+        .collect();
+    let mut queued: HashSet<Entity> = pending.iter().map(|Reverse((_, entity))| *entity).collect();
+    let mut frame_vars: HashMap<Entity, Arc<HashMap<String, String>>> = HashMap::new();
+
+    while let Some(Reverse((_, entity))) = pending.pop() {
+        let Ok((_, state_opt, html_style_opt, css_vars_opt, refresh_on_node_added, mut ui_style)) =
+            query.get_mut(entity)
+        else {
+            continue;
+        };
+
         let state_changed = state_opt.as_ref().is_some_and(|state| state.is_changed());
         let html_style_changed = html_style_opt
             .as_ref()
             .is_some_and(|html_style| html_style.is_changed());
+        let css_vars_changed = css_vars_opt
+            .as_ref()
+            .is_some_and(|css_vars| css_vars.is_changed());
         let ui_style_changed = ui_style.is_changed();
         if state_changed
             && !html_style_changed
+            && !css_vars_changed
             && !ui_style_changed
             && refresh_on_node_added.is_none()
             && ui_style.active_style.is_some()
@@ -301,7 +346,10 @@ pub fn update_widget_styles_system(
             if metadata.skip {
                 continue;
             }
-            if selector_matches_state(metadata, &state) {
+            if selector_matches_state(metadata, &state)
+                && (!metadata.state_arguments
+                    || matches_subject_state_arguments(selector, entity, &selector_query))
+            {
                 if metadata.has_pseudo {
                     pseudo_styles.push((key, metadata.specificity, style_pair.origin));
                 } else {
@@ -315,24 +363,89 @@ pub fn update_widget_styles_system(
         sort_style_candidates(&mut base_styles);
         sort_style_candidates(&mut pseudo_styles);
 
+        // Custom properties cascade like any other property, then inherit from the parent.
+        let html_style = html_style_opt.as_deref().map(|html_style| &html_style.0);
+        let css_vars = css_vars_opt.as_deref();
+        let inherited_vars =
+            inherited_css_variables(entity, &parent_query, &frame_vars, &computed_vars_query);
+        let declares_vars = candidates_declare_vars(&ui_style, &base_styles)
+            || candidates_declare_vars(&ui_style, &pseudo_styles)
+            || html_style.is_some_and(|html_style| !html_style.vars.properties.is_empty())
+            || css_vars.is_some_and(|css_vars| !css_vars.0.is_empty());
+
+        // Without own declarations the entity shares the inherited map.
+        let vars = if declares_vars {
+            let mut declared_vars = HashMap::new();
+            collect_candidate_vars(&mut declared_vars, &ui_style, &base_styles, false);
+            collect_candidate_vars(&mut declared_vars, &ui_style, &base_styles, true);
+            if let Some(html_style) = html_style {
+                declared_vars.extend(html_style.vars.properties.clone());
+            }
+            collect_candidate_vars(&mut declared_vars, &ui_style, &pseudo_styles, false);
+            collect_candidate_vars(&mut declared_vars, &ui_style, &pseudo_styles, true);
+            if let Some(css_vars) = css_vars {
+                declared_vars.extend(css_vars.0.clone());
+            }
+            Arc::new(compute_css_variables(
+                (*inherited_vars).clone(),
+                &declared_vars,
+            ))
+        } else {
+            inherited_vars
+        };
+
         let mut final_style = Style::default();
 
         // 1) base normal
-        merge_style_candidates(&mut final_style, &ui_style, &base_styles, false);
+        merge_style_candidates(&mut final_style, &ui_style, &base_styles, false, &vars);
 
         // 2) base important
-        merge_style_candidates(&mut final_style, &ui_style, &base_styles, true);
+        merge_style_candidates(&mut final_style, &ui_style, &base_styles, true, &vars);
 
         // 3) inline html
-        if let Some(html_style) = html_style_opt {
-            final_style.merge(&html_style.0);
+        if let Some(html_style) = html_style {
+            merge_style_with_vars(&mut final_style, html_style, &vars);
         }
 
         // 4) pseudo normal
-        merge_style_candidates(&mut final_style, &ui_style, &pseudo_styles, false);
+        merge_style_candidates(&mut final_style, &ui_style, &pseudo_styles, false, &vars);
 
         // 5) pseudo important
-        merge_style_candidates(&mut final_style, &ui_style, &pseudo_styles, true);
+        merge_style_candidates(&mut final_style, &ui_style, &pseudo_styles, true, &vars);
+
+        final_style.vars.declarations.clear();
+        final_style.vars.overrides.clear();
+
+        let vars_changed = match computed_vars_query.get_mut(entity) {
+            Ok(mut computed) if declares_vars => {
+                let changed = *computed.0 != *vars;
+                if changed {
+                    computed.0 = Arc::clone(&vars);
+                }
+                changed
+            }
+            Ok(_) => {
+                commands.entity(entity).remove::<ComputedCssVariables>();
+                true
+            }
+            Err(_) if declares_vars => {
+                commands
+                    .entity(entity)
+                    .insert(ComputedCssVariables(Arc::clone(&vars)));
+                true
+            }
+            Err(_) => false,
+        };
+        // Descendants resolve `var()` against the new values in this pass.
+        if vars_changed {
+            for descendant in children_query.iter_descendants(entity) {
+                if styled_query.contains(descendant) && queued.insert(descendant) {
+                    let depth = parent_query.iter_ancestors(descendant).count();
+                    pending.push(Reverse((depth, descendant)));
+                }
+            }
+        }
+        frame_vars.insert(entity, vars);
 
         let previous_style = ui_style.active_style.clone();
         let has_changed = previous_style.as_ref() != Some(&final_style);
@@ -1465,18 +1578,102 @@ fn merge_style_candidates(
     ui_style: &UiStyle,
     candidates: &[StyleCandidate<'_>],
     important: bool,
+    vars: &HashMap<String, String>,
 ) {
     for (sel, _, _) in candidates {
         if let Some(pair) = ui_style.styles.get(*sel) {
             if important {
-                final_style.merge(&pair.important);
+                merge_style_with_vars(final_style, &pair.important, vars);
             } else {
-                final_style.merge(&pair.normal);
+                merge_style_with_vars(final_style, &pair.normal, vars);
             }
         }
     }
 }
 
+/// Merges `style` into `final_style`, resolving its `var()` declarations against `vars`.
+fn merge_style_with_vars(final_style: &mut Style, style: &Style, vars: &HashMap<String, String>) {
+    match resolve_style_vars(style, vars) {
+        Some(resolved) => final_style.merge(&resolved),
+        None => final_style.merge(style),
+    }
+}
+
+/// Returns `true` when one of the matching rules declares custom properties.
+fn candidates_declare_vars(ui_style: &UiStyle, candidates: &[StyleCandidate<'_>]) -> bool {
+    candidates.iter().any(|(sel, _, _)| {
+        ui_style.styles.get(*sel).is_some_and(|pair| {
+            !pair.normal.vars.properties.is_empty() || !pair.important.vars.properties.is_empty()
+        })
+    })
+}
+
+/// Collects the custom properties declared by the matching rules, in cascade order.
+fn collect_candidate_vars(
+    declared_vars: &mut HashMap<String, String>,
+    ui_style: &UiStyle,
+    candidates: &[StyleCandidate<'_>],
+    important: bool,
+) {
+    for (sel, _, _) in candidates {
+        if let Some(pair) = ui_style.styles.get(*sel) {
+            let style = if important {
+                &pair.important
+            } else {
+                &pair.normal
+            };
+            declared_vars.extend(
+                style
+                    .vars
+                    .properties
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+        }
+    }
+}
+
+/// Returns the custom properties of the nearest styled ancestor.
+fn inherited_css_variables(
+    entity: Entity,
+    parent_query: &Query<&ChildOf>,
+    frame_vars: &HashMap<Entity, Arc<HashMap<String, String>>>,
+    computed_vars_query: &Query<&mut ComputedCssVariables>,
+) -> Arc<HashMap<String, String>> {
+    for ancestor in parent_query.iter_ancestors(entity) {
+        if let Some(vars) = frame_vars.get(&ancestor) {
+            return Arc::clone(vars);
+        }
+        if let Ok(vars) = computed_vars_query.get(ancestor) {
+            return Arc::clone(&vars.0);
+        }
+    }
+    Arc::default()
+}
+
+/// Applies declared custom properties over the inherited ones.
+///
+/// `var()` inside a declared value resolves against the entity's own scope,
+/// e.g. `--accent: var(--danger)`.
+fn compute_css_variables(
+    mut vars: HashMap<String, String>,
+    declared_vars: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    vars.extend(
+        declared_vars
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone())),
+    );
+
+    for name in declared_vars.keys() {
+        if let Some(value) = vars.get(name).filter(|value| value.contains("var(")) {
+            let resolved = resolve_var(value, &vars);
+            vars.insert(name.clone(), resolved);
+        }
+    }
+    vars
+}
+
 /// Handles `shrink_axis` in the extended UI workflow.
 fn shrink_axis(size: f32, min_inset: f32, max_inset: f32) -> f32 {
     (size - min_inset - max_inset).max(0.0)
@@ -2110,19 +2307,65 @@ fn compute_selector_metadata(selector: &str) -> SelectorMetadata {
         };
     }
 
-    let mut specificity = 0;
-    let mut pseudo_flags = 0;
-    let mut has_pseudo = false;
+    let mut metadata = SelectorMetadata::default();
+
+    // Pseudo-classes left of `+`/`~` belong to a sibling and are checked while matching.
+    let tokens = split_selector_tokens(selector);
+    let last = tokens.len().saturating_sub(1);
+    let subject_start = tokens
+        .iter()
+        .rposition(|token| matches!(*token, "+" | "~"))
+        .map_or(0, |index| index + 1);
+
+    for (index, part) in tokens.into_iter().enumerate() {
+        if matches!(part, ">" | "+" | "~") {
+            continue;
+        }
 
-    for part in selector.replace('>', " > ").split_whitespace() {
-        if part == ">" {
+        metadata.specificity += compound_specificity(part);
+
+        let (_, pseudos) = split_pseudo_classes(part);
+        metadata.has_pseudo |= !pseudos.is_empty();
+        if index < subject_start {
             continue;
         }
 
-        let segments: Vec<&str> = part.split(':').collect();
-        let base = segments[0];
+        for pseudo in &pseudos {
+            if is_state_argument_pseudo_class(pseudo) {
+                metadata.state_arguments |= index == last;
+            } else {
+                metadata.pseudo_flags |= pseudo_class_flag(pseudo.name).unwrap_or(0);
+            }
+        }
+    }
+
+    metadata
+}
+
+/// Returns the specificity of the most specific selector in a list.
+fn selector_specificity(selector: &str) -> u32 {
+    split_top_level(selector, ',')
+        .iter()
+        .map(|complex| {
+            split_selector_tokens(complex)
+                .into_iter()
+                .filter(|part| !matches!(*part, ">" | "+" | "~"))
+                .map(compound_specificity)
+                .sum::<u32>()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the specificity of one compound selector like `input.field:hover`.
+///
+/// `:not()`, `:is()` and `:has()` count their most specific argument, `:where()` counts nothing.
+fn compound_specificity(compound: &str) -> u32 {
+    let (base, pseudos) = split_pseudo_classes(compound);
+    let (base, attribute_count) = strip_attribute_selectors(base);
 
-        specificity += if base.starts_with('#') {
+    let mut specificity = attribute_count * 10
+        + if base.starts_with('#') {
             100
         } else if base.starts_with('.') {
             10
@@ -2132,30 +2375,78 @@ fn compute_selector_metadata(selector: &str) -> SelectorMetadata {
             1
         };
 
-        if segments.len() > 1 {
-            has_pseudo = true;
-            specificity += segments.len().saturating_sub(1) as u32;
-        }
+    for pseudo in &pseudos {
+        specificity += match pseudo.name {
+            "where" => 0,
+            "not" | "is" | "has" => pseudo.argument.map_or(0, selector_specificity),
+            _ => 1,
+        };
+    }
+
+    specificity
+}
 
-        for pseudo in &segments[1..] {
-            match *pseudo {
-                "read-only" => pseudo_flags |= SELECTOR_READ_ONLY,
-                "disabled" => pseudo_flags |= SELECTOR_DISABLED,
-                "checked" => pseudo_flags |= SELECTOR_CHECKED,
-                "focus" => pseudo_flags |= SELECTOR_FOCUS,
-                "hover" => pseudo_flags |= SELECTOR_HOVER,
-                "invalid" => pseudo_flags |= SELECTOR_INVALID,
-                _ => {}
+/// Maps a state pseudo-class like `hover` to its selector flag.
+fn pseudo_class_flag(name: &str) -> Option<u16> {
+    match name {
+        "read-only" => Some(SELECTOR_READ_ONLY),
+        "disabled" => Some(SELECTOR_DISABLED),
+        "enabled" => Some(SELECTOR_ENABLED),
+        "checked" => Some(SELECTOR_CHECKED),
+        "focus" => Some(SELECTOR_FOCUS),
+        "focus-visible" => Some(SELECTOR_FOCUS_VISIBLE),
+        "focus-within" => Some(SELECTOR_FOCUS_WITHIN),
+        "hover" => Some(SELECTOR_HOVER),
+        "active" => Some(SELECTOR_ACTIVE),
+        "invalid" => Some(SELECTOR_INVALID),
+        "valid" => Some(SELECTOR_VALID),
+        "placeholder-shown" => Some(SELECTOR_PLACEHOLDER_SHOWN),
+        "open" => Some(SELECTOR_OPEN),
+        "required" => Some(SELECTOR_REQUIRED),
+        "drag-over" => Some(SELECTOR_DRAG_OVER),
+        _ => None,
+    }
+}
+
+/// Returns whether a state pseudo-class like `hover` matches `state`,
+/// or `None` when `name` is not a state pseudo-class.
+pub(crate) fn pseudo_class_matches_state(name: &str, state: &UIWidgetState) -> Option<bool> {
+    let metadata = SelectorMetadata {
+        pseudo_flags: pseudo_class_flag(name)?,
+        ..Default::default()
+    };
+    Some(selector_matches_state(metadata, state))
+}
+
+/// Removes `[...]` attribute selectors from a compound selector and counts them.
+fn strip_attribute_selectors(part: &str) -> (String, u32) {
+    let mut stripped = String::with_capacity(part.len());
+    let mut count = 0;
+    let mut depth = 0usize;
+    let mut quote = None;
+
+    for ch in part.chars() {
+        if let Some(active) = quote {
+            if ch == active {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '[' => {
+                if depth == 0 {
+                    count += 1;
+                }
+                depth += 1;
             }
+            ']' => depth = depth.saturating_sub(1),
+            '"' | '\'' if depth > 0 => quote = Some(ch),
+            _ if depth == 0 => stripped.push(ch),
+            _ => {}
         }
     }
 
-    SelectorMetadata {
-        specificity,
-        pseudo_flags,
-        has_pseudo,
-        skip: false,
-    }
+    (stripped, count)
 }
 
 /// Returns true if the selector's cached pseudo state matches the widget state.
@@ -2178,6 +2469,35 @@ fn selector_matches_state(metadata: SelectorMetadata, state: &UIWidgetState) ->
     if metadata.pseudo_flags & SELECTOR_INVALID != 0 && !state.invalid {
         return false;
     }
+    if metadata.pseudo_flags & SELECTOR_DRAG_OVER != 0 && !state.drag_over {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_ACTIVE != 0 && (state.disabled || !state.pressed) {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_FOCUS_VISIBLE != 0
+        && (state.disabled || !state.focused || !state.focus_visible)
+    {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_FOCUS_WITHIN != 0 && !state.focus_within {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_ENABLED != 0 && state.disabled {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_VALID != 0 && state.invalid {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_PLACEHOLDER_SHOWN != 0 && !state.placeholder_shown {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_OPEN != 0 && !state.open {
+        return false;
+    }
+    if metadata.pseudo_flags & SELECTOR_REQUIRED != 0 && !state.required {
+        return false;
+    }
     true
 }
 
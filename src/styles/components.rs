@@ -81,7 +81,14 @@ impl UiStyle {
         let mut filtered = HashMap::new();
         let mut priority_map = HashMap::<String, u8>::new();
 
-        let pseudo_classes = ["hover", "focus", "read-only", "disabled", "invalid"];
+        let pseudo_classes = [
+            "hover",
+            "focus",
+            "read-only",
+            "disabled",
+            "invalid",
+            "drag-over",
+        ];
 
         let mut insert_with_pseudo = |base: &str, prio: u8| {
             for (key, style) in self.styles.iter() {
@@ -9,6 +9,7 @@ use bevy::text::{FontSize, LineHeight};
 use bevy::window::SystemCursorIcon;
 use std::cmp::PartialEq;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 // ==================================================
 //                     Css Styling
@@ -44,6 +45,11 @@ impl CssSource {
         Self(vec![asset_server.load::<CssAsset>(path.to_string())])
     }
 
+    /// Creates a CSS source from in-memory CSS text.
+    pub fn from_string(assets: &mut Assets<CssAsset>, css: impl Into<String>) -> Self {
+        Self(vec![assets.add(CssAsset { text: css.into() })])
+    }
+
     /// Appends another CSS asset path to the source list.
     pub fn push_path(&mut self, asset_server: &AssetServer, path: &str) {
         self.0.push(asset_server.load::<CssAsset>(path.to_string()));
@@ -63,6 +69,21 @@ impl CssSource {
     }
 }
 
+/// Custom properties set on an entity from code, e.g. via `UiDom::set_var`.
+///
+/// They override the custom properties declared by CSS rules and inherit to descendants.
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component)]
+pub struct CssVariables(pub HashMap<String, String>);
+
+/// Custom properties in effect for an entity after cascade and inheritance.
+///
+/// Only entities that declare custom properties carry it; descendants share
+/// the map of their nearest ancestor with one.
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component)]
+pub struct ComputedCssVariables(#[reflect(ignore)] pub Arc<HashMap<String, String>>);
+
 /// Represents the border-radius of a rectangle with individual corner values.
 #[derive(Reflect, Default, Clone, PartialEq, Debug)]
 pub struct Radius {
@@ -847,6 +868,55 @@ pub struct StylePair {
     pub media: Option<MediaQueryCondition>,
 }
 
+/// A declaration referencing `var()`, like `width: calc(var(--hp) - 4px)`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CssVarDeclaration {
+    pub property: String,
+    pub raw: String,
+    /// The value resolved against `:root` when the stylesheet was parsed.
+    pub resolved: String,
+}
+
+/// Custom properties and `var()` declarations of a rule, resolved per entity when applied.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CssVarStyle {
+    /// Declared custom properties like `--accent: red`.
+    pub properties: HashMap<String, String>,
+    pub declarations: Vec<CssVarDeclaration>,
+    /// Properties declared without `var()`; they replace earlier `var()` declarations on merge.
+    pub overrides: Vec<String>,
+}
+
+impl CssVarStyle {
+    /// Records a declaration of `property` that does not use `var()`.
+    pub fn declare(&mut self, property: &str) {
+        self.declarations
+            .retain(|declaration| declaration.property != property);
+        if !self.overrides.iter().any(|name| name == property) {
+            self.overrides.push(property.to_string());
+        }
+    }
+
+    /// Merges another rule into this one, overriding declarations it sets again.
+    pub fn merge(&mut self, other: &CssVarStyle) {
+        self.properties.extend(
+            other
+                .properties
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        for property in &other.overrides {
+            self.declare(property);
+        }
+        for declaration in &other.declarations {
+            self.declarations
+                .retain(|existing| existing.property != declaration.property);
+            self.overrides.retain(|name| *name != declaration.property);
+            self.declarations.push(declaration.clone());
+        }
+    }
+}
+
 /// Transforms parsed from CSS transform properties.
 #[derive(Reflect, Default, Debug, Clone, PartialEq)]
 pub struct TransformStyle {
@@ -991,6 +1061,8 @@ pub struct Style {
     pub transition: Option<TransitionSpec>,
     pub transform: TransformStyle,
     pub animation: Option<AnimationSpec>,
+    #[reflect(ignore)]
+    pub vars: CssVarStyle,
 }
 
 impl Style {
@@ -1172,6 +1244,7 @@ impl Style {
         merge_opt(&mut self.transform.rotation, &other.transform.rotation);
 
         merge_opt(&mut self.animation, &other.animation);
+        self.vars.merge(&other.vars);
     }
 }
 
@@ -1209,5 +1282,7 @@ impl Plugin for ExtendedStylingPlugin {
         app.register_type::<UiStyle>();
         app.register_type::<CssClass>();
         app.register_type::<CssID>();
+        app.register_type::<CssVariables>();
+        app.register_type::<ComputedCssVariables>();
     }
 }
@@ -2,9 +2,9 @@ use crate::styles::paint::Colored;
 use crate::styles::{
     AnimationDirection, AnimationKeyframe, AnimationSpec, BackdropFilter, Background,
     BackgroundAttachment, BackgroundPosition, BackgroundPositionValue, BackgroundSize,
-    BackgroundSizeValue, CalcExpr, CalcUnit, CalcValue, CursorStyle, FontFamily, FontWeight,
-    GradientStop, GradientStopPosition, LinearGradient, MediaQueryCondition, ParsedCss, Radius,
-    Style, StylePair, TextTransform, TransformStyle, TransitionProperty, TransitionSpec,
+    BackgroundSizeValue, CalcExpr, CalcUnit, CalcValue, CssVarDeclaration, CursorStyle, FontFamily,
+    FontWeight, GradientStop, GradientStopPosition, LinearGradient, MediaQueryCondition, ParsedCss,
+    Radius, Style, StylePair, TextTransform, TransformStyle, TransitionProperty, TransitionSpec,
     TransitionTiming,
 };
 use bevy::prelude::*;
@@ -224,15 +224,13 @@ fn collect_style_rule(
     allow_root_var_collection: bool,
 ) {
     let selectors = selector_list_to_strings(&style_rule.selectors);
-    if parent_selectors.is_none()
+    if allow_root_var_collection
+        && parent_selectors.is_none()
         && media_condition.is_none()
         && selectors.len() == 1
         && selectors[0].trim() == ":root"
     {
-        if allow_root_var_collection {
-            collect_css_vars(&style_rule.declarations, css_vars);
-        }
-        return;
+        collect_css_vars(&style_rule.declarations, css_vars);
     }
 
     let full_selectors = expand_selectors(parent_selectors, selectors);
@@ -615,11 +613,89 @@ fn apply_declaration_list(
             Err(_) => continue,
         };
 
-        let resolved = resolve_var(&value, css_vars);
+        apply_declaration_to_style(style, name, &value, css_vars);
+    }
+}
+
+/// Applies one declaration and records custom properties and `var()` references.
+///
+/// `var()` is resolved against `css_vars` right away and again per entity when
+/// the style is applied, so scoped custom properties can override it.
+pub fn apply_declaration_to_style(
+    style: &mut Style,
+    name: &str,
+    value: &str,
+    css_vars: &HashMap<String, String>,
+) {
+    if name.starts_with("--") {
+        style
+            .vars
+            .properties
+            .insert(name.to_string(), value.trim().to_string());
+        return;
+    }
+
+    if !contains_var_call(value) {
+        style.vars.declare(name);
+        apply_property_to_style(style, name, value);
+        return;
+    }
+
+    let resolved = resolve_var(value, css_vars);
+    style.vars.overrides.retain(|property| property != name);
+    style
+        .vars
+        .declarations
+        .retain(|declaration| declaration.property != name);
+    style.vars.declarations.push(CssVarDeclaration {
+        property: name.to_string(),
+        raw: value.to_string(),
+        resolved: resolved.clone(),
+    });
+    if !contains_var_call(&resolved) {
         apply_property_to_style(style, name, &resolved);
     }
 }
 
+/// Re-resolves the `var()` declarations of `style` against an entity's custom properties.
+///
+/// Declarations referencing a variable missing from `vars` keep their parsed value.
+/// Returns `None` when nothing resolves differently than at parse time.
+pub fn resolve_style_vars(style: &Style, vars: &HashMap<String, String>) -> Option<Style> {
+    let mut resolved_style: Option<Style> = None;
+
+    for declaration in &style.vars.declarations {
+        if !var_call_names(&declaration.raw).all(|name| vars.contains_key(name)) {
+            continue;
+        }
+
+        let value = resolve_var(&declaration.raw, vars);
+        if value == declaration.resolved || contains_var_call(&value) {
+            continue;
+        }
+
+        let target = resolved_style.get_or_insert_with(|| style.clone());
+        apply_property_to_style(target, &declaration.property, &value);
+    }
+
+    resolved_style
+}
+
+/// Returns whether a value contains a `var(...)` call.
+fn contains_var_call(value: &str) -> bool {
+    value.to_ascii_lowercase().contains("var(")
+}
+
+/// Returns the custom property names referenced by `var(...)` calls in `value`.
+fn var_call_names(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .match_indices("var(")
+        .filter_map(move |(start, call)| {
+            let rest = &value[start + call.len()..];
+            rest.split([',', ')']).next().map(str::trim)
+        })
+}
+
 /// Handles `merge_style_map` in the extended UI workflow.
 fn merge_style_map(style_map: &mut HashMap<String, StylePair>, selector: &str, style: &StylePair) {
     let key = selector_map_key(selector, style.media.as_ref());
@@ -646,7 +722,7 @@ fn selector_map_key(selector: &str, media: Option<&MediaQueryCondition>) -> Stri
 }
 
 /// Resolves a `var(...)` reference using the collected CSS variables.
-fn resolve_var(value: &str, css_vars: &HashMap<String, String>) -> String {
+pub(crate) fn resolve_var(value: &str, css_vars: &HashMap<String, String>) -> String {
     resolve_var_inner(value, css_vars, 0)
 }
 
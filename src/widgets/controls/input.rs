@@ -10,6 +10,7 @@ use std::time::Duration;
 
 use crate::html::HtmlSystemSet;
 use crate::services::image_service::get_or_load_image;
+use crate::services::style_service::pseudo_class_matches_state;
 use crate::styles::components::UiStyle;
 use crate::styles::paint::Colored;
 use crate::styles::{CssClass, CssSource, Style, TagName, font_size_to_px};
@@ -2273,14 +2274,8 @@ fn selector_matches_state_for_selection(selector: &str, state: &UIWidgetState) -
                 continue;
             }
 
-            match pseudo {
-                "read-only" if !state.readonly => return false,
-                "disabled" if !state.disabled => return false,
-                "checked" if state.disabled || !state.checked => return false,
-                "focus" if state.disabled || !state.focused => return false,
-                "hover" if state.disabled || !state.hovered => return false,
-                "invalid" if !state.invalid => return false,
-                _ => {}
+            if pseudo_class_matches_state(pseudo, state) == Some(false) {
+                return false;
             }
         }
     }
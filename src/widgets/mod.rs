@@ -81,6 +81,18 @@ pub struct UIWidgetState {
     pub checked: bool,
     pub open: bool,
     pub invalid: bool,
+    /// A `draggable` widget is dragged over this drop target.
+    pub drag_over: bool,
+    /// A pointer button is held down on this widget or one of its descendants.
+    pub pressed: bool,
+    /// The widget received focus from the keyboard.
+    pub focus_visible: bool,
+    /// The widget or one of its descendants is focused.
+    pub focus_within: bool,
+    /// The widget is an empty input with a placeholder.
+    pub placeholder_shown: bool,
+    /// The widget must be filled in before its form is submitted.
+    pub required: bool,
 }
 
 /// Component storing an optional widget controller name.
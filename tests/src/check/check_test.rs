@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod unit_tests {
+    use super::super::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_temp_dir(prefix: &str) -> PathBuf {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        std::env::temp_dir().join(format!("bevy_extended_ui_check_{prefix}_{stamp}"))
+    }
+
+    fn write_file(path: &Path, content: &str) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("mkdir parent");
+        }
+        fs::write(path, content).expect("write");
+    }
+
+    fn write_project(base: &Path, index_html: &str) -> CheckConfig {
+        let assets = base.join("assets");
+        let src = base.join("src");
+
+        write_file(
+            &assets.join("components/hud.component.rs"),
+            r#"
+            #[bevy_extended_ui_macros::ui_component]
+            const COMPONENT: Component = Component {
+                template_name: "app-hud",
+                template_file: "hud.component.html",
+                styles: ["hud.component.css"],
+            };
+
+            #[derive(Resource, Serialize)]
+            pub struct PlayerStats {
+                pub hp: u32,
+            }
+            "#,
+        );
+        write_file(
+            &assets.join("components/hud.component.html"),
+            "<div><p>{{ player_stats.hp }}</p></div>",
+        );
+        write_file(&assets.join("components/hud.component.css"), "div {}");
+        write_file(&assets.join("style.css"), "body {}");
+        write_file(
+            &src.join("main.rs"),
+            r#"
+            #[html_fn("start_game")]
+            fn start_game(In(_event): In<HtmlEvent>) {}
+            "#,
+        );
+        write_file(&assets.join("index.html"), index_html);
+
+        CheckConfig {
+            asset_root: assets,
+            source_roots: vec![src],
+            language_path: base.join("lang"),
+            ..CheckConfig::default()
+        }
+    }
+
+    #[test]
+    fn check_project_accepts_valid_project() {
+        let base = unique_temp_dir("valid");
+        let config = write_project(
+            &base,
+            r#"<html><head><meta name="main"><link rel="stylesheet" href="style.css"></head>
+<body>
+  <app-hud></app-hud>
+  <button onclick="start_game">Start</button>
+  <button onclick="$set(score, 1)">Reset</button>
+  @for(item in items) { <p>{{ item }}</p> }
+</body></html>"#,
+        );
+
+        let report = check_project(&config);
+
+        assert!(!report.has_errors(), "{:?}", report.diagnostics);
+        assert_eq!(report.checked_files, 2);
+        assert_eq!(report.warning_count(), 0);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn check_project_reports_unknown_tags_handlers_css_and_bindings() {
+        let base = unique_temp_dir("broken");
+        let config = write_project(
+            &base,
+            r#"<html><head><meta name="main"><link rel="stylesheet" href="missing.css"></head>
+<body>
+  <app-missing></app-missing>
+  <button onclick="quit_game">Quit</button>
+  <p>{{ unknown_value.name }}</p>
+  @if(true) {
+</body></html>"#,
+        );
+
+        let report = check_project(&config);
+        let messages = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>();
+
+        assert!(report.has_errors());
+        assert!(messages.iter().any(|m| m.contains("`<app-missing>`")));
+        assert!(messages.iter().any(|m| m.contains("`quit_game`")));
+        assert!(messages.iter().any(|m| m.contains("missing.css")));
+        assert!(messages.iter().any(|m| m.contains("unclosed `{`")));
+
+        let unresolved = report
+            .diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.message.contains("unknown_value"))
+            .expect("unresolved binding");
+        assert!(!unresolved.is_error());
+        assert_eq!(unresolved.line, 5);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn check_project_accepts_non_attribute_handlers_and_warns_on_unknown_ones() {
+        let base = unique_temp_dir("handlers");
+        let mut config = write_project(
+            &base,
+            r#"<html><head><meta name="main"></head>
+<body>
+  <button onclick="open_menu">Menu</button>
+  <button onclick="runtime_quit">Quit</button>
+  <button onclick="quit_game">Quit</button>
+</body></html>"#,
+        );
+        write_file(
+            &config.source_roots[0].join("menu.rs"),
+            r#"
+            inventory::submit! {
+                HtmlFnRegistration::HtmlClick {
+                    name: "open_menu",
+                    build: build_open_menu,
+                }
+            }
+            "#,
+        );
+        config.allowed_handlers.push("runtime_quit".to_string());
+
+        let report = check_project(&config);
+
+        assert!(!report.has_errors(), "{:?}", report.diagnostics);
+        assert_eq!(report.warning_count(), 1, "{:?}", report.diagnostics);
+        assert!(report.diagnostics[0].message.contains("`quit_game`"));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn check_project_locates_repeated_elements_and_entity_encoded_attributes() {
+        let base = unique_temp_dir("locations");
+        let config = write_project(
+            &base,
+            r#"<html><head><meta name="main"></head>
+<body>
+  <button onclick="start_game">Start</button>
+  <button onclick="quit_game">Quit</button>
+  <button
+      onclick="save_&quot;slot&quot;">Save</button>
+</body></html>"#,
+        );
+
+        let report = check_project(&config);
+        let location = |name: &str| {
+            let diagnostic = report
+                .diagnostics
+                .iter()
+                .find(|diagnostic| diagnostic.message.contains(name))
+                .unwrap_or_else(|| panic!("diagnostic for {name}"));
+            (diagnostic.line, diagnostic.column)
+        };
+
+        assert_eq!(location("`quit_game`"), (4, 11));
+        assert_eq!(location("`save_\"slot\"`"), (6, 7));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
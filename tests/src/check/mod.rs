@@ -0,0 +1,3 @@
+pub use beu_check::*;
+
+mod check_test;
@@ -2,13 +2,15 @@
 mod unit_tests {
     use super::super::*;
     use bevy::prelude::{App, MinimalPlugins};
-    use bevy_extended_ui::BeuStore;
-    use bevy_extended_ui::html::converter::preprocess_template_directives_with_shared;
+    use bevy_extended_ui::html::converter::{
+        preprocess_template_directives_with_shared, template_diagnostics_for_sources,
+    };
     use bevy_extended_ui::lang::{
         UiLangVariables, UiSharedValues, refresh_shared_values, serde_json::json,
     };
     use bevy_extended_ui::routing::{Router, Routes};
-    use serde::Serialize;
+    use bevy_extended_ui::{BeuStore, beu_computed};
+    use serde::{Deserialize, Serialize};
     use std::fs;
     use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -18,6 +20,30 @@ mod unit_tests {
         name: String,
     }
 
+    #[derive(BeuStore, Clone, Default, PartialEq, Serialize, Deserialize)]
+    struct Health {
+        hp: u32,
+        max_hp: u32,
+        label: String,
+    }
+
+    #[derive(BeuStore, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[beu(persist = "audio_settings.json")]
+    struct AudioSettings {
+        volume: u32,
+    }
+
+    #[beu_computed]
+    impl Health {
+        #[computed(deps = ["hp", "max_hp"])]
+        fn hp_percent(&self) -> f32 {
+            if self.max_hp == 0 {
+                return 0.0;
+            }
+            self.hp as f32 / self.max_hp as f32 * 100.0
+        }
+    }
+
     fn unique_temp_dir(prefix: &str) -> PathBuf {
         let stamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -575,6 +601,92 @@ mod unit_tests {
         let _ = fs::remove_dir_all(&base);
     }
 
+    #[test]
+    fn try_compile_framework_template_reports_router_outlet_location() {
+        let base = unique_temp_dir("router_missing_diagnostic");
+        let asset_root = base.join("assets");
+        let rust_root = base.join("src/packages");
+        write_route_component(
+            &asset_root,
+            &rust_root,
+            "main",
+            "app-main",
+            "<div>Home route</div>",
+        );
+
+        let cfg = route_test_config(&asset_root, &rust_root);
+        let mut router = Router::default();
+        router.configure(Routes::new().route("/", "app-missing"));
+        let mut cache = FrameworkCompileCache::default();
+
+        let diagnostic = try_compile_framework_template_with_router_cached(
+            "<html><head></head>\n<body>\n  <router-outlet></router-outlet>\n</body></html>",
+            "index.html",
+            &cfg,
+            Some(&router),
+            &mut cache,
+        )
+        .expect_err("missing route component must fail");
+
+        assert!(diagnostic.is_error());
+        assert_eq!(diagnostic.source_path, "index.html");
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 3));
+        assert!(diagnostic.message.contains("app-missing"));
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn template_diagnostics_point_into_inlined_component_file() {
+        let base = unique_temp_dir("component_diagnostic");
+        let asset_root = base.join("assets");
+        let rust_root = base.join("src/packages");
+        write_route_component(
+            &asset_root,
+            &rust_root,
+            "card",
+            "app-card",
+            "<div class=\"card\">\n  <p>Card</p>\n  @if (open) {\n    <span>Open</span>\n</div>",
+        );
+
+        let cfg = route_test_config(&asset_root, &rust_root);
+        let mut cache = FrameworkCompileCache::default();
+        let index = "<html><head></head>\n<body>\n  <app-card></app-card>\n</body></html>";
+        let result = try_compile_framework_template_with_router_cached(
+            index,
+            "index.html",
+            &cfg,
+            None,
+            &mut cache,
+        )
+        .expect("component compiles");
+
+        assert_eq!(result.component_sources.len(), 1);
+        let component = &result.component_sources[0];
+        assert_eq!(component.source_path, "components/card.component.html");
+
+        let mut sources = vec![("index.html", index)];
+        sources.extend(
+            result
+                .component_sources
+                .iter()
+                .map(|source| (source.source_path.as_str(), source.html.as_str())),
+        );
+        let diagnostics = template_diagnostics_for_sources(
+            &sources,
+            &UiLangVariables::default(),
+            &UiSharedValues::default(),
+            &[] as &[&str],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert!(diagnostic.is_error());
+        assert_eq!(diagnostic.source_path, "components/card.component.html");
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 14));
+        assert_eq!(diagnostic.snippet, "  @if (open) {");
+        let _ = fs::remove_dir_all(&base);
+    }
+
     #[test]
     fn compile_framework_template_panics_for_missing_keep_alive_component() {
         let base = unique_temp_dir("router_missing_keep_alive");
@@ -855,4 +967,260 @@ mod unit_tests {
         assert_eq!(store.json_path("info.items.3"), None);
         assert_eq!(store.json_path("info.value.missing"), None);
     }
+
+    #[test]
+    fn ui_binding_store_path_revisions_track_only_changed_paths() {
+        let mut store = UiBindingStore::default();
+        store.set(
+            "stats",
+            json!({"hp": 10, "name": "Ada", "pos": {"x": 1, "y": 2}}),
+        );
+        let initial = store.revision();
+        let name_revision = store.path_revision("stats.name");
+
+        assert!(store.set(
+            "stats",
+            json!({"hp": 9, "name": "Ada", "pos": {"x": 1, "y": 2}})
+        ));
+
+        assert!(store.path_revision("stats.hp") > initial);
+        assert!(store.path_revision("stats") > initial);
+        assert_eq!(store.path_revision("stats.name"), name_revision);
+        assert_eq!(store.path_revision("stats.pos.x"), name_revision);
+        assert_eq!(store.path_revision("missing.value"), 0);
+        assert_eq!(
+            store.changed_paths_since(initial),
+            vec!["stats", "stats.hp"]
+        );
+
+        assert!(store.set_path_json("stats.pos.y", json!(5)));
+        let revision = store.revision();
+        assert_eq!(store.path_revision("stats.pos"), revision);
+        assert_eq!(store.path_revision("stats.pos.y"), revision);
+        assert!(store.path_revision("stats.pos.x") < revision);
+
+        assert!(store.set("stats", json!(null)));
+        assert_eq!(store.path_revision("stats.name"), store.revision());
+    }
+
+    #[test]
+    fn ui_binding_path_changed_runs_only_for_matching_paths() {
+        use bevy::prelude::IntoScheduleConfigs;
+
+        #[derive(bevy::prelude::Resource, Default)]
+        struct Runs(u32);
+
+        let mut app = App::new();
+        app.init_resource::<UiBindingStore>();
+        app.init_resource::<Runs>();
+        app.add_systems(
+            bevy::prelude::Update,
+            (|mut runs: bevy::prelude::ResMut<Runs>| runs.0 += 1)
+                .run_if(ui_binding_path_changed("player.name")),
+        );
+
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_store(Player {
+                name: String::from("Ada"),
+            });
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, 1);
+
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set("score", 3_u32);
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, 1);
+
+        assert!(
+            app.world_mut()
+                .resource_mut::<UiBindingStore>()
+                .set_path_json("player.name", json!("Grace"))
+        );
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, 2);
+    }
+
+    #[test]
+    fn ui_binding_store_computed_fields_run_only_for_changed_deps() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        fn double_score(
+            json: &bevy_extended_ui::lang::serde_json::Value,
+        ) -> Option<bevy_extended_ui::lang::serde_json::Value> {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            Some(json!(json.get("score")?.as_u64()? * 2))
+        }
+
+        let mut store = UiBindingStore::default();
+        store.set("stats", json!({"score": 2, "name": "Ada"}));
+        assert!(store.register_computed("stats", "double", &["score"], double_score));
+        assert!(!store.register_computed("stats", "double", &["score"], double_score));
+        assert_eq!(store.json_path("stats.double"), Some(json!(4)));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        assert!(store.set_path_json("stats.name", json!("Grace")));
+        assert_eq!(store.json_path("stats.double"), Some(json!(4)));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        assert!(store.set_path_json("stats.score", json!(5)));
+        assert_eq!(store.json_path("stats.double"), Some(json!(10)));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn beu_computed_fields_are_exposed_to_templates() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ExtendedFrameworkPlugin));
+        app.update();
+
+        let mut store = app.world_mut().resource_mut::<UiBindingStore>();
+        assert_eq!(store.json_path("health.hp_percent"), Some(json!(0.0)));
+
+        store.set_store(Health {
+            hp: 25,
+            max_hp: 50,
+            label: String::from("Ada"),
+        });
+        assert_eq!(store.json_path("health.hp_percent"), Some(json!(50.0)));
+
+        assert!(store.set_path_json("health.hp", json!(50)));
+        assert_eq!(store.json_path("health.hp_percent"), Some(json!(100.0)));
+    }
+
+    #[test]
+    fn beu_persisted_store_loads_at_startup_and_saves_changes() {
+        let base = unique_temp_dir("persist");
+        write_file(&base.join("audio_settings.json"), r#"{"volume": 7}"#);
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ExtendedFrameworkPlugin));
+        let mut persistence = UiBindingPersistence::default();
+        persistence.root = base.clone();
+        persistence.debounce = std::time::Duration::ZERO;
+        app.insert_resource(persistence);
+        app.update();
+
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(
+            store
+                .get_store::<AudioSettings>()
+                .map(|settings| settings.volume),
+            Some(7)
+        );
+
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_store(AudioSettings { volume: 3 });
+        app.update();
+
+        let saved = fs::read_to_string(base.join("audio_settings.json")).expect("saved store");
+        let saved: bevy_extended_ui::lang::serde_json::Value =
+            bevy_extended_ui::lang::serde_json::from_str(&saved).expect("json");
+        assert_eq!(saved, json!({"volume": 3}));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn beu_persisted_store_retries_failed_writes() {
+        let base = unique_temp_dir("persist_retry");
+        // A directory in place of the save file makes every write fail.
+        let blocked = base.join("audio_settings.json");
+        fs::create_dir_all(&blocked).expect("mkdir blocker");
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ExtendedFrameworkPlugin));
+        let mut persistence = UiBindingPersistence::default();
+        persistence.root = base.clone();
+        persistence.debounce = std::time::Duration::ZERO;
+        app.insert_resource(persistence);
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_store(AudioSettings { volume: 9 });
+        app.update();
+        assert!(blocked.is_dir());
+
+        fs::remove_dir_all(&blocked).expect("remove blocker");
+        app.update();
+
+        let saved = fs::read_to_string(&blocked).expect("saved store after retry");
+        let saved: bevy_extended_ui::lang::serde_json::Value =
+            bevy_extended_ui::lang::serde_json::from_str(&saved).expect("json");
+        assert_eq!(saved, json!({"volume": 9}));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn ui_binding_store_history_groups_and_limits_undo_steps() {
+        let mut store = UiBindingStore::default();
+        store.set("score", 0_u32);
+        assert!(store.set_path_json("score", json!(1)));
+        assert!(!store.can_undo());
+
+        store.enable_history(2);
+        store.set_path_json("score", json!(2));
+        store.begin_group();
+        store.set_path_json("score", json!(3));
+        store.set_path_json("info.value", json!("x"));
+        store.end_group();
+        assert!(store.undo());
+        assert_eq!(store.get::<u32>("score"), Some(&2));
+        assert_eq!(store.json_path("info.value"), None);
+
+        assert!(store.undo());
+        assert_eq!(store.get::<u32>("score"), Some(&1));
+        assert!(!store.undo());
+
+        assert!(store.redo());
+        assert!(store.redo());
+        assert_eq!(store.get::<u32>("score"), Some(&3));
+        assert_eq!(store.json_path("info.value"), Some(json!("x")));
+
+        store.undo();
+        store.set_path_json("score", json!(9));
+        assert!(!store.can_redo());
+    }
+
+    #[test]
+    fn ui_binding_store_undo_removes_keys_created_by_the_edit() {
+        let mut app = App::new();
+        app.init_resource::<UiBindingStore>();
+        app.init_resource::<UiSharedValues>();
+        {
+            let mut store = app.world_mut().resource_mut::<UiBindingStore>();
+            store.enable_history(5);
+            assert!(store.set_path_json("draft.title", json!("Hello")));
+        }
+        sync_ui_binding_store_values(app.world_mut());
+        assert!(
+            app.world()
+                .resource::<UiSharedValues>()
+                .values
+                .contains_key("draft")
+        );
+
+        assert!(app.world_mut().resource_mut::<UiBindingStore>().undo());
+        sync_ui_binding_store_values(app.world_mut());
+        let store = app.world().resource::<UiBindingStore>();
+        assert!(!store.contains_key("draft"));
+        assert_eq!(store.json_path("draft.title"), None);
+        assert!(
+            !app.world()
+                .resource::<UiSharedValues>()
+                .values
+                .contains_key("draft")
+        );
+
+        let mut store = app.world_mut().resource_mut::<UiBindingStore>();
+        assert!(store.redo());
+        assert_eq!(store.json_path("draft.title"), Some(json!("Hello")));
+    }
 }
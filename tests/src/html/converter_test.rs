@@ -2,9 +2,11 @@
 mod tests {
     use super::super::converter::{
         extract_inner_bindings, parse_inner_content, preprocess_template_directives,
+        preprocess_template_directives_with_diagnostics,
         preprocess_template_directives_with_shared,
         preprocess_template_directives_with_shared_and_local_types,
     };
+    use super::super::{TemplateDiagnostic, TemplateDiagnosticSeverity};
     use crate::lang::{UiLangVariables, UiSharedValues};
     use kuchiki::traits::TendrilSink;
 
@@ -113,6 +115,73 @@ mod tests {
         assert!(!rendered.contains("@use"));
     }
 
+    #[test]
+    fn preprocess_template_directives_reads_reflected_resource_paths() {
+        let vars = UiLangVariables::default();
+        let mut shared = UiSharedValues::default();
+        shared.values.insert(
+            "res:AudioSettings".to_string(),
+            crate::lang::serde_json::from_str(r#"{"master_volume":0.5,"muted":false}"#).unwrap(),
+        );
+
+        let template = r#"
+            <slider value="{{ res:AudioSettings.master_volume }}"></slider>
+            @if(!res:AudioSettings.muted) {
+              <p>Sound On</p>
+            }
+        "#;
+
+        let rendered = preprocess_template_directives_with_shared(template, &vars, &shared);
+
+        assert!(rendered.contains(r#"value="0.5""#));
+        assert!(rendered.contains("<p>Sound On</p>"));
+    }
+
+    #[test]
+    fn preprocess_template_directives_evaluates_loop_handler_arguments() {
+        let mut vars = UiLangVariables::default();
+        vars.set(
+            "inventory",
+            r#"{"items":[{"id":7,"name":"Sword"},{"id":9,"name":"Bow \"Long\""}]}"#,
+        );
+
+        let template = r#"
+            @for(item, index in inventory.items) {
+              <button onclick="select_slot(item.id, item.name, 'primary', index, inventory.slot)">{{ item.name }}</button>
+            }
+            <button onclick="select_slot(inventory.slot)">Keep</button>
+        "#;
+
+        let rendered = preprocess_template_directives(template, &vars);
+
+        assert!(rendered.contains(
+            r#"onclick="select_slot(7, &quot;Sword&quot;, 'primary', 0, inventory.slot)""#
+        ));
+        assert!(rendered.contains(
+            r#"onclick="select_slot(9, &quot;Bow \&quot;Long\&quot;&quot;, 'primary', 1, inventory.slot)""#
+        ));
+        assert!(rendered.contains(r#"onclick="select_slot(inventory.slot)""#));
+    }
+
+    #[test]
+    fn preprocess_template_directives_evaluates_loop_drag_payloads() {
+        let mut vars = UiLangVariables::default();
+        vars.set("inventory", r#"{"items":[{"id":7},{"id":9}],"slot":2}"#);
+
+        let template = r#"
+            @for(item, index in inventory.items) {
+              <div draggable="true" data-drag="index" ondrop="swap_slots($event.payload, index)"></div>
+            }
+            <div draggable="true" data-drag="inventory.slot"></div>
+        "#;
+
+        let rendered = preprocess_template_directives(template, &vars);
+
+        assert!(rendered.contains(r#"data-drag="0" ondrop="swap_slots($event.payload, 0)""#));
+        assert!(rendered.contains(r#"data-drag="1" ondrop="swap_slots($event.payload, 1)""#));
+        assert!(rendered.contains(r#"data-drag="inventory.slot""#));
+    }
+
     #[test]
     fn preprocess_template_directives_resolves_use_with_default_alias() {
         let vars = UiLangVariables::default();
@@ -322,4 +391,79 @@ mod tests {
         assert!(rendered.contains("<p>Player Name: NetRunner</p>"));
         assert!(rendered.contains("<p>Enabled</p>"));
     }
+
+    #[test]
+    fn preprocess_template_directives_reports_unclosed_if_block_location() {
+        let vars = UiLangVariables::default();
+        let shared = UiSharedValues::default();
+        let template = "<div>\n  <p>Intro</p>\n  @if(true) {\n    <p>Never closed</p>\n</div>";
+
+        let (rendered, diagnostics) = preprocess_template_directives_with_diagnostics(
+            template,
+            &vars,
+            &shared,
+            &Vec::<String>::new(),
+            "ui/broken.html",
+        );
+
+        assert!(rendered.contains("@if(true) {"));
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, TemplateDiagnosticSeverity::Error);
+        assert_eq!(diagnostic.source_path, "ui/broken.html");
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 13));
+        assert_eq!(diagnostic.snippet, "  @if(true) {");
+        assert!(diagnostic.message.contains("unclosed `{`"));
+    }
+
+    #[test]
+    fn preprocess_template_directives_reports_nested_and_for_header_errors() {
+        let mut vars = UiLangVariables::default();
+        vars.set("items", "[1, 2]");
+        let shared = UiSharedValues::default();
+        let template = "@if(true) {\n  @for(items) { <p>x</p> }\n}";
+
+        let (_, diagnostics) = preprocess_template_directives_with_diagnostics(
+            template,
+            &vars,
+            &shared,
+            &Vec::<String>::new(),
+            "",
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 8));
+        assert!(diagnostics[0].message.contains("invalid @for header"));
+    }
+
+    #[test]
+    fn preprocess_template_directives_reports_unknown_use_target_as_warning() {
+        let vars = UiLangVariables::default();
+        let shared = UiSharedValues::default();
+        let template = "<p>Hi</p>\n@use \"Missing\";\n<p>{{ name }}</p>";
+
+        let (_, diagnostics) = preprocess_template_directives_with_diagnostics(
+            template,
+            &vars,
+            &shared,
+            &Vec::<String>::new(),
+            "ui/use.html",
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(!diagnostics[0].is_error());
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 7));
+        assert_eq!(diagnostics[0].snippet, "@use \"Missing\";");
+    }
+
+    #[test]
+    fn template_diagnostic_display_includes_location_and_snippet() {
+        let diagnostic = TemplateDiagnostic::error("index.html", "a\nbé @if(\n", 6, "unclosed");
+
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 4));
+        assert_eq!(
+            diagnostic.to_string(),
+            "index.html:2:4: error: unclosed\n    | bé @if("
+        );
+    }
 }
@@ -0,0 +1,437 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::styles::components::UiStyle;
+    use crate::styles::{CssClass, CssID, CssSource, CssVariables, Style, TagName};
+    use crate::widgets::{Button, Div, Paragraph, UIWidgetState, Widget};
+    use bevy::asset::AssetPlugin;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::prelude::*;
+
+    fn spawn_node(world: &mut World, tag: &str, id: &str, classes: &[&str]) -> Entity {
+        world
+            .spawn((
+                TagName(tag.to_string()),
+                CssID(id.to_string()),
+                CssClass(classes.iter().map(|class| class.to_string()).collect()),
+                UIWidgetState::default(),
+            ))
+            .id()
+    }
+
+    /// `body > div#inventory > (div.slot, div.row > div.slot)` plus a stray
+    /// `.slot` outside the inventory.
+    fn spawn_inventory(world: &mut World) -> [Entity; 5] {
+        let body = spawn_node(world, "body", "", &[]);
+        let inventory = spawn_node(world, "div", "inventory", &[]);
+        let first = spawn_node(world, "div", "", &["slot"]);
+        let row = spawn_node(world, "div", "", &["row"]);
+        let nested = spawn_node(world, "div", "", &["slot", "empty"]);
+        let stray = spawn_node(world, "div", "", &["slot"]);
+        world.entity_mut(body).add_children(&[inventory, stray]);
+        world.entity_mut(inventory).add_children(&[first, row]);
+        world.entity_mut(row).add_child(nested);
+        [inventory, first, row, nested, stray]
+    }
+
+    #[test]
+    fn ui_dom_query_resolves_selectors_in_document_order() {
+        let mut world = World::new();
+        let [inventory, first, _, nested, stray] = spawn_inventory(&mut world);
+
+        let result = world
+            .run_system_once(|dom: UiDom| {
+                (
+                    dom.query("#inventory .slot"),
+                    dom.query("#inventory > .slot"),
+                    dom.query(".row .slot, body > .slot"),
+                    dom.query_one(".slot.empty"),
+                    dom.by_id("inventory"),
+                    dom.by_id("missing"),
+                )
+            })
+            .expect("system runs");
+
+        assert_eq!(result.0, vec![first, nested]);
+        assert_eq!(result.1, vec![first]);
+        assert_eq!(result.2, vec![nested, stray]);
+        assert_eq!(result.3, Some(nested));
+        assert_eq!(result.4, Some(inventory));
+        assert_eq!(result.5, None);
+    }
+
+    #[test]
+    fn ui_dom_mutates_classes_state_text_and_style() {
+        let mut world = World::new();
+        let [_, first, _, nested, _] = spawn_inventory(&mut world);
+        let label = world
+            .spawn((
+                TagName("p".to_string()),
+                Paragraph::default(),
+                HtmlInnerContent::new("old", "old", vec!["count".to_string()]),
+                HtmlTextBinding {
+                    template: "{{count}}".to_string(),
+                    bindings: vec!["count".to_string()],
+                },
+            ))
+            .id();
+
+        world
+            .run_system_once(move |mut dom: UiDom| {
+                for slot in dom.query("#inventory .slot") {
+                    dom.toggle_class(slot, "empty");
+                }
+                dom.add_class(first, "selected");
+                dom.add_class(first, "selected");
+                dom.remove_class(nested, "slot");
+                dom.set_disabled(first, true);
+                dom.set_attr(nested, "checked", "");
+                dom.set_attr(nested, "id", "last");
+                dom.set_text(label, "3 items");
+                dom.set_style(label, "width", "50px");
+            })
+            .expect("system runs");
+
+        assert_eq!(
+            world.get::<CssClass>(first).unwrap().0,
+            vec!["slot", "empty", "selected"]
+        );
+        assert!(world.get::<CssClass>(nested).unwrap().0.is_empty());
+        assert!(world.get::<UIWidgetState>(first).unwrap().disabled);
+        assert!(world.get::<UIWidgetState>(nested).unwrap().checked);
+        assert_eq!(world.get::<CssID>(nested).unwrap().0, "last");
+
+        assert_eq!(world.get::<Paragraph>(label).unwrap().text, "3 items");
+        let content = world.get::<HtmlInnerContent>(label).unwrap();
+        assert_eq!(content.inner_text(), "3 items");
+        assert!(content.inner_bindings().is_empty());
+        assert!(world.get::<HtmlTextBinding>(label).is_none());
+        assert_eq!(
+            world.get::<HtmlStyle>(label).unwrap().0.width,
+            Some(Val::Px(50.0))
+        );
+    }
+
+    #[test]
+    fn ui_dom_sets_and_removes_css_variables() {
+        let mut world = World::new();
+        let [inventory, ..] = spawn_inventory(&mut world);
+
+        world
+            .run_system_once(move |mut dom: UiDom| {
+                dom.set_var(inventory, "--hp", "40%");
+                dom.set_var(inventory, "--accent", "red");
+                dom.remove_var(inventory, "--accent");
+            })
+            .expect("system runs");
+
+        let vars = &world.get::<CssVariables>(inventory).unwrap().0;
+        assert_eq!(vars.get("--hp"), Some(&"40%".to_string()));
+        assert!(!vars.contains_key("--accent"));
+    }
+
+    #[test]
+    fn ui_dom_query_resolves_sibling_combinators() {
+        let mut world = World::new();
+        let [inventory, first, row, nested, _] = spawn_inventory(&mut world);
+        let footer = spawn_node(&mut world, "p", "", &["footer"]);
+        world.entity_mut(inventory).add_child(footer);
+
+        let before = world
+            .run_system_once(|dom: UiDom| {
+                (
+                    dom.query(".slot + .row"),
+                    dom.query(".slot ~ p"),
+                    dom.query(".slot + p"),
+                    dom.query("#inventory > .slot:checked ~ .row .slot"),
+                )
+            })
+            .expect("system runs");
+        assert_eq!(before.0, vec![row]);
+        assert_eq!(before.1, vec![footer]);
+        assert!(before.2.is_empty());
+        assert!(before.3.is_empty());
+
+        world.get_mut::<UIWidgetState>(first).unwrap().checked = true;
+        let after = world
+            .run_system_once(|dom: UiDom| dom.query("#inventory > .slot:checked ~ .row .slot"))
+            .expect("system runs");
+        assert_eq!(after, vec![nested]);
+    }
+
+    #[test]
+    fn ui_dom_query_resolves_structural_and_logical_pseudo_classes() {
+        let mut world = World::new();
+        let list = spawn_node(&mut world, "div", "list", &[]);
+        let title = spawn_node(&mut world, "h1", "", &[]);
+        let rows: Vec<Entity> = (0..4)
+            .map(|index| {
+                let class = if index == 2 { "row selected" } else { "row" };
+                spawn_node(&mut world, "p", "", &class.split(' ').collect::<Vec<_>>())
+            })
+            .collect();
+        let icon = spawn_node(&mut world, "img", "", &["icon"]);
+        world.entity_mut(list).add_child(title);
+        world.entity_mut(list).add_children(&rows);
+        world.entity_mut(rows[1]).add_child(icon);
+
+        let result = world
+            .run_system_once(|dom: UiDom| {
+                (
+                    dom.query("#list > :first-child"),
+                    dom.query(".row:last-child"),
+                    dom.query(".row:nth-child(2n+1)"),
+                    dom.query("p:nth-of-type(odd)"),
+                    dom.query("p:nth-last-of-type(1)"),
+                    dom.query("h1:only-of-type, img:only-child"),
+                    dom.query(".row:empty"),
+                    dom.query(".row:not(.selected, :first-of-type)"),
+                    dom.query(":is(h1, .selected)"),
+                    dom.query(".row:where(:has(> .icon))"),
+                    dom.query("#list:has(.selected)"),
+                    dom.query(".row:has(+ .selected)"),
+                )
+            })
+            .expect("system runs");
+
+        assert_eq!(result.0, vec![title]);
+        assert_eq!(result.1, vec![rows[3]]);
+        assert_eq!(result.2, vec![rows[1], rows[3]]);
+        assert_eq!(result.3, vec![rows[0], rows[2]]);
+        assert_eq!(result.4, vec![rows[3]]);
+        assert_eq!(result.5, vec![title, icon]);
+        assert_eq!(result.6, vec![rows[0], rows[2], rows[3]]);
+        assert_eq!(result.7, vec![rows[1], rows[3]]);
+        assert_eq!(result.8, vec![title, rows[2]]);
+        assert_eq!(result.9, vec![rows[1]]);
+        assert_eq!(result.10, vec![list]);
+        assert_eq!(result.11, vec![rows[1]]);
+    }
+
+    #[test]
+    fn ui_dom_queries_and_sets_html_attributes() {
+        let mut world = World::new();
+        let [inventory, first, _, nested, _] = spawn_inventory(&mut world);
+        let mut attributes = HtmlAttributes::default();
+        attributes.set("data-rarity", "epic");
+        world.entity_mut(first).insert(attributes);
+
+        let before = world
+            .run_system_once(|dom: UiDom| {
+                (
+                    dom.query("#inventory [data-rarity]"),
+                    dom.query(".slot[data-rarity^=ep]"),
+                    dom.query("[data-rarity=rare]"),
+                )
+            })
+            .expect("system runs");
+        assert_eq!(before.0, vec![first]);
+        assert_eq!(before.1, vec![first]);
+        assert!(before.2.is_empty());
+
+        world
+            .run_system_once(move |mut dom: UiDom| {
+                dom.set_attr(first, "data-rarity", "false");
+                dom.set_attr(nested, "Data-Rarity", "rare");
+                dom.set_attr(inventory, "disabled", "");
+            })
+            .expect("system runs");
+
+        assert_eq!(
+            world
+                .get::<HtmlAttributes>(first)
+                .unwrap()
+                .get("data-rarity"),
+            Some("false")
+        );
+        assert!(world.get::<UIWidgetState>(inventory).unwrap().disabled);
+        let after = world
+            .run_system_once(|dom: UiDom| {
+                (
+                    dom.query("[data-rarity=rare]"),
+                    dom.query("div[disabled] .slot[data-rarity]"),
+                )
+            })
+            .expect("system runs");
+        assert_eq!(after.0, vec![nested]);
+        assert_eq!(after.1, vec![first, nested]);
+
+        world
+            .run_system_once(move |mut dom: UiDom| {
+                dom.set_attr(inventory, "disabled", "false");
+                dom.remove_attr(first, "data-rarity");
+            })
+            .expect("system runs");
+
+        assert!(!world.get::<UIWidgetState>(inventory).unwrap().disabled);
+        let attributes = world.get::<HtmlAttributes>(inventory).unwrap();
+        assert_eq!(attributes.get("disabled"), None);
+        let attributes = world.get::<HtmlAttributes>(first).unwrap();
+        assert_eq!(attributes.get("data-rarity"), None);
+    }
+
+    fn children_of(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Children>(entity)
+            .map(|children| children.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn ui_dom_insert_html_spawns_bound_fragment_at_position() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        let world = app.world_mut();
+        let log = world
+            .spawn((
+                TagName("div".to_string()),
+                CssID("log".to_string()),
+                CssSource::default(),
+                Widget(Some("chat".to_string())),
+            ))
+            .id();
+        let existing = spawn_node(world, "p", "", &[]);
+        world.entity_mut(log).add_child(existing);
+
+        world
+            .run_system_once(move |mut dom: UiDom| {
+                dom.insert_html(
+                    log,
+                    HtmlInsertPosition::BeforeEnd,
+                    r#"<p class="line">{{ chat.last }}</p><button onclick="ack">Ok</button>"#,
+                );
+                dom.insert_html(
+                    log,
+                    HtmlInsertPosition::AfterBegin,
+                    "<div class='first'></div>",
+                );
+            })
+            .expect("system runs");
+
+        let children = children_of(world, log);
+        assert_eq!(children.len(), 4);
+        assert!(world.get::<Div>(children[0]).is_some());
+        assert_eq!(children[1], existing);
+
+        let line = children[2];
+        assert!(world.get::<Paragraph>(line).is_some());
+        assert_eq!(world.get::<CssClass>(line).unwrap().0, vec!["line"]);
+        assert_eq!(
+            world.get::<HtmlTextBinding>(line).unwrap().template,
+            "{{ chat.last }}"
+        );
+        assert!(world.get::<HtmlID>(line).is_none());
+
+        let button = children[3];
+        assert_eq!(world.get::<Button>(button).unwrap().text, "Ok");
+        assert!(
+            world
+                .get::<HtmlEventBindings>(button)
+                .unwrap()
+                .listens("onclick")
+        );
+        assert_eq!(
+            world.get::<Widget>(button).unwrap().0.as_deref(),
+            Some("chat")
+        );
+    }
+
+    #[test]
+    fn ui_dom_remove_and_replace_by_selector() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        let world = app.world_mut();
+        let [inventory, first, row, _, stray] = spawn_inventory(world);
+
+        let counts = world
+            .run_system_once(|mut dom: UiDom| {
+                (
+                    dom.remove(".row"),
+                    dom.replace("#inventory > .slot", "<p>empty</p>"),
+                    dom.remove(".missing"),
+                )
+            })
+            .expect("system runs");
+
+        assert_eq!(counts, (1, 1, 0));
+        assert!(world.get_entity(row).is_err());
+        assert!(world.get_entity(first).is_err());
+        let children = children_of(world, inventory);
+        assert_eq!(children.len(), 1);
+        assert!(world.get::<Paragraph>(children[0]).is_some());
+        assert!(world.get_entity(stray).is_ok());
+    }
+
+    #[test]
+    fn ui_dom_serializes_widgets_to_html_and_text_snapshot() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        let world = app.world_mut();
+
+        let body = world
+            .run_system_once(|mut dom: UiDom| {
+                dom.spawn(
+                    ui::body().child(
+                        ui::div()
+                            .id("inv")
+                            .class("panel")
+                            .child(ui::p("3 < 5 items"))
+                            .child(ui::input("name", "Your name").disabled())
+                            .child(ui::button("Buy").on("onclick", "buy"))
+                            .child(ui::checkbox("Sound")),
+                    ),
+                )
+            })
+            .expect("system runs")
+            .expect("asset server exists");
+
+        let inventory = children_of(world, body)[0];
+        let [label, _, _, sound] = children_of(world, inventory)[..] else {
+            panic!("expected four widgets");
+        };
+        world.get_mut::<UIWidgetState>(sound).unwrap().checked = true;
+        world.entity_mut(label).insert(UiStyle {
+            css: Handle::default(),
+            styles: Default::default(),
+            keyframes: Default::default(),
+            active_style: Some(Style {
+                width: Some(Val::Px(20.0)),
+                ..default()
+            }),
+        });
+
+        let (html, snapshot) = world
+            .run_system_once(move |dom: UiDom| (dom.serialize(body), dom.snapshot(inventory)))
+            .expect("system runs");
+
+        assert_eq!(
+            html,
+            concat!(
+                "<body>\n",
+                "  <div id=\"inv\" class=\"panel\">\n",
+                "    <p>3 &lt; 5 items</p>\n",
+                "    <input type=\"text\" name=\"name\" placeholder=\"Your name\" disabled />\n",
+                "    <button onclick=\"buy\">Buy</button>\n",
+                "    <checkbox checked>Sound</checkbox>\n",
+                "  </div>\n",
+                "</body>\n",
+            )
+        );
+
+        let lines = snapshot.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "div#inv.panel");
+        assert_eq!(lines[1], "  p");
+        assert_eq!(lines[2], "    text: \"3 < 5 items\"");
+        assert!(lines[3].starts_with("    width: ") && lines[3].contains("Px(20.0)"));
+        assert_eq!(
+            lines[4..],
+            [
+                "  input [disabled]",
+                "  button",
+                "    text: \"Buy\"",
+                "  checkbox [checked]",
+                "    text: \"Sound\"",
+            ]
+        );
+    }
+}
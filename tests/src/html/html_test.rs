@@ -29,6 +29,7 @@ mod tests {
     use bevy_extended_ui::routing::{Router, Routes};
     #[cfg(feature = "extended-framework")]
     use std::path::{Path, PathBuf};
+    use std::time::Duration;
     #[cfg(feature = "extended-framework")]
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -92,6 +93,24 @@ mod tests {
     fn build_test_html_touchend(world: &mut World) -> SystemId<In<HtmlTouchEnd>, ()> {
         world.register_system(|In(_event): In<HtmlTouchEnd>| {})
     }
+    fn build_test_html_dblclick(world: &mut World) -> SystemId<In<HtmlDoubleClick>, ()> {
+        world.register_system(|In(_event): In<HtmlDoubleClick>| {})
+    }
+    fn build_test_html_contextmenu(world: &mut World) -> SystemId<In<HtmlContextMenu>, ()> {
+        world.register_system(|In(_event): In<HtmlContextMenu>| {})
+    }
+    fn build_test_html_longpress(world: &mut World) -> SystemId<In<HtmlLongPress>, ()> {
+        world.register_system(|In(_event): In<HtmlLongPress>| {})
+    }
+    fn build_test_html_dragenter(world: &mut World) -> SystemId<In<HtmlDragEnter>, ()> {
+        world.register_system(|In(_event): In<HtmlDragEnter>| {})
+    }
+    fn build_test_html_dragleave(world: &mut World) -> SystemId<In<HtmlDragLeave>, ()> {
+        world.register_system(|In(_event): In<HtmlDragLeave>| {})
+    }
+    fn build_test_html_drop(world: &mut World) -> SystemId<In<HtmlDrop>, ()> {
+        world.register_system(|In(_event): In<HtmlDrop>| {})
+    }
 
     inventory::submit! {
         HtmlFnRegistration::HtmlEvent {
@@ -213,6 +232,42 @@ mod tests {
             build: build_test_html_touchend,
         }
     }
+    inventory::submit! {
+        HtmlFnRegistration::HtmlDoubleClick {
+            name: "__unit_html_dblclick",
+            build: build_test_html_dblclick,
+        }
+    }
+    inventory::submit! {
+        HtmlFnRegistration::HtmlContextMenu {
+            name: "__unit_html_contextmenu",
+            build: build_test_html_contextmenu,
+        }
+    }
+    inventory::submit! {
+        HtmlFnRegistration::HtmlLongPress {
+            name: "__unit_html_longpress",
+            build: build_test_html_longpress,
+        }
+    }
+    inventory::submit! {
+        HtmlFnRegistration::HtmlDragEnter {
+            name: "__unit_html_dragenter",
+            build: build_test_html_dragenter,
+        }
+    }
+    inventory::submit! {
+        HtmlFnRegistration::HtmlDragLeave {
+            name: "__unit_html_dragleave",
+            build: build_test_html_dragleave,
+        }
+    }
+    inventory::submit! {
+        HtmlFnRegistration::HtmlDrop {
+            name: "__unit_html_drop",
+            build: build_test_html_drop,
+        }
+    }
 
     fn setup_converter_app() -> App {
         let mut app = App::new();
@@ -269,6 +324,7 @@ mod tests {
             handle: handle.clone(),
             source_id: source_id.to_string(),
             controller: controller.map(str::to_string),
+            virtual_path: None,
         });
 
         handle
@@ -521,6 +577,62 @@ mod tests {
         assert_eq!(source.get_source_path(), "examples/test.html");
     }
 
+    #[test]
+    fn html_and_css_sources_from_string_resolve_virtual_paths() {
+        let mut app = setup_converter_app();
+
+        let css = CssSource::from_string(
+            &mut app.world_mut().resource_mut::<Assets<CssAsset>>(),
+            ".price { color: gold; }",
+        );
+        assert_eq!(
+            app.world()
+                .resource::<Assets<CssAsset>>()
+                .get(&css.0[0])
+                .unwrap()
+                .text,
+            ".price { color: gold; }"
+        );
+
+        let mut source = HtmlSource::from_string(
+            &mut app.world_mut().resource_mut::<Assets<HtmlAsset>>(),
+            "mods/shop/shop.html",
+            r#"
+            <html>
+              <head>
+                <meta name="shop" />
+                <link rel="stylesheet" href="shop.css" />
+              </head>
+              <body><img src="icons/coin.png" /></body>
+            </html>
+            "#,
+        );
+        assert_eq!(source.get_source_path(), "mods/shop/shop.html");
+        source.source_id = "shop".to_string();
+        app.world_mut().spawn(source);
+
+        app.update();
+
+        let structure_map = app.world().resource::<HtmlStructureMap>();
+        let nodes = structure_map
+            .html_map
+            .get("shop")
+            .expect("expected parsed html structure");
+        let HtmlWidgetNode::Body(_, meta, _, children, _, _, _) = &nodes[0] else {
+            panic!("expected body node");
+        };
+        let css_paths: Vec<String> = meta
+            .css
+            .iter()
+            .filter_map(|handle| handle.path().map(|path| path.path().display().to_string()))
+            .collect();
+        assert_eq!(css_paths, vec!["mods/shop/shop.css"]);
+        let HtmlWidgetNode::Img(img, ..) = &children[0] else {
+            panic!("expected img node");
+        };
+        assert_eq!(img.src.as_deref(), Some("mods/shop/icons/coin.png"));
+    }
+
     #[test]
     fn register_html_fns_populates_untyped_and_typed_registry_maps() {
         let mut app = App::new();
@@ -549,6 +661,12 @@ mod tests {
         assert!(registry.touchstart.contains_key("__unit_html_event"));
         assert!(registry.touchmove.contains_key("__unit_html_event"));
         assert!(registry.touchend.contains_key("__unit_html_event"));
+        assert!(registry.dblclick.contains_key("__unit_html_event"));
+        assert!(registry.contextmenu.contains_key("__unit_html_event"));
+        assert!(registry.longpress.contains_key("__unit_html_event"));
+        assert!(registry.dragenter.contains_key("__unit_html_event"));
+        assert!(registry.dragleave.contains_key("__unit_html_event"));
+        assert!(registry.drop.contains_key("__unit_html_event"));
 
         assert!(registry.click_typed.contains_key("__unit_html_click"));
         assert!(
@@ -585,6 +703,28 @@ mod tests {
                 .contains_key("__unit_html_touchmove")
         );
         assert!(registry.touchend_typed.contains_key("__unit_html_touchend"));
+        assert!(registry.dblclick_typed.contains_key("__unit_html_dblclick"));
+        assert!(
+            registry
+                .contextmenu_typed
+                .contains_key("__unit_html_contextmenu")
+        );
+        assert!(
+            registry
+                .longpress_typed
+                .contains_key("__unit_html_longpress")
+        );
+        assert!(
+            registry
+                .dragenter_typed
+                .contains_key("__unit_html_dragenter")
+        );
+        assert!(
+            registry
+                .dragleave_typed
+                .contains_key("__unit_html_dragleave")
+        );
+        assert!(registry.drop_typed.contains_key("__unit_html_drop"));
     }
 
     #[test]
@@ -635,6 +775,146 @@ mod tests {
         assert_eq!(input_action.calls()[0].target.as_dotted(), "player.name");
     }
 
+    #[test]
+    fn converter_parses_event_modifiers() {
+        let mut app = setup_converter_app();
+
+        add_html_source(
+            &mut app,
+            "examples/event_modifiers.html",
+            r#"
+            <html>
+              <head><meta name="modifier-key" /></head>
+              <body>
+                <button onclick.once="claim_reward" onclick.capture.throttle.50ms="$add(info.clicks, 1)">Claim</button>
+                <input id="search" onchange.debounce.300ms="search($event.value)" />
+              </body>
+            </html>
+            "#,
+            "modifier-key",
+            None,
+        );
+
+        app.update();
+
+        let structure_map = app.world().resource::<HtmlStructureMap>();
+        let nodes = structure_map
+            .html_map
+            .get("modifier-key")
+            .expect("expected parsed html structure");
+        let mut all = Vec::new();
+        collect_nodes(nodes, &mut all);
+
+        let button = all
+            .iter()
+            .find_map(|node| match node {
+                HtmlWidgetNode::Button(_, _, _, bindings, _, _) => Some(bindings),
+                _ => None,
+            })
+            .expect("button bindings");
+        let input = all
+            .iter()
+            .find_map(|node| match node {
+                HtmlWidgetNode::Input(_, _, _, bindings, _, _) => Some(bindings),
+                _ => None,
+            })
+            .expect("input bindings");
+
+        assert_eq!(button.onclick.as_deref(), Some("claim_reward"));
+        assert!(button.capture.contains_key("onclick"));
+        assert_eq!(
+            button.modifiers.get("onclick"),
+            Some(&HtmlEventModifiers {
+                once: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            button.modifiers.get("onclick.capture"),
+            Some(&HtmlEventModifiers {
+                throttle: Some(Duration::from_millis(50)),
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(input.onchange.as_deref(), Some("search"));
+        assert_eq!(input.args.get("onchange").map(Vec::len), Some(1));
+        assert_eq!(
+            input.modifiers.get("onchange"),
+            Some(&HtmlEventModifiers {
+                debounce: Some(Duration::from_millis(300)),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn converter_parses_shortcuts_and_hotkeys() {
+        let mut app = setup_converter_app();
+
+        add_html_source(
+            &mut app,
+            "examples/shortcuts.html",
+            r#"
+            <html>
+              <head><meta name="shortcut-key" /></head>
+              <body>
+                <shortcut keys="Ctrl+S" action="save_game('quick')"></shortcut>
+                <shortcut keys="Escape" action="$toggle(menu.open)"></shortcut>
+                <button hotkey="Alt+Shift+1">Slot 1</button>
+              </body>
+            </html>
+            "#,
+            "shortcut-key",
+            None,
+        );
+
+        app.update();
+
+        let structure_map = app.world().resource::<HtmlStructureMap>();
+        let nodes = structure_map
+            .html_map
+            .get("shortcut-key")
+            .expect("expected parsed html structure");
+        let mut all = Vec::new();
+        collect_nodes(nodes, &mut all);
+
+        let body = all
+            .iter()
+            .find_map(|node| match node {
+                HtmlWidgetNode::Body(_, meta, ..) => meta.shortcuts.as_ref(),
+                _ => None,
+            })
+            .expect("body shortcuts");
+        assert_eq!(body.0.len(), 2);
+        assert_eq!(body.0[0].chord, HtmlKeyChord::parse("ctrl+s").unwrap());
+        assert!(body.0[0].chord.ctrl && body.0[0].chord.key == KeyCode::KeyS);
+        assert!(matches!(
+            &body.0[0].handler,
+            Some(HtmlEventHandler::Named { name, args }) if name == "save_game" && args.len() == 1
+        ));
+        assert_eq!(body.0[1].chord.key, KeyCode::Escape);
+        assert!(matches!(
+            body.0[1].handler,
+            Some(HtmlEventHandler::Inline(_))
+        ));
+
+        let button = all
+            .iter()
+            .find_map(|node| match node {
+                HtmlWidgetNode::Button(_, meta, ..) => meta.shortcuts.as_ref(),
+                _ => None,
+            })
+            .expect("button hotkey");
+        let chord = button.0[0].chord;
+        assert!(chord.alt && chord.shift && !chord.ctrl);
+        assert_eq!(chord.key, KeyCode::Digit1);
+        assert_eq!(button.0[0].handler, None);
+
+        assert!(HtmlKeyChord::parse("Ctrl+Shift").is_err());
+        assert!(HtmlKeyChord::parse("Ctrl+S+D").is_err());
+    }
+
     #[test]
     fn converter_parses_complex_html_fixture() {
         let mut app = setup_converter_app();
@@ -1188,6 +1468,7 @@ mod tests {
             handle: handle.clone(),
             source_id: "pending-ui".to_string(),
             controller: None,
+            virtual_path: None,
         });
 
         app.update();
@@ -1863,6 +2144,9 @@ mod tests {
             validation: None,
             inner_content: HtmlInnerContent::default(),
             text_binding: None,
+            draggable: None,
+            shortcuts: None,
+            attributes: HtmlAttributes::default(),
         };
         let mk_bindings = || HtmlEventBindings::default();
         let mk_widget = || Widget(None);
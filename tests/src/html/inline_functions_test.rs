@@ -3,15 +3,29 @@ mod tests {
     use super::super::*;
     use crate::framework::UiBindingStore;
     use crate::widgets::{
-        CheckBox, ChoiceBox, ChoiceOption, ColorPicker, DatePicker, FieldSelectionMulti,
+        Body, CheckBox, ChoiceBox, ChoiceOption, ColorPicker, DatePicker, FieldSelectionMulti,
         FieldSelectionSingle, InputValue, ListBox, ProgressBar, RadioButton, Slider, SwitchButton,
         ToggleButton, WidgetValue,
     };
+    use bevy::camera::NormalizedRenderTarget;
+    use bevy::picking::backend::HitData;
+    use bevy::picking::pointer::{Location, PointerButton, PointerId};
     use bevy::prelude::*;
     use bevy_extended_ui::html::inline_functions::{HtmlInlineExpr, HtmlInlinePath};
+    use bevy_extended_ui::html::reflect_binding::{
+        read_reflect_path, read_reflect_resource_json, write_reflect_path,
+    };
     use bevy_extended_ui::lang::UiSharedValues;
     use bevy_extended_ui::lang::serde_json::{Value as JsonValue, json};
 
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct ReflectedAudio {
+        master_volume: f32,
+        steps: u32,
+        label: String,
+    }
+
     fn setup_inline_app() -> App {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
@@ -21,6 +35,16 @@ mod tests {
         app
     }
 
+    /// `app.update()` runs every binding system, so they need their inputs.
+    fn setup_updating_inline_app() -> App {
+        let mut app = setup_inline_app();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<bevy_extended_ui::CurrentWidgetState>();
+        app.init_resource::<HtmlInitDelay>();
+        app.add_message::<HtmlAllWidgetsVisible>();
+        app
+    }
+
     fn setup_inline_app_without_store() -> App {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
@@ -44,6 +68,7 @@ mod tests {
         app.world_mut().trigger(HtmlChange {
             entity,
             action: HtmlChangeAction::State,
+            propagation: default(),
         });
         app.world_mut().flush();
     }
@@ -96,8 +121,14 @@ mod tests {
             "$unknown(info.value, 1)",
             "$set(info.value)",
             "$set(.value, 1)",
+            "$undo(info.value)",
             "$set(info.value, $event.)",
             "$set(info.value, 1 + 2)",
+            "$toggle()",
+            "$clamp(info.value, 1)",
+            "$call()",
+            "$call(1, 2)",
+            "$emit(info.value + 1)",
         ] {
             assert!(
                 parse_html_inline_action(invalid).is_err(),
@@ -586,4 +617,929 @@ mod tests {
 
         trigger_change(&mut app, entity);
     }
+
+    #[test]
+    fn inline_undo_and_redo_revert_whole_actions() {
+        let mut app = setup_inline_app();
+        {
+            let mut store = app.world_mut().resource_mut::<UiBindingStore>();
+            store.set_path_json("info.value", json!(1));
+            store.enable_history(10);
+        }
+        let edit = app
+            .world_mut()
+            .spawn(inline_change_binding(
+                "$set(info.value, 5); $set(info.label, 'five')",
+            ))
+            .id();
+        let undo = app.world_mut().spawn(inline_change_binding("$undo()")).id();
+        let redo = app.world_mut().spawn(inline_change_binding("$redo()")).id();
+
+        trigger_change(&mut app, edit);
+        trigger_change(&mut app, undo);
+        {
+            let store = app.world().resource::<UiBindingStore>();
+            assert_eq!(store.json_path("info.value"), Some(json!(1)));
+            assert_eq!(store.json_path("info.label"), None);
+            assert!(store.can_redo());
+        }
+
+        trigger_change(&mut app, redo);
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("info.value"), Some(json!(5)));
+        assert_eq!(store.json_path("info.label"), Some(json!("five")));
+        assert!(!store.can_redo());
+    }
+
+    #[test]
+    fn parses_reflected_resource_targets() {
+        let action =
+            parse_html_inline_action("$set(res:AudioSettings.master_volume, $event.value)")
+                .unwrap();
+        let target = &action.calls()[0].target;
+        assert_eq!(target.root(), "res:AudioSettings");
+        assert_eq!(target.as_dotted(), "res:AudioSettings.master_volume");
+
+        assert!(parse_html_inline_action("$set(info.res:value, 1)").is_err());
+    }
+
+    #[test]
+    fn inline_functions_write_reflected_resources() {
+        let mut app = setup_inline_app();
+        app.register_type::<ReflectedAudio>();
+        app.init_resource::<ReflectedAudio>();
+        let entity = app
+            .world_mut()
+            .spawn(inline_change_binding(
+                "$set(res:ReflectedAudio.master_volume, 0.25); \
+                 $add(res:ReflectedAudio.steps, 2); \
+                 $set(res:ReflectedAudio.label, 'loud')",
+            ))
+            .id();
+
+        trigger_change(&mut app, entity);
+        trigger_change(&mut app, entity);
+
+        let audio = app.world().resource::<ReflectedAudio>();
+        assert_eq!(audio.master_volume, 0.25);
+        assert_eq!(audio.steps, 4);
+        assert_eq!(audio.label, "loud");
+        assert_eq!(
+            app.world()
+                .resource::<UiBindingStore>()
+                .json_path("res:ReflectedAudio"),
+            None
+        );
+    }
+
+    #[test]
+    fn reflect_paths_read_and_write_resources_by_type_name() {
+        let mut app = setup_inline_app();
+        app.register_type::<ReflectedAudio>();
+        app.insert_resource(ReflectedAudio {
+            master_volume: 0.5,
+            steps: 3,
+            label: String::from("mid"),
+        });
+        let world = app.world_mut();
+
+        assert_eq!(
+            read_reflect_resource_json(world, "ReflectedAudio"),
+            Some(json!({"master_volume": 0.5, "steps": 3, "label": "mid"}))
+        );
+        assert_eq!(
+            read_reflect_path(world, "res:ReflectedAudio.steps"),
+            Some(json!(3))
+        );
+        assert!(write_reflect_path(
+            world,
+            "res:ReflectedAudio.steps",
+            json!("7")
+        ));
+        assert!(!write_reflect_path(
+            world,
+            "res:ReflectedAudio.missing",
+            json!(1)
+        ));
+        assert!(!write_reflect_path(
+            world,
+            "res:UnknownResource.steps",
+            json!(1)
+        ));
+        assert_eq!(world.resource::<ReflectedAudio>().steps, 7);
+
+        world.remove_resource::<ReflectedAudio>();
+        assert_eq!(read_reflect_path(world, "res:ReflectedAudio.steps"), None);
+    }
+
+    #[test]
+    fn parses_collection_and_side_effect_functions() {
+        let action = parse_html_inline_action(
+            "$toggle(menu.open); $clamp(info.value, 0, 10); $call(select_slot, item.id, 'primary')",
+        )
+        .unwrap();
+        let calls = action.calls();
+        assert_eq!(calls[0].function, HtmlInlineFunction::Toggle);
+        assert_eq!(calls[0].target.as_dotted(), "menu.open");
+        assert_eq!(calls[0].value, HtmlInlineExpr::Literal(JsonValue::Null));
+        assert_eq!(calls[1].function, HtmlInlineFunction::Clamp);
+        assert_eq!(calls[1].value, HtmlInlineExpr::Literal(json!(0)));
+        assert_eq!(calls[2].function, HtmlInlineFunction::Call);
+        assert!(calls[2].target.is_empty());
+        assert_eq!(
+            calls[2].args[0],
+            HtmlInlineExpr::Literal(json!("select_slot"))
+        );
+        assert_eq!(calls[2].args.len(), 3);
+    }
+
+    #[test]
+    fn inline_toggle_push_remove_and_clamp_update_store_values() {
+        let mut app = setup_inline_app();
+        {
+            let mut store = app.world_mut().resource_mut::<UiBindingStore>();
+            store.set_path_json("menu.open", json!(false));
+            store.set_path_json("cart.items", json!([1, 2, 3]));
+            store.set_path_json("info.value", json!(42));
+        }
+        let entity = app
+            .world_mut()
+            .spawn(inline_change_binding(
+                "$toggle(menu.open); $push(cart.items, 4); $remove(cart.items, 2); \
+                 $push(cart.tags, 'new'); $clamp(info.value, 0, 10)",
+            ))
+            .id();
+
+        trigger_change(&mut app, entity);
+
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("menu.open"), Some(json!(true)));
+        assert_eq!(store.json_path("cart.items"), Some(json!([1, 3, 4])));
+        assert_eq!(store.json_path("cart.tags"), Some(json!(["new"])));
+        assert_eq!(store.json_path("info.value"), Some(json!(10.0)));
+    }
+
+    #[derive(Resource, Default)]
+    struct CalledWith(Vec<(Entity, Vec<JsonValue>)>);
+
+    fn record_call(
+        In(event): In<HtmlEvent>,
+        args: Res<HtmlEventArgs>,
+        mut calls: ResMut<CalledWith>,
+    ) {
+        calls.0.push((event.entity, args.values().to_vec()));
+    }
+
+    #[test]
+    fn inline_call_emit_and_navigate_reach_handlers_messages_and_router() {
+        let mut app = setup_inline_app();
+        app.init_resource::<CalledWith>();
+        app.init_resource::<bevy_extended_ui::routing::Router>();
+        let system = app.world_mut().register_system(record_call);
+        app.world_mut()
+            .resource_mut::<HtmlFunctionRegistry>()
+            .click
+            .insert("select_slot".to_string(), system);
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_path_json("item.id", json!(7));
+        let entity = app
+            .world_mut()
+            .spawn(inline_change_binding(
+                "$call(select_slot, item.id, 'primary'); $emit('saved', item.id); \
+                 $navigate('/settings')",
+            ))
+            .id();
+
+        trigger_change(&mut app, entity);
+
+        let calls = &app.world().resource::<CalledWith>().0;
+        assert_eq!(calls, &vec![(entity, vec![json!(7), json!("primary")])]);
+        assert!(app.world().resource::<HtmlEventArgs>().values().is_empty());
+
+        let emitted = app
+            .world_mut()
+            .resource_mut::<Messages<HtmlEmit>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            emitted,
+            vec![HtmlEmit {
+                entity,
+                name: "saved".to_string(),
+                value: json!(7),
+            }]
+        );
+        assert_eq!(
+            app.world()
+                .resource::<bevy_extended_ui::routing::Router>()
+                .current_path(),
+            "/settings"
+        );
+    }
+
+    #[test]
+    fn parses_named_handler_calls() {
+        let (name, args) = parse_html_handler_call("select_slot(3, 'primary', item.id)").unwrap();
+        assert_eq!(name, "select_slot");
+        assert_eq!(
+            args,
+            vec![
+                HtmlInlineExpr::Literal(json!(3)),
+                HtmlInlineExpr::Literal(json!("primary")),
+                HtmlInlineExpr::Path(
+                    HtmlInlinePath::new(vec!["item".to_string(), "id".to_string()]).unwrap()
+                ),
+            ]
+        );
+
+        assert_eq!(
+            parse_html_handler_call("select_slot").unwrap(),
+            ("select_slot".to_string(), Vec::new())
+        );
+        assert!(parse_html_handler_call("select slot(1)").is_err());
+        assert!(parse_html_handler_call("select_slot(1").is_err());
+    }
+
+    #[test]
+    fn named_handlers_receive_call_arguments() {
+        let mut app = setup_inline_app();
+        app.init_resource::<CalledWith>();
+        let system = app.world_mut().register_system(record_call);
+        app.world_mut()
+            .resource_mut::<HtmlFunctionRegistry>()
+            .click
+            .insert("select_slot".to_string(), system);
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_path_json("info.value", json!(5));
+
+        let (name, args) = parse_html_handler_call("select_slot(3, info.value)").unwrap();
+        let entity = app
+            .world_mut()
+            .spawn(HtmlEventBindings {
+                onclick: Some(name),
+                args: [("onclick".to_string(), args)].into_iter().collect(),
+                ..Default::default()
+            })
+            .id();
+
+        app.world_mut().trigger(HtmlClick {
+            entity,
+            position: Vec2::ZERO,
+            inner_position: Vec2::ZERO,
+            propagation: default(),
+        });
+        app.world_mut().flush();
+
+        let calls = &app.world().resource::<CalledWith>().0;
+        assert_eq!(calls, &vec![(entity, vec![json!(3), json!(5)])]);
+        assert!(app.world().resource::<HtmlEventArgs>().values().is_empty());
+    }
+
+    #[test]
+    fn inline_call_finds_handlers_registered_for_any_event() {
+        let mut app = setup_inline_app();
+        app.init_resource::<CalledWith>();
+        let system = app.world_mut().register_system(record_call);
+        app.world_mut()
+            .resource_mut::<HtmlFunctionRegistry>()
+            .keyup
+            .insert("on_key".to_string(), system);
+        let entity = app
+            .world_mut()
+            .spawn(inline_change_binding("$call(on_key, 1); $call(missing)"))
+            .id();
+
+        trigger_change(&mut app, entity);
+
+        let calls = &app.world().resource::<CalledWith>().0;
+        assert_eq!(calls, &vec![(entity, vec![json!(1)])]);
+    }
+
+    fn trigger_click(app: &mut App, entity: Entity) {
+        app.world_mut().trigger(HtmlClick {
+            entity,
+            position: Vec2::ZERO,
+            inner_position: Vec2::ZERO,
+            propagation: default(),
+        });
+        app.world_mut().flush();
+    }
+
+    fn named_click(name: &str) -> HtmlEventBindings {
+        HtmlEventBindings {
+            onclick: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct ClickLog(Vec<(Entity, Entity, HtmlEventPhase)>);
+
+    fn log_click(
+        In(click): In<HtmlClick>,
+        propagation: Res<HtmlEventPropagation>,
+        mut log: ResMut<ClickLog>,
+    ) {
+        log.0.push((
+            click.entity,
+            propagation.current_target().unwrap(),
+            propagation.phase().unwrap(),
+        ));
+    }
+
+    fn log_click_and_stop(
+        In(click): In<HtmlClick>,
+        mut propagation: ResMut<HtmlEventPropagation>,
+        mut log: ResMut<ClickLog>,
+    ) {
+        log.0.push((
+            click.entity,
+            propagation.current_target().unwrap(),
+            propagation.phase().unwrap(),
+        ));
+        propagation.stop_propagation();
+    }
+
+    fn stop_click(In(click): In<HtmlClick>, mut log: ResMut<ClickLog>) {
+        log.0
+            .push((click.entity, click.entity, HtmlEventPhase::AtTarget));
+        click.stop_propagation();
+    }
+
+    fn setup_click_log_app() -> App {
+        let mut app = setup_inline_app();
+        app.init_resource::<ClickLog>();
+        let log = app.world_mut().register_system(log_click);
+        let stop = app.world_mut().register_system(log_click_and_stop);
+        let stop_event = app.world_mut().register_system(stop_click);
+        let mut registry = app.world_mut().resource_mut::<HtmlFunctionRegistry>();
+        registry.click_typed.insert("log".to_string(), log);
+        registry.click_typed.insert("stop".to_string(), stop);
+        registry
+            .click_typed
+            .insert("stop_event".to_string(), stop_event);
+        app
+    }
+
+    #[test]
+    fn click_bubbles_from_target_to_ancestors() {
+        let mut app = setup_click_log_app();
+        let root = app.world_mut().spawn(named_click("log")).id();
+        let middle = app
+            .world_mut()
+            .spawn((HtmlEventBindings::default(), ChildOf(root)))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((named_click("log"), ChildOf(middle)))
+            .id();
+
+        trigger_click(&mut app, target);
+
+        assert_eq!(
+            app.world().resource::<ClickLog>().0,
+            vec![
+                (target, target, HtmlEventPhase::AtTarget),
+                (target, root, HtmlEventPhase::Bubble),
+            ]
+        );
+    }
+
+    #[test]
+    fn stop_propagation_keeps_ancestors_from_running() {
+        let mut app = setup_click_log_app();
+        let root = app.world_mut().spawn(named_click("log")).id();
+        let target = app
+            .world_mut()
+            .spawn((named_click("stop"), ChildOf(root)))
+            .id();
+
+        trigger_click(&mut app, target);
+
+        assert_eq!(
+            app.world().resource::<ClickLog>().0,
+            vec![(target, target, HtmlEventPhase::AtTarget)]
+        );
+    }
+
+    #[test]
+    fn stop_propagation_on_the_typed_event_keeps_ancestors_from_running() {
+        let mut app = setup_click_log_app();
+        let root = app.world_mut().spawn(named_click("log")).id();
+        let target = app
+            .world_mut()
+            .spawn((named_click("stop_event"), ChildOf(root)))
+            .id();
+
+        trigger_click(&mut app, target);
+        trigger_click(&mut app, target);
+
+        assert_eq!(
+            app.world().resource::<ClickLog>().0,
+            vec![(target, target, HtmlEventPhase::AtTarget); 2]
+        );
+    }
+
+    #[test]
+    fn capture_handlers_run_root_first_before_bubbling() {
+        let mut app = setup_click_log_app();
+        let mut root_bindings = named_click("log");
+        root_bindings.capture.insert(
+            "onclick".to_string(),
+            HtmlEventHandler::Named {
+                name: "log".to_string(),
+                args: Vec::new(),
+            },
+        );
+        let root = app.world_mut().spawn(root_bindings).id();
+        let target = app
+            .world_mut()
+            .spawn((named_click("log"), ChildOf(root)))
+            .id();
+
+        trigger_click(&mut app, target);
+
+        assert_eq!(
+            app.world().resource::<ClickLog>().0,
+            vec![
+                (target, root, HtmlEventPhase::Capture),
+                (target, target, HtmlEventPhase::AtTarget),
+                (target, root, HtmlEventPhase::Bubble),
+            ]
+        );
+    }
+
+    fn log_untyped(
+        In(event): In<HtmlEvent>,
+        propagation: Res<HtmlEventPropagation>,
+        mut log: ResMut<ClickLog>,
+    ) {
+        log.0.push((
+            event.entity,
+            propagation.current_target().unwrap(),
+            propagation.phase().unwrap(),
+        ));
+    }
+
+    #[test]
+    fn untyped_handlers_see_the_same_target_as_typed_ones() {
+        let mut app = setup_click_log_app();
+        let untyped = app.world_mut().register_system(log_untyped);
+        app.world_mut()
+            .resource_mut::<HtmlFunctionRegistry>()
+            .click
+            .insert("untyped".to_string(), untyped);
+        let root = app.world_mut().spawn(named_click("untyped")).id();
+        let target = app
+            .world_mut()
+            .spawn((named_click("log"), ChildOf(root)))
+            .id();
+
+        trigger_click(&mut app, target);
+
+        assert_eq!(
+            app.world().resource::<ClickLog>().0,
+            vec![
+                (target, target, HtmlEventPhase::AtTarget),
+                (target, root, HtmlEventPhase::Bubble),
+            ]
+        );
+        assert_eq!(
+            app.world().resource::<HtmlEventPropagation>().target(),
+            None
+        );
+    }
+
+    #[test]
+    fn bubbled_inline_actions_read_event_from_target() {
+        let mut app = setup_inline_app();
+        let root = app
+            .world_mut()
+            .spawn(inline_change_binding("$set(info.value, $event.value)"))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((
+                HtmlEventBindings::default(),
+                InputValue("Ada".to_string()),
+                ChildOf(root),
+            ))
+            .id();
+
+        trigger_change(&mut app, target);
+
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("info.value"), Some(json!("Ada")));
+    }
+
+    fn press(app: &mut App, entity: Entity, button: PointerButton) {
+        app.world_mut().entity_mut(entity).trigger(|entity| {
+            Pointer::new(
+                PointerId::Mouse,
+                Location {
+                    target: NormalizedRenderTarget::None {
+                        width: 800,
+                        height: 600,
+                    },
+                    position: Vec2::ZERO,
+                },
+                Press {
+                    button,
+                    count: 1,
+                    hit: HitData {
+                        camera: Entity::PLACEHOLDER,
+                        depth: 0.0,
+                        position: None,
+                        normal: None,
+                        extra: None,
+                    },
+                },
+                entity,
+            )
+        });
+        app.world_mut().flush();
+    }
+
+    #[test]
+    fn long_press_fires_once_the_delay_passed() {
+        let mut app = setup_updating_inline_app();
+        app.insert_resource(HtmlLongPressDelay(std::time::Duration::ZERO));
+        let entity = app
+            .world_mut()
+            .spawn(HtmlEventBindings {
+                inline: HtmlInlineEventBindings {
+                    onlongpress: Some(parse_html_inline_action("$toggle(menu.open)").unwrap()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+
+        press(&mut app, entity, PointerButton::Secondary);
+        app.update();
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("menu.open"), None);
+
+        press(&mut app, entity, PointerButton::Primary);
+        app.update();
+        app.update();
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("menu.open"), Some(json!(true)));
+    }
+
+    fn click(app: &mut App, entity: Entity, button: PointerButton) {
+        app.world_mut().entity_mut(entity).trigger(|entity| {
+            Pointer::new(
+                PointerId::Mouse,
+                Location {
+                    target: NormalizedRenderTarget::None {
+                        width: 800,
+                        height: 600,
+                    },
+                    position: Vec2::ZERO,
+                },
+                Click {
+                    button,
+                    hit: HitData {
+                        camera: Entity::PLACEHOLDER,
+                        depth: 0.0,
+                        position: None,
+                        normal: None,
+                        extra: None,
+                    },
+                    duration: std::time::Duration::ZERO,
+                    count: 1,
+                },
+                entity,
+            )
+        });
+        app.world_mut().flush();
+    }
+
+    #[test]
+    fn onclick_runs_for_the_primary_button_only() {
+        let mut app = setup_inline_app();
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_path_json("counter.clicks", json!(0));
+        let entity = app
+            .world_mut()
+            .spawn(HtmlEventBindings {
+                inline: HtmlInlineEventBindings {
+                    onclick: Some(parse_html_inline_action("$add(counter.clicks, 1)").unwrap()),
+                    oncontextmenu: Some(parse_html_inline_action("$toggle(menu.open)").unwrap()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+
+        click(&mut app, entity, PointerButton::Primary);
+        click(&mut app, entity, PointerButton::Secondary);
+        click(&mut app, entity, PointerButton::Middle);
+
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("counter.clicks"), Some(json!(1.0)));
+        assert_eq!(store.json_path("menu.open"), Some(json!(true)));
+    }
+
+    #[test]
+    fn event_modifiers_limit_how_often_handlers_run() {
+        let mut app = setup_updating_inline_app();
+        let mut once = inline_change_binding("$add(counter.once, 1)");
+        once.modifiers.insert(
+            "onchange".to_string(),
+            HtmlEventModifiers {
+                once: true,
+                ..Default::default()
+            },
+        );
+        let mut throttled = inline_change_binding("$add(counter.throttled, 1)");
+        throttled.modifiers.insert(
+            "onchange".to_string(),
+            HtmlEventModifiers {
+                throttle: Some(std::time::Duration::from_secs(60)),
+                ..Default::default()
+            },
+        );
+        let mut debounced = inline_change_binding("$add(counter.debounced, 1)");
+        debounced.modifiers.insert(
+            "onchange".to_string(),
+            HtmlEventModifiers {
+                debounce: Some(std::time::Duration::ZERO),
+                ..Default::default()
+            },
+        );
+        let once = app.world_mut().spawn(once).id();
+        let throttled = app.world_mut().spawn(throttled).id();
+        let debounced = app.world_mut().spawn(debounced).id();
+        {
+            let mut store = app.world_mut().resource_mut::<UiBindingStore>();
+            store.set_path_json("counter.once", json!(0));
+            store.set_path_json("counter.throttled", json!(0));
+            store.set_path_json("counter.debounced", json!(0));
+        }
+
+        for entity in [once, throttled, debounced] {
+            trigger_change(&mut app, entity);
+            trigger_change(&mut app, entity);
+        }
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("counter.once"), Some(json!(1.0)));
+        assert_eq!(store.json_path("counter.throttled"), Some(json!(1.0)));
+        assert_eq!(store.json_path("counter.debounced"), Some(json!(0)));
+
+        app.update();
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("counter.debounced"), Some(json!(1.0)));
+    }
+
+    #[test]
+    fn event_modifier_state_is_cleared_with_the_bindings() {
+        let mut app = setup_inline_app();
+        let mut once = inline_change_binding("$add(counter.once, 1)");
+        once.modifiers.insert(
+            "onchange".to_string(),
+            HtmlEventModifiers {
+                once: true,
+                ..Default::default()
+            },
+        );
+        app.world_mut()
+            .resource_mut::<UiBindingStore>()
+            .set_path_json("counter.once", json!(0));
+        let entity = app.world_mut().spawn(once.clone()).id();
+
+        trigger_change(&mut app, entity);
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<HtmlEventBindings>()
+            .insert(once);
+        trigger_change(&mut app, entity);
+
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("counter.once"), Some(json!(2.0)));
+    }
+
+    #[test]
+    fn debounced_handlers_still_read_the_event_payload() {
+        let mut app = setup_updating_inline_app();
+        let slot = app
+            .world_mut()
+            .spawn(HtmlEventBindings {
+                inline: HtmlInlineEventBindings {
+                    ondrop: Some(
+                        parse_html_inline_action("$set(inventory.dropped, $event.payload)")
+                            .unwrap(),
+                    ),
+                    ..Default::default()
+                },
+                modifiers: [(
+                    "ondrop".to_string(),
+                    HtmlEventModifiers {
+                        debounce: Some(std::time::Duration::ZERO),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            })
+            .id();
+        let source = app.world_mut().spawn_empty().id();
+
+        app.world_mut().trigger(HtmlDrop {
+            entity: slot,
+            source,
+            payload: json!({"slot": 5}),
+            position: Vec2::ZERO,
+            propagation: default(),
+        });
+        app.world_mut().flush();
+        assert_eq!(
+            app.world()
+                .resource::<UiBindingStore>()
+                .json_path("inventory.dropped"),
+            None
+        );
+
+        app.update();
+        assert_eq!(
+            app.world()
+                .resource::<UiBindingStore>()
+                .json_path("inventory.dropped"),
+            Some(json!({"slot": 5}))
+        );
+    }
+
+    #[test]
+    fn shortcuts_run_without_a_focused_widget() {
+        let mut app = setup_updating_inline_app();
+        app.world_mut().spawn(HtmlShortcuts(vec![HtmlShortcut {
+            chord: HtmlKeyChord::parse("Ctrl+S").unwrap(),
+            handler: Some(HtmlEventHandler::Inline(
+                parse_html_inline_action("$toggle(game.saved)").unwrap(),
+            )),
+        }]));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyS);
+        app.update();
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("game.saved"), None);
+
+        {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.reset_all();
+            keyboard.press(KeyCode::ControlLeft);
+            keyboard.press(KeyCode::KeyS);
+        }
+        app.update();
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("game.saved"), Some(json!(true)));
+    }
+
+    #[test]
+    fn shortcuts_shared_by_two_pages_run_on_the_top_page_only() {
+        let mut app = setup_updating_inline_app();
+        app.init_resource::<HtmlStructureMap>();
+        for (page, store_key) in [
+            ("menu", "$toggle(menu.saved)"),
+            ("hud", "$toggle(hud.saved)"),
+        ] {
+            let body = app
+                .world_mut()
+                .spawn(Body {
+                    html_key: Some(page.to_string()),
+                    ..Default::default()
+                })
+                .id();
+            app.world_mut().spawn((
+                HtmlShortcuts(vec![HtmlShortcut {
+                    chord: HtmlKeyChord::parse("Ctrl+S").unwrap(),
+                    handler: Some(HtmlEventHandler::Inline(
+                        parse_html_inline_action(store_key).unwrap(),
+                    )),
+                }]),
+                ChildOf(body),
+            ));
+        }
+        app.world_mut().resource_mut::<HtmlStructureMap>().active =
+            Some(vec!["menu".to_string(), "hud".to_string()]);
+
+        {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.press(KeyCode::ControlLeft);
+            keyboard.press(KeyCode::KeyS);
+        }
+        app.update();
+
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(store.json_path("hud.saved"), Some(json!(true)));
+        assert_eq!(store.json_path("menu.saved"), None);
+    }
+
+    #[test]
+    fn drop_handlers_read_the_drag_payload() {
+        let mut app = setup_inline_app();
+        app.init_resource::<CalledWith>();
+        let system = app.world_mut().register_system(record_call);
+        app.world_mut()
+            .resource_mut::<HtmlFunctionRegistry>()
+            .drop
+            .insert("swap_slots".to_string(), system);
+
+        let slot = app
+            .world_mut()
+            .spawn(HtmlEventBindings {
+                ondrop: Some("swap_slots".to_string()),
+                args: [(
+                    "ondrop".to_string(),
+                    vec![
+                        parse_html_inline_expr("$event.payload.slot").unwrap(),
+                        parse_html_inline_expr("3").unwrap(),
+                    ],
+                )]
+                .into(),
+                ..Default::default()
+            })
+            .id();
+        app.world_mut()
+            .spawn(HtmlEventBindings {
+                inline: HtmlInlineEventBindings {
+                    ondrop: Some(
+                        parse_html_inline_action("$set(inventory.dropped, $event.payload)")
+                            .unwrap(),
+                    ),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .add_child(slot);
+        let source = app.world_mut().spawn_empty().id();
+
+        let drop = HtmlDrop {
+            entity: slot,
+            source,
+            payload: json!({"slot": 5}),
+            position: Vec2::ZERO,
+            propagation: default(),
+        };
+        assert_eq!(drop.payload_as::<JsonValue>(), Some(json!({"slot": 5})));
+        app.world_mut().trigger(drop);
+        app.world_mut().flush();
+
+        assert_eq!(
+            app.world().resource::<CalledWith>().0,
+            vec![(slot, vec![json!(5), json!(3)])]
+        );
+        let store = app.world().resource::<UiBindingStore>();
+        assert_eq!(
+            store.json_path("inventory.dropped"),
+            Some(json!({"slot": 5}))
+        );
+    }
+
+    #[test]
+    fn despawning_the_drag_source_removes_its_ghost() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HtmlDragDropPlugin));
+        let source = app.world_mut().spawn(HtmlDraggable::default()).id();
+
+        app.world_mut().entity_mut(source).trigger(|entity| {
+            Pointer::new(
+                PointerId::Mouse,
+                Location {
+                    target: NormalizedRenderTarget::None {
+                        width: 800,
+                        height: 600,
+                    },
+                    position: Vec2::ZERO,
+                },
+                DragStart {
+                    button: PointerButton::Primary,
+                    hit: HitData {
+                        camera: Entity::PLACEHOLDER,
+                        depth: 0.0,
+                        position: None,
+                        normal: None,
+                        extra: None,
+                    },
+                },
+                entity,
+            )
+        });
+        app.world_mut().flush();
+        let mut ghosts = app
+            .world_mut()
+            .query_filtered::<Entity, With<HtmlDragGhost>>();
+        assert_eq!(ghosts.iter(app.world()).count(), 1);
+
+        app.world_mut().despawn(source);
+        app.update();
+
+        assert_eq!(ghosts.iter(app.world()).count(), 0);
+    }
 }
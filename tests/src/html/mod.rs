@@ -6,5 +6,8 @@ pub use bevy_extended_ui::html::converter;
 pub use bevy_extended_ui::html::reload;
 
 mod converter_test;
+mod dom_test;
 mod html_test;
 mod inline_functions_test;
+mod reflect_form_test;
+mod ui_test;
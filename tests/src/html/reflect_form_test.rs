@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use bevy::prelude::*;
+    use bevy::reflect::TypeRegistry;
+    use bevy_extended_ui::lang::serde_json::json;
+
+    #[derive(Reflect, Default, Clone, Copy, PartialEq, Debug)]
+    enum Quality {
+        #[default]
+        Low,
+        HighQuality,
+    }
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct GraphicsSettings {
+        vsync: bool,
+        #[reflect(@0.0..=1.0_f32)]
+        brightness: f32,
+        fps_limit: u32,
+        profile_name: String,
+        quality: Quality,
+        tint: Color,
+        #[reflect(ignore)]
+        _cache: Vec<u8>,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<GraphicsSettings>();
+        registry.register::<Quality>();
+        registry
+    }
+
+    #[test]
+    fn expand_reflect_forms_generates_bound_widgets_per_field() {
+        let html = r#"<body><reflect-form type="GraphicsSettings" bind="res:GraphicsSettings" class="panel"></reflect-form></body>"#;
+        let expanded = expand_reflect_forms(html, &registry());
+
+        assert!(!expanded.contains("<reflect-form"));
+        assert!(expanded.contains(r#"<div class="reflect-form panel">"#));
+        assert!(expanded.contains(
+            r#"<switch value="{{ res:GraphicsSettings.vsync }}" onchange="$set(res:GraphicsSettings.vsync, $event.checked)">"#
+        ));
+        assert!(expanded.contains(r#"<slider min="0" max="1" step="0.01""#));
+        assert!(
+            expanded
+                .contains(r#"<input type="number" value="{{ res:GraphicsSettings.fps_limit }}""#)
+        );
+        assert!(expanded.contains(r#"<input value="{{ res:GraphicsSettings.profile_name }}""#));
+        assert!(expanded.contains(r#"<option value="HighQuality">High Quality</option>"#));
+        assert!(expanded.contains(r#"onchange="$set(res:GraphicsSettings.tint, $event.hex)""#));
+        assert!(expanded.contains("<p>Profile Name</p>"));
+    }
+
+    #[test]
+    fn expand_reflect_forms_drops_unknown_types() {
+        let html = r#"<body><reflect-form type="Missing" bind="res:Missing"/></body>"#;
+        assert_eq!(expand_reflect_forms(html, &registry()), "<body></body>");
+    }
+
+    #[test]
+    fn expand_reflect_forms_escapes_attributes_and_rejects_unsafe_binds() {
+        let html = r#"<reflect-form type="GraphicsSettings" bind="res:GraphicsSettings" id="a&quot; onclick=&quot;x" class="p&lt;b&gt;"></reflect-form>"#;
+        let expanded = expand_reflect_forms(html, &registry());
+        assert!(
+            expanded.starts_with(
+                r#"<div id="a&quot; onclick=&quot;x" class="reflect-form p&lt;b&gt;">"#
+            )
+        );
+
+        let html = r#"<reflect-form type="GraphicsSettings" bind="res:GraphicsSettings.x) onclick=(y"></reflect-form>"#;
+        assert_eq!(expand_reflect_forms(html, &registry()), "");
+    }
+
+    #[test]
+    fn reflect_form_diagnostics_locate_forms_that_are_dropped() {
+        let html = "<body>\n  <reflect-form type=\"GraphicsSettings\" bind=\"store.graphics\"/>\n  <reflect-form type=\"GraphicsSettings\" bind=\"list[0].key\"/>\n  <reflect-form type=\"Missing\" bind=\"res:Missing\"/>\n</body>";
+
+        let diagnostics = reflect_form_diagnostics(html, &registry(), "ui/settings.html");
+
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        assert!(diagnostics.iter().all(TemplateDiagnostic::is_error));
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 3));
+        assert!(
+            diagnostics[0]
+                .message
+                .contains("invalid 'bind' path 'list[0].key'")
+        );
+        assert_eq!(diagnostics[1].source_path, "ui/settings.html");
+        assert_eq!(diagnostics[1].line, 4);
+        assert!(
+            diagnostics[1]
+                .message
+                .contains("'Missing' is not a registered struct")
+        );
+    }
+
+    #[test]
+    fn reflected_colors_and_enums_round_trip_as_strings() {
+        let mut app = App::new();
+        app.register_type::<GraphicsSettings>();
+        app.register_type::<Quality>();
+        app.init_resource::<GraphicsSettings>();
+        let world = app.world_mut();
+
+        assert!(write_reflect_path(
+            world,
+            "res:GraphicsSettings.tint",
+            json!("#FF0000")
+        ));
+        assert!(write_reflect_path(
+            world,
+            "res:GraphicsSettings.quality",
+            json!("HighQuality")
+        ));
+        assert!(write_reflect_path(
+            world,
+            "res:GraphicsSettings.fps_limit",
+            json!(60.0)
+        ));
+        assert!(!write_reflect_path(
+            world,
+            "res:GraphicsSettings.missing",
+            json!(1)
+        ));
+
+        assert_eq!(
+            read_reflect_path(world, "res:GraphicsSettings.tint"),
+            Some(json!("#FF0000"))
+        );
+        let settings = world.resource::<GraphicsSettings>();
+        assert_eq!(settings.quality, Quality::HighQuality);
+        assert_eq!(settings.fps_limit, 60);
+    }
+}
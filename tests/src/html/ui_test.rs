@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::styles::{CssClass, CssID, CssSource};
+    use crate::widgets::{Body, Button, Div, Paragraph, UIWidgetState, Widget};
+    use bevy::asset::AssetPlugin;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::ecs::world::CommandQueue;
+    use bevy::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct Clicks(Vec<Entity>);
+
+    fn children_of(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Children>(entity)
+            .map(|children| children.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn ui_builder_produces_html_widget_nodes() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let node = {
+            let mut commands = Commands::new(&mut queue, &world);
+            ui::div()
+                .id("menu")
+                .class("row")
+                .class("wide row")
+                .style("width: 50px; height: 10px")
+                .controller("menu")
+                .disabled()
+                .child(ui::p("Title").hidden())
+                .child(ui::button("Play").on("onclick", "play"))
+                .into_widget_node(&mut commands)
+        };
+        queue.apply(&mut world);
+
+        let HtmlWidgetNode::Div(_, meta, states, children, _, widget, _) = node else {
+            panic!("expected a div node");
+        };
+        assert_eq!(meta.id.as_deref(), Some("menu"));
+        assert_eq!(
+            meta.class,
+            Some(vec!["row".to_string(), "wide".to_string()])
+        );
+        let style = meta.style.expect("inline style").0;
+        assert_eq!(style.width, Some(Val::Px(50.0)));
+        assert_eq!(style.height, Some(Val::Px(10.0)));
+        assert!(states.disabled);
+        assert_eq!(widget.0.as_deref(), Some("menu"));
+        assert_eq!(children.len(), 2);
+
+        let HtmlWidgetNode::Paragraph(paragraph, meta, states, _, widget, _) = &children[0] else {
+            panic!("expected a paragraph node");
+        };
+        assert_eq!(paragraph.text, "Title");
+        assert_eq!(meta.inner_content.inner_text(), "Title");
+        assert!(states.hidden);
+        assert_eq!(widget.0.as_deref(), Some("menu"));
+
+        let HtmlWidgetNode::Button(button, _, _, bindings, _, _) = &children[1] else {
+            panic!("expected a button node");
+        };
+        assert_eq!(button.text, "Play");
+        assert_eq!(bindings.onclick.as_deref(), Some("play"));
+    }
+
+    #[test]
+    fn ui_dom_spawns_builder_tree_with_typed_handlers() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_resource::<Clicks>();
+        let world = app.world_mut();
+
+        let body = world
+            .run_system_once(|mut dom: UiDom| {
+                dom.spawn(
+                    ui::body()
+                        .controller("menu")
+                        .child(ui::div().class("row").child(ui::button("Play").on_click(
+                            |In(event): In<HtmlClick>, mut clicks: ResMut<Clicks>| {
+                                clicks.0.push(event.entity);
+                            },
+                        ))),
+                )
+            })
+            .expect("system runs")
+            .expect("asset server exists");
+
+        assert!(world.get::<Body>(body).is_some());
+        let row = children_of(world, body)[0];
+        assert!(world.get::<Div>(row).is_some());
+        assert_eq!(world.get::<CssClass>(row).unwrap().0, vec!["row"]);
+        let play = children_of(world, row)[0];
+        assert_eq!(world.get::<Button>(play).unwrap().text, "Play");
+        assert_eq!(
+            world.get::<Widget>(play).unwrap().0.as_deref(),
+            Some("menu")
+        );
+
+        let handler = world
+            .get::<HtmlEventBindings>(play)
+            .unwrap()
+            .onclick
+            .clone()
+            .expect("onclick bound");
+        let system = world.resource::<HtmlFunctionRegistry>().click_typed[&handler];
+        world
+            .run_system_with(
+                system,
+                HtmlClick {
+                    entity: play,
+                    position: Vec2::ZERO,
+                    inner_position: Vec2::ZERO,
+                    propagation: default(),
+                },
+            )
+            .expect("handler runs");
+        assert_eq!(world.resource::<Clicks>().0, vec![play]);
+    }
+
+    #[test]
+    fn despawning_builder_widgets_unregisters_their_handlers() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.add_plugins(HtmlEventBindingsPlugin);
+        let world = app.world_mut();
+
+        let button = world
+            .run_system_once(|mut dom: UiDom| {
+                dom.spawn(ui::button("Play").on_click(|_: In<HtmlClick>| {}))
+            })
+            .expect("system runs")
+            .expect("asset server exists");
+        let handler = world
+            .get::<HtmlEventBindings>(button)
+            .unwrap()
+            .onclick
+            .clone()
+            .expect("onclick bound");
+        let system = world.resource::<HtmlFunctionRegistry>().click_typed[&handler];
+
+        world.despawn(button);
+        world.flush();
+
+        assert!(
+            !world
+                .resource::<HtmlFunctionRegistry>()
+                .click_typed
+                .contains_key(&handler)
+        );
+        assert!(
+            world
+                .run_system_with(
+                    system,
+                    HtmlClick {
+                        entity: button,
+                        position: Vec2::ZERO,
+                        inner_position: Vec2::ZERO,
+                        propagation: default(),
+                    },
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn ui_dom_spawn_without_asset_server_returns_none() {
+        let mut world = World::new();
+        let spawned = world
+            .run_system_once(|mut dom: UiDom| dom.spawn(ui::div()))
+            .expect("system runs");
+        assert_eq!(spawned, None);
+    }
+
+    #[test]
+    fn ui_dom_append_inherits_parent_css_and_controller() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        let world = app.world_mut();
+        let css = CssSource(vec![Handle::default()]);
+        let log = world
+            .spawn((
+                CssID("log".to_string()),
+                css.clone(),
+                Widget(Some("chat".to_string())),
+            ))
+            .id();
+
+        let line = world
+            .run_system_once(move |mut dom: UiDom| {
+                dom.append(log, ui::p("Hello").class("line").readonly())
+            })
+            .expect("system runs")
+            .expect("asset server exists");
+
+        assert_eq!(children_of(world, log), vec![line]);
+        assert_eq!(world.get::<Paragraph>(line).unwrap().text, "Hello");
+        assert_eq!(world.get::<CssSource>(line), Some(&css));
+        assert_eq!(
+            world.get::<Widget>(line).unwrap().0.as_deref(),
+            Some("chat")
+        );
+        assert!(world.get::<UIWidgetState>(line).unwrap().readonly);
+        assert!(world.get::<HtmlID>(line).is_none());
+    }
+}
@@ -1,5 +1,7 @@
 pub use bevy_extended_ui::*;
 
+#[cfg(feature = "extended-framework")]
+pub mod check;
 #[cfg(feature = "extended-framework")]
 pub mod component;
 #[cfg(feature = "extended-dialog")]
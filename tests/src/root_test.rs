@@ -18,6 +18,7 @@ mod tests {
             handle: Handle::default(),
             source_id: String::new(),
             controller: controller.map(str::to_string),
+            virtual_path: None,
         }
     }
 
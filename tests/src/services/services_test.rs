@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
     use super::super::css_service::{
-        CssService, CssUsers, collect_assets_with_changed_media_matches, matches_css_selector_token,
+        CssService, CssUsers, collect_assets_with_changed_media_matches,
+        matches_css_selector_token, matches_css_selector_token_with_attributes,
     };
     use super::super::image_service::{get_or_load_image, pre_load_assets};
     use super::super::state_service::{StateService, update_widget_states};
@@ -9,15 +10,21 @@ mod tests {
         LastUiTransform, StyleTransition, propagate_style_inheritance, sync_last_ui_transform,
         update_widget_styles_system,
     };
+    use crate::html::HtmlAttributes;
     use crate::io::CssAsset;
     use crate::styles::components::UiStyle;
+    use crate::styles::parser::load_css;
     use crate::styles::{
-        CssClass, CssID, CssSource, Style, StylePair, TagName, TextTransform, TransitionSpec,
+        ComputedCssVariables, CssClass, CssID, CssSource, CssVariables, Style, StylePair, TagName,
+        TextTransform, TransitionSpec,
     };
-    use crate::widgets::{BindToID, UIGenID, UIWidgetState};
+    use crate::widgets::{BindToID, InputField, UIGenID, UIWidgetState, ValidationRules};
     use crate::{CurrentWidgetState, ExtendedUiConfiguration, ImageCache};
     use bevy::asset::AssetPlugin;
+    use bevy::camera::NormalizedRenderTarget;
     use bevy::input::ButtonInput;
+    use bevy::picking::backend::HitData;
+    use bevy::picking::pointer::{Location, PointerButton, PointerId};
     use bevy::prelude::*;
     use bevy::text::{FontSize, LineHeight};
     use std::collections::{HashMap, HashSet};
@@ -79,6 +86,9 @@ mod tests {
                 checked: true,
                 open: false,
                 invalid: false,
+                drag_over: false,
+                pressed: true,
+                ..default()
             },
         ));
         let parent_id = parent.get::<UIGenID>().expect("UIGenID missing").get();
@@ -99,6 +109,7 @@ mod tests {
         assert!(child.disabled);
         assert!(child.readonly);
         assert!(child.checked);
+        assert!(child.pressed);
     }
 
     #[test]
@@ -249,6 +260,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn state_service_tracks_focus_visible_focus_within_and_pressed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(CurrentWidgetState::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.add_plugins(StateService);
+
+        let parent = app.world_mut().spawn(UIWidgetState::default()).id();
+        let child = app
+            .world_mut()
+            .spawn((
+                UIGenID::default(),
+                UIWidgetState {
+                    pressed: true,
+                    ..default()
+                },
+                ChildOf(parent),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Tab);
+        app.update();
+
+        let child_state = app.world().get::<UIWidgetState>(child).unwrap();
+        assert!(child_state.focused && child_state.focus_visible && child_state.focus_within);
+        assert!(!child_state.pressed);
+        assert!(
+            app.world()
+                .get::<UIWidgetState>(parent)
+                .unwrap()
+                .focus_within
+        );
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .reset(KeyCode::Tab);
+        app.world_mut()
+            .get_mut::<UIWidgetState>(child)
+            .unwrap()
+            .focused = false;
+        app.update();
+
+        let child_state = app.world().get::<UIWidgetState>(child).unwrap();
+        assert!(!child_state.focus_visible && !child_state.focus_within);
+        assert!(
+            !app.world()
+                .get::<UIWidgetState>(parent)
+                .unwrap()
+                .focus_within
+        );
+    }
+
+    #[test]
+    fn state_service_marks_pressed_for_the_primary_button_only() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(CurrentWidgetState::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.add_plugins(StateService);
+
+        let button = app.world_mut().spawn(UIWidgetState::default()).id();
+        let press = |app: &mut App, button_kind: PointerButton| {
+            app.world_mut().entity_mut(button).trigger(|entity| {
+                Pointer::new(
+                    PointerId::Mouse,
+                    Location {
+                        target: NormalizedRenderTarget::None {
+                            width: 800,
+                            height: 600,
+                        },
+                        position: Vec2::ZERO,
+                    },
+                    Press {
+                        button: button_kind,
+                        count: 1,
+                        hit: HitData {
+                            camera: Entity::PLACEHOLDER,
+                            depth: 0.0,
+                            position: None,
+                            normal: None,
+                            extra: None,
+                        },
+                    },
+                    entity,
+                )
+            });
+            app.world_mut().flush();
+        };
+        let pressed = |app: &App| app.world().get::<UIWidgetState>(button).unwrap().pressed;
+
+        press(&mut app, PointerButton::Secondary);
+        press(&mut app, PointerButton::Middle);
+        assert!(!pressed(&app));
+
+        press(&mut app, PointerButton::Primary);
+        assert!(pressed(&app));
+    }
+
+    #[test]
+    fn state_service_syncs_placeholder_and_required_states() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(CurrentWidgetState::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.add_plugins(StateService);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                InputField {
+                    placeholder: "Your name".to_string(),
+                    ..default()
+                },
+                ValidationRules {
+                    required: true,
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+
+        let state = app.world().get::<UIWidgetState>(entity).unwrap();
+        assert!(state.placeholder_shown && state.required);
+
+        app.world_mut().get_mut::<InputField>(entity).unwrap().text = "Ada".to_string();
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<ValidationRules>();
+        app.update();
+
+        let state = app.world().get::<UIWidgetState>(entity).unwrap();
+        assert!(!state.placeholder_shown && !state.required);
+    }
+
     #[test]
     fn get_or_load_image_returns_cached_handle_on_second_call() {
         let mut app = App::new();
@@ -475,6 +625,284 @@ mod tests {
         }
     }
 
+    /// Adds a stylesheet under `handle` for tests that run the `CssService`.
+    ///
+    /// Parsed CSS is cached per asset id across apps, so these tests use a
+    /// UUID id instead of an index that tests running in parallel reuse.
+    fn insert_css(app: &mut App, handle: Handle<CssAsset>, text: &str) -> Handle<CssAsset> {
+        app.world_mut()
+            .resource_mut::<Assets<CssAsset>>()
+            .insert(
+                &handle,
+                CssAsset {
+                    text: text.to_string(),
+                },
+            )
+            .expect("uuid ids always insert");
+        handle
+    }
+
+    #[test]
+    fn css_service_applies_sibling_combinators_and_sibling_states() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), CssService));
+        app.init_asset::<CssAsset>();
+
+        let css = insert_css(
+            &mut app,
+            bevy::asset::uuid_handle!("5f0c1e8a-2b47-4d7e-9a3c-6e1f0b2d4c85"),
+            ".label + .input { width: 10px; } \
+             input:checked ~ .panel { height: 20px; } \
+             .label + .panel { width: 30px; }",
+        );
+        let spawn_child = |world: &mut World, tag: &str, class: &str| {
+            world
+                .spawn((
+                    CssSource(vec![css.clone()]),
+                    TagName(tag.to_string()),
+                    CssClass(vec![class.to_string()]),
+                    UIWidgetState::default(),
+                ))
+                .id()
+        };
+        let world = app.world_mut();
+        let label = spawn_child(world, "p", "label");
+        let input = spawn_child(world, "input", "input");
+        let panel = spawn_child(world, "div", "panel");
+        world.spawn_empty().add_children(&[label, input, panel]);
+        app.update();
+
+        let selectors = |app: &App, entity: Entity| -> Vec<String> {
+            let mut keys: Vec<String> = app
+                .world()
+                .get::<UiStyle>(entity)
+                .map(|style| style.styles.keys().cloned().collect())
+                .unwrap_or_default();
+            keys.sort();
+            keys
+        };
+        assert_eq!(selectors(&app, input), vec![".label + .input"]);
+        assert!(selectors(&app, panel).is_empty());
+
+        app.world_mut()
+            .get_mut::<UIWidgetState>(input)
+            .unwrap()
+            .checked = true;
+        app.update();
+
+        assert_eq!(selectors(&app, panel), vec!["input:checked ~ .panel"]);
+    }
+
+    #[test]
+    fn css_service_reapplies_structural_selectors_when_siblings_change() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), CssService));
+        app.init_asset::<CssAsset>();
+
+        let css = insert_css(
+            &mut app,
+            bevy::asset::uuid_handle!("b83d2f61-7c94-4e0a-8d15-3a9e6c07f2b4"),
+            ".row:last-child { width: 10px; } \
+             .list:has(.row) { height: 5px; }",
+        );
+        let spawn_styled = |world: &mut World, tag: &str, class: &str| {
+            world
+                .spawn((
+                    CssSource(vec![css.clone()]),
+                    TagName(tag.to_string()),
+                    CssClass(vec![class.to_string()]),
+                ))
+                .id()
+        };
+        let world = app.world_mut();
+        let list = spawn_styled(world, "div", "list");
+        let first = spawn_styled(world, "p", "row");
+        world.entity_mut(list).add_child(first);
+        app.update();
+
+        let matched = |app: &App, entity: Entity| -> Vec<String> {
+            app.world()
+                .get::<UiStyle>(entity)
+                .map(|style| style.styles.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        assert_eq!(matched(&app, first), vec![".row:last-child"]);
+        assert_eq!(matched(&app, list), vec![".list:has(.row)"]);
+
+        let world = app.world_mut();
+        let second = spawn_styled(world, "p", "row");
+        world.entity_mut(list).add_child(second);
+        app.update();
+
+        assert!(matched(&app, first).is_empty());
+        assert_eq!(matched(&app, second), vec![".row:last-child"]);
+
+        app.world_mut().entity_mut(second).despawn();
+        app.world_mut().entity_mut(first).despawn();
+        app.update();
+
+        assert!(matched(&app, list).is_empty());
+    }
+
+    #[test]
+    fn css_service_reapplies_has_selectors_when_descendant_state_changes() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), CssService));
+        app.init_asset::<CssAsset>();
+
+        let css = insert_css(
+            &mut app,
+            bevy::asset::uuid_handle!("2e6a9c41-d8f3-4b07-a5e2-91c4f7b036d8"),
+            ".card:has(input:checked) { height: 5px; }",
+        );
+        let world = app.world_mut();
+        let card = world
+            .spawn((
+                CssSource(vec![css.clone()]),
+                TagName("div".to_string()),
+                CssClass(vec!["card".to_string()]),
+            ))
+            .id();
+        let checkbox = world
+            .spawn((
+                CssSource(vec![css.clone()]),
+                TagName("input".to_string()),
+                UIWidgetState::default(),
+            ))
+            .id();
+        world.entity_mut(card).add_child(checkbox);
+        app.update();
+
+        let matched = |app: &App| -> Vec<String> {
+            app.world()
+                .get::<UiStyle>(card)
+                .map(|style| style.styles.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        assert!(matched(&app).is_empty());
+
+        app.world_mut()
+            .get_mut::<UIWidgetState>(checkbox)
+            .unwrap()
+            .checked = true;
+        app.update();
+        assert_eq!(matched(&app), vec![".card:has(input:checked)"]);
+
+        app.world_mut()
+            .get_mut::<UIWidgetState>(checkbox)
+            .unwrap()
+            .checked = false;
+        app.update();
+        assert!(matched(&app).is_empty());
+    }
+
+    #[test]
+    fn css_service_matches_enabled_and_valid_on_form_controls_only() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), CssService));
+        app.init_asset::<CssAsset>();
+
+        let css = insert_css(
+            &mut app,
+            bevy::asset::uuid_handle!("c4f18b27-6e5a-4d93-8b0e-2a7d95e1c6f3"),
+            ".x:enabled { width: 10px; } .x:valid { height: 10px; }",
+        );
+        let mut spawn_tagged = |tag: &str| {
+            app.world_mut()
+                .spawn((
+                    CssSource(vec![css.clone()]),
+                    TagName(tag.to_string()),
+                    CssClass(vec!["x".to_string()]),
+                    UIWidgetState::default(),
+                ))
+                .id()
+        };
+        let div = spawn_tagged("div");
+        let input = spawn_tagged("input");
+        let form = spawn_tagged("form");
+        app.update();
+
+        let matched = |app: &App, entity: Entity| -> Vec<String> {
+            let mut keys: Vec<String> = app
+                .world()
+                .get::<UiStyle>(entity)
+                .map(|style| style.styles.keys().cloned().collect())
+                .unwrap_or_default();
+            keys.sort();
+            keys
+        };
+        assert!(matched(&app, div).is_empty());
+        assert_eq!(matched(&app, input), vec![".x:enabled", ".x:valid"]);
+        assert_eq!(matched(&app, form), vec![".x:valid"]);
+    }
+
+    #[test]
+    fn state_pseudo_classes_inside_is_and_not_follow_the_widget_state() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), CssService));
+        app.init_asset::<CssAsset>();
+        app.init_asset::<Image>();
+        app.insert_resource(ImageCache::default());
+        app.add_systems(PostUpdate, update_widget_styles_system);
+
+        let css = insert_css(
+            &mut app,
+            bevy::asset::uuid_handle!("7b1d4e92-3c08-4f6a-b2d5-e8a0c6f19374"),
+            ".btn:is(:hover, :focus-visible) { width: 10px; } \
+             .btn:not(:disabled, .ghost) { height: 20px; } \
+             .x:not(.primary:hover) { min-width: 30px; } \
+             .x:not(:hover:focus) { min-height: 40px; }",
+        );
+        let mut spawn_styled = |classes: &[&str]| {
+            app.world_mut()
+                .spawn((
+                    CssSource(vec![css.clone()]),
+                    TagName("div".to_string()),
+                    CssClass(classes.iter().map(|class| class.to_string()).collect()),
+                    UIWidgetState::default(),
+                    Node::default(),
+                ))
+                .id()
+        };
+        let button = spawn_styled(&["btn"]);
+        let ghost = spawn_styled(&["btn", "ghost"]);
+        let plain = spawn_styled(&["x"]);
+        let primary = spawn_styled(&["x", "primary"]);
+        app.update();
+
+        let active = |app: &App, entity: Entity| -> Style {
+            app.world()
+                .get::<UiStyle>(entity)
+                .and_then(|style| style.active_style.clone())
+                .unwrap_or_default()
+        };
+        let set_state = |app: &mut App, entity: Entity, edit: fn(&mut UIWidgetState)| {
+            edit(&mut app.world_mut().get_mut::<UIWidgetState>(entity).unwrap());
+            app.update();
+        };
+
+        assert_eq!(active(&app, button).width, None);
+        assert_eq!(active(&app, button).height, Some(Val::Px(20.0)));
+        assert_eq!(active(&app, ghost).height, None);
+
+        set_state(&mut app, ghost, |state| state.hovered = true);
+        assert_eq!(active(&app, ghost).width, Some(Val::Px(10.0)));
+        assert_eq!(active(&app, ghost).height, None);
+
+        set_state(&mut app, button, |state| state.disabled = true);
+        assert_eq!(active(&app, button).height, None);
+
+        set_state(&mut app, plain, |state| state.hovered = true);
+        assert_eq!(active(&app, plain).min_width, Some(Val::Px(30.0)));
+        assert_eq!(active(&app, plain).min_height, Some(Val::Px(40.0)));
+        set_state(&mut app, plain, |state| state.focused = true);
+        assert_eq!(active(&app, plain).min_height, None);
+
+        assert_eq!(active(&app, primary).min_width, Some(Val::Px(30.0)));
+        set_state(&mut app, primary, |state| state.hovered = true);
+        assert_eq!(active(&app, primary).min_width, None);
+    }
+
     #[test]
     fn css_service_matches_compound_selectors_and_suffixes() {
         let id = CssID("main-card".to_string());
@@ -504,11 +932,14 @@ mod tests {
             Some(&classes),
             Some(&tag)
         ));
-        assert!(matches_css_selector_token(
+        let mut attributes = HtmlAttributes::default();
+        attributes.set("data-kind", "main");
+        assert!(matches_css_selector_token_with_attributes(
             ".btn.primary[data-kind='main']",
             Some(&id),
             Some(&classes),
-            Some(&tag)
+            Some(&tag),
+            Some(&attributes)
         ));
     }
 
@@ -592,7 +1023,7 @@ mod tests {
     }
 
     #[test]
-    fn css_service_ignores_pseudo_and_attribute_suffixes() {
+    fn css_service_ignores_pseudo_suffixes_but_checks_attributes() {
         let id = CssID("cta".to_string());
         let classes = CssClass(vec!["btn".to_string(), "primary".to_string()]);
         let tag = TagName("button".to_string());
@@ -603,7 +1034,7 @@ mod tests {
             Some(&classes),
             Some(&tag)
         ));
-        assert!(matches_css_selector_token(
+        assert!(!matches_css_selector_token(
             ".btn.primary[data-kind='main']",
             Some(&id),
             Some(&classes),
@@ -611,6 +1042,48 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn css_service_matches_attribute_selector_operators() {
+        let tag = TagName("input".to_string());
+        let mut attributes = HtmlAttributes::default();
+        attributes.set("type", "password");
+        attributes.set("disabled", "");
+        attributes.set("data-tags", "rare quest-item");
+        attributes.set("lang", "en-US");
+        attributes.set("Data-Kind", "Main");
+        let matches = |selector: &str| {
+            matches_css_selector_token_with_attributes(
+                selector,
+                None,
+                None,
+                Some(&tag),
+                Some(&attributes),
+            )
+        };
+
+        assert!(matches("[disabled]"));
+        assert!(matches("input[type=password]"));
+        assert!(matches("input[type=\"password\"]:focus"));
+        assert!(matches("[type^='pass'][type$=word][type*=ssw]"));
+        assert!(matches("[data-tags~=rare]"));
+        assert!(matches("[lang|=en]"));
+        assert!(matches("[DATA-KIND='main' i]"));
+
+        assert!(!matches("[readonly]"));
+        assert!(!matches("input[type=text]"));
+        assert!(!matches("[data-tags~=quest]"));
+        assert!(!matches("[type^='']"));
+        assert!(!matches("[data-kind=main]"));
+        assert!(!matches("[lang|=e]"));
+        assert!(!matches("[type=password"));
+        assert!(!matches_css_selector_token(
+            "[disabled]",
+            None,
+            None,
+            Some(&tag)
+        ));
+    }
+
     #[test]
     fn sync_last_ui_transform_inserts_and_updates_cached_transform() {
         let mut app = App::new();
@@ -909,6 +1382,145 @@ mod tests {
         assert_eq!(text_color.0, Color::srgb(1.0, 0.5, 0.0));
     }
 
+    #[test]
+    fn update_widget_styles_system_applies_active_and_focus_visible_pseudo_styles() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Image>();
+        app.insert_resource(ImageCache::default());
+        app.add_systems(Update, update_widget_styles_system);
+
+        let base = Color::srgb(0.2, 0.2, 1.0);
+        let active = Color::srgb(1.0, 0.5, 0.0);
+        let focus_visible = Color::srgb(1.0, 0.0, 0.0);
+
+        let mut styles = HashMap::new();
+        for (selector, color) in [
+            ("button", base),
+            ("button:enabled:active", active),
+            ("button:focus-visible", focus_visible),
+        ] {
+            let mut style = Style::default();
+            style.color = Some(color);
+            styles.insert(
+                selector.to_string(),
+                StylePair {
+                    normal: style,
+                    selector: selector.to_string(),
+                    ..default()
+                },
+            );
+        }
+
+        let mut spawn = |state: UIWidgetState| {
+            app.world_mut()
+                .spawn((
+                    UiStyle {
+                        css: Handle::default(),
+                        styles: styles.clone(),
+                        keyframes: HashMap::new(),
+                        active_style: None,
+                    },
+                    state,
+                    Node::default(),
+                    TextColor(Color::NONE),
+                ))
+                .id()
+        };
+
+        let pressed = spawn(UIWidgetState {
+            pressed: true,
+            ..default()
+        });
+        let pointer_focused = spawn(UIWidgetState {
+            focused: true,
+            ..default()
+        });
+        let keyboard_focused = spawn(UIWidgetState {
+            focused: true,
+            focus_visible: true,
+            ..default()
+        });
+        let pressed_disabled = spawn(UIWidgetState {
+            pressed: true,
+            disabled: true,
+            ..default()
+        });
+
+        app.update();
+
+        let color = |entity: Entity| app.world().get::<TextColor>(entity).unwrap().0;
+        assert_eq!(color(pressed), active);
+        assert_eq!(color(pointer_focused), base);
+        assert_eq!(color(keyboard_focused), focus_visible);
+        assert_eq!(color(pressed_disabled), base);
+    }
+
+    #[test]
+    fn update_widget_styles_system_resolves_scoped_css_variables() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Image>();
+        app.insert_resource(ImageCache::default());
+        app.add_systems(Update, update_widget_styles_system);
+
+        let parsed = load_css(".zone { --w: 40px; } .bar { width: var(--w, 10px); }");
+        let ui_style = |selector: &str| {
+            let mut ui_style = empty_ui_style();
+            ui_style.styles = parsed
+                .styles
+                .iter()
+                .filter(|(_, pair)| pair.selector == selector)
+                .map(|(key, pair)| (key.clone(), pair.clone()))
+                .collect();
+            ui_style
+        };
+
+        let parent = app
+            .world_mut()
+            .spawn((ui_style(".zone"), Node::default()))
+            .id();
+        let child = app
+            .world_mut()
+            .spawn((ui_style(".bar"), Node::default(), ChildOf(parent)))
+            .id();
+
+        let width = |app: &App| {
+            app.world()
+                .get::<UiStyle>(child)
+                .and_then(|style| style.active_style.as_ref())
+                .and_then(|style| style.width)
+        };
+
+        app.update();
+        assert_eq!(width(&app), Some(Val::Px(40.0)));
+        assert_eq!(
+            app.world()
+                .get::<ComputedCssVariables>(parent)
+                .and_then(|vars| vars.0.get("--w")),
+            Some(&"40px".to_string())
+        );
+        assert!(app.world().get::<ComputedCssVariables>(child).is_none());
+
+        app.world_mut()
+            .entity_mut(parent)
+            .insert(CssVariables(HashMap::from([(
+                "--w".to_string(),
+                "70px".to_string(),
+            )])));
+        app.update();
+        assert_eq!(width(&app), Some(Val::Px(70.0)));
+
+        app.world_mut()
+            .entity_mut(child)
+            .insert(CssVariables(HashMap::from([(
+                "--w".to_string(),
+                "25px".to_string(),
+            )])));
+        app.update();
+        assert_eq!(width(&app), Some(Val::Px(25.0)));
+    }
+
     #[test]
     fn update_widget_styles_system_applies_line_height_from_css() {
         let mut app = App::new();
@@ -297,6 +297,59 @@ mod tests {
         assert_eq!(background.color, Color::srgb_u8(0x11, 0x22, 0x33));
     }
 
+    #[test]
+    fn records_scoped_custom_properties_for_runtime_resolution() {
+        let parsed = load_css(
+            r#"
+            :root { --hp: 100%; --gap: 4px; }
+            .danger-zone { --accent: red; }
+            .bar {
+                width: calc(var(--hp) - var(--gap));
+                background: var(--accent, #000);
+            }
+        "#,
+        );
+
+        let find = |selector: &str| {
+            parsed
+                .styles
+                .values()
+                .find(|pair| pair.selector == selector)
+                .unwrap_or_else(|| panic!("missing parsed {selector} style"))
+        };
+
+        let root = find(":root");
+        assert_eq!(
+            root.normal.vars.properties.get("--hp"),
+            Some(&"100%".to_string())
+        );
+        let zone = find(".danger-zone");
+        assert_eq!(
+            zone.normal.vars.properties.get("--accent"),
+            Some(&"red".to_string())
+        );
+
+        let bar = find(".bar");
+        assert_eq!(bar.normal.vars.declarations.len(), 2);
+        assert!(bar.normal.width_calc.is_some());
+
+        let mut vars = HashMap::new();
+        vars.insert("--hp".to_string(), "40%".to_string());
+        vars.insert("--gap".to_string(), "4px".to_string());
+        vars.insert("--accent".to_string(), "red".to_string());
+
+        let resolved =
+            resolve_style_vars(&bar.normal, &vars).expect("scoped vars change the style");
+        assert!(resolved.width_calc.is_some());
+        assert_ne!(resolved.width_calc, bar.normal.width_calc);
+
+        let mut expected = Style::default();
+        apply_property_to_style(&mut expected, "background", "red");
+        assert_eq!(resolved.background, expected.background);
+
+        assert!(resolve_style_vars(&bar.normal, &HashMap::new()).is_none());
+    }
+
     #[test]
     fn parses_body_flex_layout_properties() {
         let parsed = load_css(